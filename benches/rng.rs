@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 use rand_aes::tls::{rand_fill_bytes, rand_seed_from_entropy, rand_u64};
-use rand_aes::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64};
+use rand_aes::{Aes128Ctr128, Aes128Ctr128R, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64};
 use rand_core::{RngCore, SeedableRng};
 
 fn criterion_benchmark(c: &mut Criterion) {
@@ -12,6 +12,7 @@ fn criterion_benchmark(c: &mut Criterion) {
     let mut aes128_128: Aes128Ctr128 = Aes128Ctr128::seed_from_u64(seed);
     let mut aes256_64: Aes256Ctr64 = Aes256Ctr64::seed_from_u64(seed);
     let mut aes256_128: Aes256Ctr128 = Aes256Ctr128::seed_from_u64(seed);
+    let mut aes128_128_r5: Aes128Ctr128R<5> = Aes128Ctr128R::seed_from_u64(seed);
     rand_seed_from_entropy();
 
     let mut cha_cha8 = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
@@ -48,6 +49,12 @@ fn criterion_benchmark(c: &mut Criterion) {
             black_box(x);
         })
     });
+    group.bench_function("Aes128Ctr128R5", |b| {
+        b.iter(|| {
+            x = x.wrapping_add(aes128_128_r5.next_u64());
+            black_box(x);
+        })
+    });
     group.bench_function("TLS", |b| {
         b.iter(|| {
             x = x.wrapping_add(rand_u64());
@@ -103,6 +110,9 @@ fn criterion_benchmark(c: &mut Criterion) {
     group.bench_function("Aes256Ct128", |b| {
         b.iter(|| aes256_128.fill_bytes(&mut buffer))
     });
+    group.bench_function("Aes128Ctr128R5", |b| {
+        b.iter(|| aes128_128_r5.fill_bytes(&mut buffer))
+    });
     group.bench_function("TLS", |b| b.iter(|| rand_fill_bytes(&mut buffer)));
     group.bench_function("ChaCha8", |b| b.iter(|| cha_cha8.fill_bytes(&mut buffer)));
     group.bench_function("ChaCha12", |b| b.iter(|| cha_cha12.fill_bytes(&mut buffer)));
@@ -1,9 +1,11 @@
 use core::{arch::aarch64::*, cell::Cell};
 
+use crate::backend::leftover::Leftover;
 use crate::constants::{
-    AES128_KEY_COUNT, AES128_KEY_SIZE, AES256_KEY_COUNT, AES256_KEY_SIZE, AES_BLOCK_WORDS,
-    AES_RCON, AES_WORD_SIZE,
+    AES128_KEY_COUNT, AES128_KEY_SIZE, AES128_ROUNDS, AES256_KEY_COUNT, AES256_KEY_SIZE,
+    AES256_ROUNDS, AES_BLOCK_WORDS, AES_RCON, AES_WORD_SIZE,
 };
+use crate::exhaustion::Exhaustion;
 
 // Compile-time checks to verify that some casts are sound.
 const _: () = assert!(size_of::<uint8x16_t>() == size_of::<u128>());
@@ -13,22 +15,26 @@ const _: () = assert!(align_of::<uint8x16_t>() >= align_of::<u32>());
 /// A random number generator based on the AES-128 block cipher that runs in CTR mode and has a
 /// period of 64-bit.
 ///
-/// The full 10 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`Aes128Ctr64`](crate::Aes128Ctr64) is the variant with the full 10 rounds.
 #[derive(Clone)]
-pub struct Aes128Ctr64 {
+pub struct Aes128Ctr64R<const R: usize> {
     counter: Cell<uint64x2_t>,
     round_keys: Cell<[uint8x16_t; AES128_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes128Ctr64 {
+impl<const R: usize> Drop for Aes128Ctr64R<R> {
     fn drop(&mut self) {
         self.counter.set(unsafe { core::mem::zeroed() });
         self.round_keys.set(unsafe { core::mem::zeroed() });
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes128Ctr64 {
+impl<const R: usize> Aes128Ctr64R<R> {
     #[cfg(all(
         feature = "tls",
         not(any(
@@ -41,12 +47,21 @@ impl Aes128Ctr64 {
         Self {
             counter: Cell::new(unsafe { core::mem::zeroed() }),
             round_keys: Cell::new(unsafe { core::mem::zeroed() }),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
     pub(crate) unsafe fn from_seed_impl(key: [u8; 16], nonce: [u8; 8], counter: [u8; 8]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
         let counter =
             ((u64::from_le_bytes(nonce) as u128) << 64) + u64::from_le_bytes(counter) as u128;
         let counter = vreinterpretq_u64_u8(vld1q_u8(counter.to_le_bytes().as_ptr().cast()));
@@ -56,6 +71,8 @@ impl Aes128Ctr64 {
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -70,18 +87,52 @@ impl Aes128Ctr64 {
             aes_key_expansion::<AES128_KEY_SIZE, AES128_KEY_COUNT>(key);
 
         self.counter.set(counter);
-        self.round_keys.set(round_keys)
+        self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        // The first round key is the key itself.
+        unsafe { core::mem::transmute(self.round_keys.get()[0]) }
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         let bytes: [u8; 16] = unsafe { *(&self.counter.get() as *const uint64x2_t as *const _) };
         u128::from_le_bytes(bytes) as u64
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        let mut lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[0] = counter;
+        self.counter
+            .set(unsafe { core::mem::transmute::<[u64; 2], uint64x2_t>(lanes) });
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        let lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[1]
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        let mut lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[1] = stream;
+        self.counter
+            .set(unsafe { core::mem::transmute::<[u64; 2], uint64x2_t>(lanes) });
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
     #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
@@ -98,73 +149,124 @@ impl Aes128Ctr64 {
         let rks = &*((&self.round_keys) as *const Cell<[_; AES128_KEY_COUNT]>
             as *const [Cell<_>; AES128_KEY_COUNT]);
 
-        // We apply the AES encryption on the counter.
+        // We apply the R rounds of AES encryption on the counter.
         let mut state = vreinterpretq_u8_u64(counter);
-        state = vaesmcq_u8(vaeseq_u8(state, rks[0].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[1].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[2].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[3].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[4].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[5].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[6].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[7].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[8].get()));
-        state = vaeseq_u8(state, rks[9].get());
-        state = veorq_u8(state, rks[10].get());
+        for rk in &rks[..R - 1] {
+            state = vaesmcq_u8(vaeseq_u8(state, rk.get()));
+        }
+        state = vaeseq_u8(state, rks[R - 1].get());
+        state = veorq_u8(state, rks[R].get());
 
         // Return the encrypted counter as u128.
         *(&state as *const uint8x16_t as *const u128)
     }
+
+    #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        // SAFETY: `Cell<T>` has the same memory layout as `T`.
+        // Use `as_array_of_cells` once stable: https://github.com/rust-lang/rust/issues/88248
+        let rks = &*((&self.round_keys) as *const Cell<[_; AES128_KEY_COUNT]>
+            as *const [Cell<_>; AES128_KEY_COUNT]);
+
+        let mut counter = self.counter.get();
+
+        // Create the counters, while incrementing the lower 64 bits using SIMD.
+        let increment = vsetq_lane_u64::<0>(1, vmovq_n_u64(0));
+        let mut state = [vdupq_n_u8(0); N];
+        for block in state.iter_mut() {
+            *block = vreinterpretq_u8_u64(counter);
+            counter = vaddq_u64(counter, increment);
+        }
+        self.counter.set(counter);
+
+        // We apply the AES encryption round by round on all blocks, so that the independent
+        // blocks can be pipelined by the CPU.
+        for rk in &rks[..R - 1] {
+            let rk = rk.get();
+            for block in state.iter_mut() {
+                *block = vaesmcq_u8(vaeseq_u8(*block, rk));
+            }
+        }
+        let rk = rks[R - 1].get();
+        let last_rk = rks[R].get();
+        for block in state.iter_mut() {
+            *block = veorq_u8(vaeseq_u8(*block, rk), last_rk);
+        }
+
+        // Return the encrypted counters as u128.
+        state.map(|block| *(&block as *const uint8x16_t as *const u128))
+    }
 }
 
 /// A random number generator based on the AES-128 block cipher thar runs in CTR mode and has a
 /// period of 128-bit.
 ///
-/// The full 10 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`Aes128Ctr128`](crate::Aes128Ctr128) is the variant with the full 10 rounds.
 #[derive(Clone)]
-pub struct Aes128Ctr128 {
+pub struct Aes128Ctr128R<const R: usize> {
     counter: Cell<u128>,
     round_keys: Cell<[uint8x16_t; AES128_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes128Ctr128 {
+impl<const R: usize> Drop for Aes128Ctr128R<R> {
     fn drop(&mut self) {
         self.counter.set(0);
         self.round_keys.set(unsafe { core::mem::zeroed() });
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes128Ctr128 {
+impl<const R: usize> Aes128Ctr128R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes128_ctr128"))]
     pub(crate) const fn zeroed() -> Self {
         Self {
             counter: Cell::new(0),
             round_keys: Cell::new(unsafe { core::mem::zeroed() }),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     pub(crate) fn jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 64));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     pub(crate) fn long_jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 96));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
     pub(crate) unsafe fn from_seed_impl(key: [u8; 16], counter: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
         let counter = u128::from_le_bytes(counter);
         let round_keys: [uint8x16_t; AES128_KEY_COUNT] =
             aes_key_expansion::<AES128_KEY_SIZE, AES128_KEY_COUNT>(key);
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -176,17 +278,36 @@ impl Aes128Ctr128 {
             aes_key_expansion::<AES128_KEY_SIZE, AES128_KEY_COUNT>(key);
 
         self.counter.set(counter);
-        self.round_keys.set(round_keys)
+        self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        // The first round key is the key itself.
+        unsafe { core::mem::transmute(self.round_keys.get()[0]) }
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         self.counter.get()
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        self.counter.set(counter);
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
     #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
@@ -199,55 +320,99 @@ impl Aes128Ctr128 {
         let rks = &*((&self.round_keys) as *const Cell<[_; AES128_KEY_COUNT]>
             as *const [Cell<_>; AES128_KEY_COUNT]);
 
-        // We apply the AES encryption on the whitened counter.
+        // We apply the R rounds of AES encryption on the counter.
         let mut state = vld1q_u8(counter.to_le_bytes().as_ptr().cast());
-        state = vaesmcq_u8(vaeseq_u8(state, rks[0].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[1].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[2].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[3].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[4].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[5].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[6].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[7].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[8].get()));
-        state = vaeseq_u8(state, rks[9].get());
-        state = veorq_u8(state, rks[10].get());
+        for rk in &rks[..R - 1] {
+            state = vaesmcq_u8(vaeseq_u8(state, rk.get()));
+        }
+        state = vaeseq_u8(state, rks[R - 1].get());
+        state = veorq_u8(state, rks[R].get());
 
         // Return the encrypted counter as u128.
         *(&state as *const uint8x16_t as *const u128)
     }
+
+    #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        // SAFETY: `Cell<T>` has the same memory layout as `T`.
+        // Use `as_array_of_cells` once stable: https://github.com/rust-lang/rust/issues/88248
+        let rks = &*((&self.round_keys) as *const Cell<[_; AES128_KEY_COUNT]>
+            as *const [Cell<_>; AES128_KEY_COUNT]);
+
+        let counter = self.counter.get();
+        self.counter.set(counter.wrapping_add(N as u128));
+
+        // Create the counters.
+        let mut state = [vdupq_n_u8(0); N];
+        for (i, block) in state.iter_mut().enumerate() {
+            let counter = counter.wrapping_add(i as u128);
+            *block = vld1q_u8(counter.to_le_bytes().as_ptr().cast());
+        }
+
+        // We apply the AES encryption round by round on all blocks, so that the independent
+        // blocks can be pipelined by the CPU.
+        for rk in &rks[..R - 1] {
+            let rk = rk.get();
+            for block in state.iter_mut() {
+                *block = vaesmcq_u8(vaeseq_u8(*block, rk));
+            }
+        }
+        let rk = rks[R - 1].get();
+        let last_rk = rks[R].get();
+        for block in state.iter_mut() {
+            *block = veorq_u8(vaeseq_u8(*block, rk), last_rk);
+        }
+
+        // Return the encrypted counters as u128.
+        state.map(|block| *(&block as *const uint8x16_t as *const u128))
+    }
 }
 
 /// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
 /// period of 64-bit.
 ///
-/// The full 14 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`Aes256Ctr64`](crate::Aes256Ctr64) is the variant with the full 14 rounds.
 #[derive(Clone)]
-pub struct Aes256Ctr64 {
+pub struct Aes256Ctr64R<const R: usize> {
     counter: Cell<uint64x2_t>,
     round_keys: Cell<[uint8x16_t; AES256_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes256Ctr64 {
+impl<const R: usize> Drop for Aes256Ctr64R<R> {
     fn drop(&mut self) {
         self.counter.set(unsafe { core::mem::zeroed() });
         self.round_keys.set(unsafe { core::mem::zeroed() });
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes256Ctr64 {
+impl<const R: usize> Aes256Ctr64R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes256_ctr64"))]
     pub(crate) const fn zeroed() -> Self {
         Self {
             counter: Cell::new(unsafe { core::mem::zeroed() }),
             round_keys: Cell::new(unsafe { core::mem::zeroed() }),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
     pub(crate) unsafe fn from_seed_impl(key: [u8; 32], nonce: [u8; 8], counter: [u8; 8]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
         let counter =
             ((u64::from_le_bytes(nonce) as u128) << 64) + u64::from_le_bytes(counter) as u128;
         let counter = vreinterpretq_u64_u8(vld1q_u8(counter.to_le_bytes().as_ptr().cast()));
@@ -257,6 +422,8 @@ impl Aes256Ctr64 {
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -270,18 +437,53 @@ impl Aes256Ctr64 {
             aes_key_expansion::<AES256_KEY_SIZE, AES256_KEY_COUNT>(key);
 
         self.counter.set(counter);
-        self.round_keys.set(round_keys)
+        self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        // The first two round keys are the key itself.
+        let round_keys = self.round_keys.get();
+        unsafe { core::mem::transmute([round_keys[0], round_keys[1]]) }
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         let bytes: [u8; 16] = unsafe { *(&self.counter.get() as *const uint64x2_t as *const _) };
         u128::from_le_bytes(bytes) as u64
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        let mut lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[0] = counter;
+        self.counter
+            .set(unsafe { core::mem::transmute::<[u64; 2], uint64x2_t>(lanes) });
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        let lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[1]
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        let mut lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[1] = stream;
+        self.counter
+            .set(unsafe { core::mem::transmute::<[u64; 2], uint64x2_t>(lanes) });
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
     #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
@@ -297,77 +499,124 @@ impl Aes256Ctr64 {
         let rks = &*((&self.round_keys) as *const Cell<[_; AES256_KEY_COUNT]>
             as *const [Cell<_>; AES256_KEY_COUNT]);
 
-        // We apply the AES encryption on the counter.
+        // We apply the R rounds of AES encryption on the counter.
         let mut state = vreinterpretq_u8_u64(counter);
-        state = vaesmcq_u8(vaeseq_u8(state, rks[0].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[1].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[2].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[3].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[4].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[5].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[6].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[7].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[8].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[9].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[10].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[11].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[12].get()));
-        state = vaeseq_u8(state, rks[13].get());
-        state = veorq_u8(state, rks[14].get());
+        for rk in &rks[..R - 1] {
+            state = vaesmcq_u8(vaeseq_u8(state, rk.get()));
+        }
+        state = vaeseq_u8(state, rks[R - 1].get());
+        state = veorq_u8(state, rks[R].get());
 
         // Return the encrypted counter as u128.
         *(&state as *const uint8x16_t as *const u128)
     }
+
+    #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        // SAFETY: `Cell<T>` has the same memory layout as `T`.
+        // Use `as_array_of_cells` once stable: https://github.com/rust-lang/rust/issues/88248
+        let rks = &*((&self.round_keys) as *const Cell<[_; AES256_KEY_COUNT]>
+            as *const [Cell<_>; AES256_KEY_COUNT]);
+
+        let mut counter = self.counter.get();
+
+        // Create the counters, while incrementing the lower 64 bits using SIMD.
+        let increment = vsetq_lane_u64::<0>(1, vmovq_n_u64(0));
+        let mut state = [vdupq_n_u8(0); N];
+        for block in state.iter_mut() {
+            *block = vreinterpretq_u8_u64(counter);
+            counter = vaddq_u64(counter, increment);
+        }
+        self.counter.set(counter);
+
+        // We apply the AES encryption round by round on all blocks, so that the independent
+        // blocks can be pipelined by the CPU.
+        for rk in &rks[..R - 1] {
+            let rk = rk.get();
+            for block in state.iter_mut() {
+                *block = vaesmcq_u8(vaeseq_u8(*block, rk));
+            }
+        }
+        let rk = rks[R - 1].get();
+        let last_rk = rks[R].get();
+        for block in state.iter_mut() {
+            *block = veorq_u8(vaeseq_u8(*block, rk), last_rk);
+        }
+
+        // Return the encrypted counters as u128.
+        state.map(|block| *(&block as *const uint8x16_t as *const u128))
+    }
 }
 
 /// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
 /// period of 128-bit.
 ///
-/// The full 14 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`Aes256Ctr128`](crate::Aes256Ctr128) is the variant with the full 14 rounds.
 #[derive(Clone)]
-pub struct Aes256Ctr128 {
+pub struct Aes256Ctr128R<const R: usize> {
     counter: Cell<u128>,
     round_keys: Cell<[uint8x16_t; AES256_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes256Ctr128 {
+impl<const R: usize> Drop for Aes256Ctr128R<R> {
     fn drop(&mut self) {
         self.counter.set(0);
         self.round_keys.set(unsafe { core::mem::zeroed() });
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes256Ctr128 {
+impl<const R: usize> Aes256Ctr128R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes256_ctr128"))]
     pub(crate) const fn zeroed() -> Self {
         Self {
             counter: Cell::new(unsafe { core::mem::zeroed() }),
             round_keys: Cell::new(unsafe { core::mem::zeroed() }),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     pub(crate) fn jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 64));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     pub(crate) fn long_jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 96));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
     pub(crate) unsafe fn from_seed_impl(key: [u8; 32], counter: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
         let counter = u128::from_le_bytes(counter);
         let round_keys: [uint8x16_t; AES256_KEY_COUNT] =
             aes_key_expansion::<AES256_KEY_SIZE, AES256_KEY_COUNT>(key);
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -379,17 +628,37 @@ impl Aes256Ctr128 {
             aes_key_expansion::<AES256_KEY_SIZE, AES256_KEY_COUNT>(key);
 
         self.counter.set(counter);
-        self.round_keys.set(round_keys)
+        self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        // The first two round keys are the key itself.
+        let round_keys = self.round_keys.get();
+        unsafe { core::mem::transmute([round_keys[0], round_keys[1]]) }
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         self.counter.get()
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        self.counter.set(counter);
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
     #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
@@ -402,27 +671,169 @@ impl Aes256Ctr128 {
         let rks = &*((&self.round_keys) as *const Cell<[_; AES256_KEY_COUNT]>
             as *const [Cell<_>; AES256_KEY_COUNT]);
 
-        // We apply the AES encryption on the counter.
+        // We apply the R rounds of AES encryption on the counter.
         let mut state = vld1q_u8(counter.to_le_bytes().as_ptr().cast());
-        state = vaesmcq_u8(vaeseq_u8(state, rks[0].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[1].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[2].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[3].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[4].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[5].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[6].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[7].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[8].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[9].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[10].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[11].get()));
-        state = vaesmcq_u8(vaeseq_u8(state, rks[12].get()));
-        state = vaeseq_u8(state, rks[13].get());
-        state = veorq_u8(state, rks[14].get());
+        for rk in &rks[..R - 1] {
+            state = vaesmcq_u8(vaeseq_u8(state, rk.get()));
+        }
+        state = vaeseq_u8(state, rks[R - 1].get());
+        state = veorq_u8(state, rks[R].get());
 
         // Return the encrypted counter as u128.
         *(&state as *const uint8x16_t as *const u128)
     }
+
+    #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        // SAFETY: `Cell<T>` has the same memory layout as `T`.
+        // Use `as_array_of_cells` once stable: https://github.com/rust-lang/rust/issues/88248
+        let rks = &*((&self.round_keys) as *const Cell<[_; AES256_KEY_COUNT]>
+            as *const [Cell<_>; AES256_KEY_COUNT]);
+
+        let counter = self.counter.get();
+        self.counter.set(counter.wrapping_add(N as u128));
+
+        // Create the counters.
+        let mut state = [vdupq_n_u8(0); N];
+        for (i, block) in state.iter_mut().enumerate() {
+            let counter = counter.wrapping_add(i as u128);
+            *block = vld1q_u8(counter.to_le_bytes().as_ptr().cast());
+        }
+
+        // We apply the AES encryption round by round on all blocks, so that the independent
+        // blocks can be pipelined by the CPU.
+        for rk in &rks[..R - 1] {
+            let rk = rk.get();
+            for block in state.iter_mut() {
+                *block = vaesmcq_u8(vaeseq_u8(*block, rk));
+            }
+        }
+        let rk = rks[R - 1].get();
+        let last_rk = rks[R].get();
+        for block in state.iter_mut() {
+            *block = veorq_u8(vaeseq_u8(*block, rk), last_rk);
+        }
+
+        // Return the encrypted counters as u128.
+        state.map(|block| *(&block as *const uint8x16_t as *const u128))
+    }
+}
+
+/// The AES-128 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) struct Aes128Cipher<const R: usize> {
+    round_keys: [uint8x16_t; AES128_KEY_COUNT],
+}
+
+impl<const R: usize> Drop for Aes128Cipher<R> {
+    fn drop(&mut self) {
+        self.round_keys = unsafe { core::mem::zeroed() };
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<const R: usize> Aes128Cipher<R> {
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
+    pub(crate) unsafe fn new_impl(key: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
+        Self {
+            round_keys: aes_key_expansion::<AES128_KEY_SIZE, AES128_KEY_COUNT>(key),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        true
+    }
+
+    #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
+    pub(crate) unsafe fn encrypt_blocks_impl<const N: usize>(
+        &self,
+        blocks: [u128; N],
+    ) -> [u128; N] {
+        encrypt_blocks::<R, N>(&self.round_keys, blocks)
+    }
+}
+
+/// The AES-256 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) struct Aes256Cipher<const R: usize> {
+    round_keys: [uint8x16_t; AES256_KEY_COUNT],
+}
+
+impl<const R: usize> Drop for Aes256Cipher<R> {
+    fn drop(&mut self) {
+        self.round_keys = unsafe { core::mem::zeroed() };
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<const R: usize> Aes256Cipher<R> {
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
+    pub(crate) unsafe fn new_impl(key: [u8; 32]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
+        Self {
+            round_keys: aes_key_expansion::<AES256_KEY_SIZE, AES256_KEY_COUNT>(key),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        true
+    }
+
+    #[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    #[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
+    pub(crate) unsafe fn encrypt_blocks_impl<const N: usize>(
+        &self,
+        blocks: [u128; N],
+    ) -> [u128; N] {
+        encrypt_blocks::<R, N>(&self.round_keys, blocks)
+    }
+}
+
+#[cfg_attr(all(target_feature = "neon", target_feature = "aes"), inline(always))]
+#[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+#[cfg_attr(not(target_feature = "neon"), target_feature(enable = "neon"))]
+unsafe fn encrypt_blocks<const R: usize, const N: usize>(
+    rks: &[uint8x16_t],
+    blocks: [u128; N],
+) -> [u128; N] {
+    let mut state = [vdupq_n_u8(0); N];
+    for (state, block) in state.iter_mut().zip(blocks) {
+        *state = vld1q_u8(block.to_le_bytes().as_ptr().cast());
+    }
+
+    // We apply the AES encryption round by round on all blocks, so that the independent
+    // blocks can be pipelined by the CPU.
+    for rk in &rks[..R - 1] {
+        for block in state.iter_mut() {
+            *block = vaesmcq_u8(vaeseq_u8(*block, *rk));
+        }
+    }
+    for block in state.iter_mut() {
+        *block = veorq_u8(vaeseq_u8(*block, rks[R - 1]), rks[R]);
+    }
+
+    // Return the encrypted blocks as u128.
+    state.map(|block| *(&block as *const uint8x16_t as *const u128))
 }
 
 #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
@@ -0,0 +1,62 @@
+use core::cell::Cell;
+
+/// Holds the unused bits of the last generated block, so that narrow requests (like `u32` or
+/// `bool`) don't need to generate a full 128-bit block every time.
+///
+/// The bits of a block are handed out from the least significant bit to the most significant bit.
+/// A request for `BITS` bits takes the next `BITS` unused bits of the current block. If fewer than
+/// `BITS` bits are left, the rest of the current block is discarded and a new block is generated.
+#[derive(Clone)]
+pub(crate) struct Leftover {
+    block: Cell<u128>,
+    bits: Cell<u32>,
+}
+
+impl Leftover {
+    pub(crate) const fn new() -> Self {
+        Self {
+            block: Cell::new(0),
+            bits: Cell::new(0),
+        }
+    }
+
+    /// Discards all unused bits.
+    #[inline(always)]
+    pub(crate) fn clear(&self) {
+        self.block.set(0);
+        self.bits.set(0);
+    }
+
+    /// Returns the unused bits and their count.
+    #[inline(always)]
+    pub(crate) fn get(&self) -> (u128, u32) {
+        (self.block.get(), self.bits.get())
+    }
+
+    /// Replaces the unused bits and their count.
+    #[inline(always)]
+    pub(crate) fn set(&self, block: u128, bits: u32) {
+        self.block.set(block);
+        self.bits.set(bits);
+    }
+
+    /// Returns the next `BITS` bits in the lower bits of the returned value. The upper bits of the
+    /// returned value are undefined and must be truncated by the caller.
+    #[inline(always)]
+    pub(crate) fn take<const BITS: u32>(&self, next: impl FnOnce() -> u128) -> u128 {
+        const { assert!(BITS > 0 && BITS < u128::BITS) };
+
+        let bits = self.bits.get();
+        if bits >= BITS {
+            let block = self.block.get();
+            self.block.set(block >> BITS);
+            self.bits.set(bits - BITS);
+            block
+        } else {
+            let block = next();
+            self.block.set(block >> BITS);
+            self.bits.set(u128::BITS - BITS);
+            block
+        }
+    }
+}
@@ -33,3 +33,5 @@ pub(crate) mod x86;
     feature = "verification",
 ))]
 pub(crate) mod soft;
+
+pub(crate) mod leftover;
@@ -1,26 +1,35 @@
 use core::{arch::asm, cell::Cell};
 
-use crate::constants::{AES128_KEY_COUNT, AES256_KEY_COUNT};
+use crate::backend::leftover::Leftover;
+use crate::constants::{
+    AES128_KEY_COUNT, AES128_KEY_SIZE, AES128_ROUNDS, AES256_KEY_COUNT, AES256_KEY_SIZE,
+    AES256_ROUNDS,
+};
+use crate::exhaustion::Exhaustion;
 
 /// A random number generator based on the AES-128 block cipher that runs in CTR mode and has a
 /// period of 64-bit.
 ///
-/// The full 10 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`Aes128Ctr64`](crate::Aes128Ctr64) is the variant with the full 10 rounds.
 #[derive(Clone)]
-pub struct Aes128Ctr64 {
+pub struct Aes128Ctr64R<const R: usize> {
     counter: Cell<[u64; 2]>,
     round_keys: Cell<[u128; AES128_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes128Ctr64 {
+impl<const R: usize> Drop for Aes128Ctr64R<R> {
     fn drop(&mut self) {
         self.counter.set([0, 0]);
         self.round_keys.set([0; AES128_KEY_COUNT]);
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes128Ctr64 {
+impl<const R: usize> Aes128Ctr64R<R> {
     #[cfg(all(
         feature = "tls",
         not(any(
@@ -33,10 +42,19 @@ impl Aes128Ctr64 {
         Self {
             counter: Cell::new([0; 2]),
             round_keys: Cell::new([0; AES128_KEY_COUNT]),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     pub(crate) unsafe fn from_seed_impl(key: [u8; 16], nonce: [u8; 8], counter: [u8; 8]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
         let mut key_0 = [0u8; 16];
         key_0.copy_from_slice(&key[0..16]);
 
@@ -48,6 +66,8 @@ impl Aes128Ctr64 {
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -62,16 +82,52 @@ impl Aes128Ctr64 {
 
         self.counter.set(counter);
         self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        // The first round key is the key itself.
+        self.round_keys.get()[0].to_le_bytes()
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         self.counter.get()[0]
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        let mut lanes = self.counter.get();
+        lanes[0] = counter;
+        self.counter.set(lanes);
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        self.counter.get()[1]
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        let mut lanes = self.counter.get();
+        lanes[1] = stream;
+        self.counter.set(lanes);
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        core::array::from_fn(|_| unsafe { self.next_impl() })
+    }
+
     #[inline(always)]
     #[allow(unused_assignments)]
     pub(crate) unsafe fn next_impl(&self) -> u128 {
@@ -87,58 +143,63 @@ impl Aes128Ctr64 {
         let mut state = counter;
         let state_ptr = state.as_mut_ptr();
 
-        asm!(
-            "vsetivli x0, 4, e32, m1, ta, ma",
-            "vle32.v v0, (t0)", // Load counter into a register
-            "vle32.v v1, (t1)", // Copy all round keys into the vector registers
-            "addi t1, t1, 16",
-            "vle32.v v2, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v3, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v4, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v5, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v6, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v7, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v8, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v9, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v10, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v11, (t1)",
-            "vaesz.vs v0, v1", // Whiten the counter
-            "vaesem.vs v0, v2", // Apply 10 rounds of AES
-            "vaesem.vs v0, v3",
-            "vaesem.vs v0, v4",
-            "vaesem.vs v0, v5",
-            "vaesem.vs v0, v6",
-            "vaesem.vs v0, v7",
-            "vaesem.vs v0, v8",
-            "vaesem.vs v0, v9",
-            "vaesem.vs v0, v10",
-            "vaesef.vs v0, v11",
-            "vse32.v v0, (t0)", // Store the state
-            options(nostack),
-            in("t0") state_ptr,
-            inout("t1") round_keys_ptr,
-            out("v0") _,
-            out("v1") _,
-            out("v2") _,
-            out("v3") _,
-            out("v4") _,
-            out("v5") _,
-            out("v6") _,
-            out("v7") _,
-            out("v8") _,
-            out("v9") _,
-            out("v10") _,
-            out("v11") _,
-        );
+        if R < AES128_ROUNDS {
+            // The reduced-round variants load the round keys inside the round loop.
+            encrypt_rounds(state_ptr.cast(), round_keys_ptr.cast(), R);
+        } else {
+            asm!(
+                "vsetivli x0, 4, e32, m1, ta, ma",
+                "vle32.v v0, (t0)", // Load counter into a register
+                "vle32.v v1, (t1)", // Copy all round keys into the vector registers
+                "addi t1, t1, 16",
+                "vle32.v v2, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v3, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v4, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v5, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v6, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v7, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v8, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v9, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v10, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v11, (t1)",
+                "vaesz.vs v0, v1", // Whiten the counter
+                "vaesem.vs v0, v2", // Apply 10 rounds of AES
+                "vaesem.vs v0, v3",
+                "vaesem.vs v0, v4",
+                "vaesem.vs v0, v5",
+                "vaesem.vs v0, v6",
+                "vaesem.vs v0, v7",
+                "vaesem.vs v0, v8",
+                "vaesem.vs v0, v9",
+                "vaesem.vs v0, v10",
+                "vaesef.vs v0, v11",
+                "vse32.v v0, (t0)", // Store the state
+                options(nostack),
+                in("t0") state_ptr,
+                inout("t1") round_keys_ptr,
+                out("v0") _,
+                out("v1") _,
+                out("v2") _,
+                out("v3") _,
+                out("v4") _,
+                out("v5") _,
+                out("v6") _,
+                out("v7") _,
+                out("v8") _,
+                out("v9") _,
+                out("v10") _,
+                out("v11") _,
+            );
+        }
 
         // Return the encrypted counter as u128.
         u128::from(state[0]) | (u128::from(state[1]) << 64)
@@ -148,43 +209,60 @@ impl Aes128Ctr64 {
 /// A random number generator based on the AES-128 block cipher that runs in CTR mode and has a
 /// period of 128-bit.
 ///
-/// The full 10 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`Aes128Ctr128`](crate::Aes128Ctr128) is the variant with the full 10 rounds.
 #[derive(Clone)]
-pub struct Aes128Ctr128 {
+pub struct Aes128Ctr128R<const R: usize> {
     counter: Cell<u128>,
     round_keys: Cell<[u128; AES128_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes128Ctr128 {
+impl<const R: usize> Drop for Aes128Ctr128R<R> {
     fn drop(&mut self) {
         self.counter.set(0);
         self.round_keys.set([0; AES128_KEY_COUNT]);
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes128Ctr128 {
+impl<const R: usize> Aes128Ctr128R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes128_ctr128"))]
     pub(crate) const fn zeroed() -> Self {
         Self {
             counter: Cell::new(0),
             round_keys: Cell::new([0; AES128_KEY_COUNT]),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     pub(crate) fn jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 64));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     pub(crate) fn long_jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 96));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     pub(crate) unsafe fn from_seed_impl(key: [u8; 16], counter: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
         let mut key_0 = [0u8; 16];
         key_0.copy_from_slice(&key[0..16]);
 
@@ -196,6 +274,8 @@ impl Aes128Ctr128 {
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -210,16 +290,40 @@ impl Aes128Ctr128 {
 
         self.counter.set(counter);
         self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        // The first round key is the key itself.
+        self.round_keys.get()[0].to_le_bytes()
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         self.counter.get()
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        self.counter.set(counter);
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        core::array::from_fn(|_| unsafe { self.next_impl() })
+    }
+
     #[inline(always)]
     #[allow(unused_assignments)]
     pub(crate) unsafe fn next_impl(&self) -> u128 {
@@ -233,58 +337,63 @@ impl Aes128Ctr128 {
         let mut state = counter;
         let state_ptr = (&mut state) as *mut u128;
 
-        asm!(
-            "vsetivli x0, 4, e32, m1, ta, ma",
-            "vle32.v v0, (t0)", // Load counter into a register
-            "vle32.v v1, (t1)", // Copy all round keys into the vector registers
-            "addi t1, t1, 16",
-            "vle32.v v2, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v3, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v4, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v5, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v6, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v7, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v8, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v9, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v10, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v11, (t1)",
-            "vaesz.vs v0, v1", // Whiten the counter
-            "vaesem.vs v0, v2", // Apply 10 rounds of AES
-            "vaesem.vs v0, v3",
-            "vaesem.vs v0, v4",
-            "vaesem.vs v0, v5",
-            "vaesem.vs v0, v6",
-            "vaesem.vs v0, v7",
-            "vaesem.vs v0, v8",
-            "vaesem.vs v0, v9",
-            "vaesem.vs v0, v10",
-            "vaesef.vs v0, v11",
-            "vse32.v v0, (t0)", // Store the state
-            options(nostack),
-            in("t0") state_ptr,
-            inout("t1") round_keys_ptr,
-            out("v0") _,
-            out("v1") _,
-            out("v2") _,
-            out("v3") _,
-            out("v4") _,
-            out("v5") _,
-            out("v6") _,
-            out("v7") _,
-            out("v8") _,
-            out("v9") _,
-            out("v10") _,
-            out("v11") _,
-        );
+        if R < AES128_ROUNDS {
+            // The reduced-round variants load the round keys inside the round loop.
+            encrypt_rounds(state_ptr.cast(), round_keys_ptr.cast(), R);
+        } else {
+            asm!(
+                "vsetivli x0, 4, e32, m1, ta, ma",
+                "vle32.v v0, (t0)", // Load counter into a register
+                "vle32.v v1, (t1)", // Copy all round keys into the vector registers
+                "addi t1, t1, 16",
+                "vle32.v v2, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v3, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v4, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v5, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v6, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v7, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v8, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v9, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v10, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v11, (t1)",
+                "vaesz.vs v0, v1", // Whiten the counter
+                "vaesem.vs v0, v2", // Apply 10 rounds of AES
+                "vaesem.vs v0, v3",
+                "vaesem.vs v0, v4",
+                "vaesem.vs v0, v5",
+                "vaesem.vs v0, v6",
+                "vaesem.vs v0, v7",
+                "vaesem.vs v0, v8",
+                "vaesem.vs v0, v9",
+                "vaesem.vs v0, v10",
+                "vaesef.vs v0, v11",
+                "vse32.v v0, (t0)", // Store the state
+                options(nostack),
+                in("t0") state_ptr,
+                inout("t1") round_keys_ptr,
+                out("v0") _,
+                out("v1") _,
+                out("v2") _,
+                out("v3") _,
+                out("v4") _,
+                out("v5") _,
+                out("v6") _,
+                out("v7") _,
+                out("v8") _,
+                out("v9") _,
+                out("v10") _,
+                out("v11") _,
+            );
+        }
 
         // Return the encrypted counter as u128.
         state
@@ -294,31 +403,44 @@ impl Aes128Ctr128 {
 /// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
 /// period of 64-bit.
 ///
-/// The full 14 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`Aes256Ctr64`](crate::Aes256Ctr64) is the variant with the full 14 rounds.
 #[derive(Clone)]
-pub struct Aes256Ctr64 {
+pub struct Aes256Ctr64R<const R: usize> {
     counter: Cell<[u64; 2]>,
     round_keys: Cell<[u128; AES256_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes256Ctr64 {
+impl<const R: usize> Drop for Aes256Ctr64R<R> {
     fn drop(&mut self) {
         self.counter.set([0, 0]);
         self.round_keys.set([0; AES256_KEY_COUNT]);
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes256Ctr64 {
+impl<const R: usize> Aes256Ctr64R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes256_ctr64"))]
     pub(crate) const fn zeroed() -> Self {
         Self {
             counter: Cell::new([0, 0]),
             round_keys: Cell::new([0; AES256_KEY_COUNT]),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     pub(crate) unsafe fn from_seed_impl(key: [u8; 32], nonce: [u8; 8], counter: [u8; 8]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
         let mut key_0 = [0u8; 16];
         let mut key_1 = [0u8; 16];
 
@@ -333,6 +455,8 @@ impl Aes256Ctr64 {
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -350,16 +474,56 @@ impl Aes256Ctr64 {
 
         self.counter.set(counter);
         self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        // The first two round keys are the key itself.
+        let round_keys = self.round_keys.get();
+        let mut key = [0u8; AES256_KEY_SIZE];
+        key[..16].copy_from_slice(&round_keys[0].to_le_bytes());
+        key[16..].copy_from_slice(&round_keys[1].to_le_bytes());
+        key
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         self.counter.get()[0]
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        let mut lanes = self.counter.get();
+        lanes[0] = counter;
+        self.counter.set(lanes);
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        self.counter.get()[1]
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        let mut lanes = self.counter.get();
+        lanes[1] = stream;
+        self.counter.set(lanes);
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        core::array::from_fn(|_| unsafe { self.next_impl() })
+    }
+
     #[inline(always)]
     #[allow(unused_assignments)]
     pub(crate) unsafe fn next_impl(&self) -> u128 {
@@ -375,74 +539,79 @@ impl Aes256Ctr64 {
         let mut state = counter;
         let state_ptr = state.as_mut_ptr();
 
-        asm!(
-            "vsetivli x0, 4, e32, m1, ta, ma",
-            "vle32.v v0, (t0)", // Load counter into a register
-            "vle32.v v1, (t1)", // Copy all round keys into the vector registers
-            "addi t1, t1, 16",
-            "vle32.v v2, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v3, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v4, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v5, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v6, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v7, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v8, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v9, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v10, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v11, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v12, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v13, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v14, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v15, (t1)",
-            "vaesz.vs v0, v1", // Whiten the counter
-            "vaesem.vs v0, v2", // Apply 14 rounds of AES
-            "vaesem.vs v0, v3",
-            "vaesem.vs v0, v4",
-            "vaesem.vs v0, v5",
-            "vaesem.vs v0, v6",
-            "vaesem.vs v0, v7",
-            "vaesem.vs v0, v8",
-            "vaesem.vs v0, v9",
-            "vaesem.vs v0, v10",
-            "vaesem.vs v0, v11",
-            "vaesem.vs v0, v12",
-            "vaesem.vs v0, v13",
-            "vaesem.vs v0, v14",
-            "vaesef.vs v0, v15",
-            "vse32.v v0, (t0)", // Store the state
-            options(nostack),
-            in("t0") state_ptr,
-            inout("t1") round_keys_ptr,
-            out("v0") _,
-            out("v1") _,
-            out("v2") _,
-            out("v3") _,
-            out("v4") _,
-            out("v5") _,
-            out("v6") _,
-            out("v7") _,
-            out("v8") _,
-            out("v9") _,
-            out("v10") _,
-            out("v11") _,
-            out("v12") _,
-            out("v13") _,
-            out("v14") _,
-            out("v15") _,
-        );
+        if R < AES256_ROUNDS {
+            // The reduced-round variants load the round keys inside the round loop.
+            encrypt_rounds(state_ptr.cast(), round_keys_ptr.cast(), R);
+        } else {
+            asm!(
+                "vsetivli x0, 4, e32, m1, ta, ma",
+                "vle32.v v0, (t0)", // Load counter into a register
+                "vle32.v v1, (t1)", // Copy all round keys into the vector registers
+                "addi t1, t1, 16",
+                "vle32.v v2, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v3, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v4, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v5, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v6, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v7, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v8, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v9, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v10, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v11, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v12, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v13, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v14, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v15, (t1)",
+                "vaesz.vs v0, v1", // Whiten the counter
+                "vaesem.vs v0, v2", // Apply 14 rounds of AES
+                "vaesem.vs v0, v3",
+                "vaesem.vs v0, v4",
+                "vaesem.vs v0, v5",
+                "vaesem.vs v0, v6",
+                "vaesem.vs v0, v7",
+                "vaesem.vs v0, v8",
+                "vaesem.vs v0, v9",
+                "vaesem.vs v0, v10",
+                "vaesem.vs v0, v11",
+                "vaesem.vs v0, v12",
+                "vaesem.vs v0, v13",
+                "vaesem.vs v0, v14",
+                "vaesef.vs v0, v15",
+                "vse32.v v0, (t0)", // Store the state
+                options(nostack),
+                in("t0") state_ptr,
+                inout("t1") round_keys_ptr,
+                out("v0") _,
+                out("v1") _,
+                out("v2") _,
+                out("v3") _,
+                out("v4") _,
+                out("v5") _,
+                out("v6") _,
+                out("v7") _,
+                out("v8") _,
+                out("v9") _,
+                out("v10") _,
+                out("v11") _,
+                out("v12") _,
+                out("v13") _,
+                out("v14") _,
+                out("v15") _,
+            );
+        }
 
         // Return the encrypted counter as u128.
         u128::from(state[0]) | (u128::from(state[1]) << 64)
@@ -452,43 +621,60 @@ impl Aes256Ctr64 {
 /// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
 /// period of 128-bit.
 ///
-/// The full 14 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`Aes256Ctr128`](crate::Aes256Ctr128) is the variant with the full 14 rounds.
 #[derive(Clone)]
-pub struct Aes256Ctr128 {
+pub struct Aes256Ctr128R<const R: usize> {
     counter: Cell<u128>,
     round_keys: Cell<[u128; AES256_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes256Ctr128 {
+impl<const R: usize> Drop for Aes256Ctr128R<R> {
     fn drop(&mut self) {
         self.counter.set(0);
         self.round_keys.set([0; AES256_KEY_COUNT]);
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes256Ctr128 {
+impl<const R: usize> Aes256Ctr128R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes256_ctr128"))]
     pub(crate) const fn zeroed() -> Self {
         Self {
             counter: Cell::new(0),
             round_keys: Cell::new([0; AES256_KEY_COUNT]),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     pub(crate) fn jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 64));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     pub(crate) fn long_jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 96));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     pub(crate) unsafe fn from_seed_impl(key: [u8; 32], counter: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
         let mut key_0 = [0u8; 16];
         let mut key_1 = [0u8; 16];
 
@@ -503,13 +689,36 @@ impl Aes256Ctr128 {
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        // The first two round keys are the key itself.
+        let round_keys = self.round_keys.get();
+        let mut key = [0u8; AES256_KEY_SIZE];
+        key[..16].copy_from_slice(&round_keys[0].to_le_bytes());
+        key[16..].copy_from_slice(&round_keys[1].to_le_bytes());
+        key
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         self.counter.get()
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        self.counter.set(counter);
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
     pub(crate) unsafe fn seed_impl(&self, key: [u8; 32], counter: [u8; 16]) {
         let mut key_0 = [0u8; 16];
         let mut key_1 = [0u8; 16];
@@ -524,12 +733,19 @@ impl Aes256Ctr128 {
 
         self.counter.set(counter);
         self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    #[inline(always)]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        core::array::from_fn(|_| unsafe { self.next_impl() })
+    }
+
     #[inline(always)]
     #[allow(unused_assignments)]
     pub(crate) unsafe fn next_impl(&self) -> u128 {
@@ -543,80 +759,211 @@ impl Aes256Ctr128 {
         let mut state = counter;
         let state_ptr = (&mut state) as *mut u128;
 
-        asm!(
-            "vsetivli x0, 4, e32, m1, ta, ma",
-            "vle32.v v0, (t0)", // Load counter into a register
-            "vle32.v v1, (t1)", // Copy all round keys into the vector registers
-            "addi t1, t1, 16",
-            "vle32.v v2, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v3, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v4, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v5, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v6, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v7, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v8, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v9, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v10, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v11, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v12, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v13, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v14, (t1)",
-            "addi t1, t1, 16",
-            "vle32.v v15, (t1)",
-            "vaesz.vs v0, v1", // Whiten the counter
-            "vaesem.vs v0, v2", // Apply 14 rounds of AES
-            "vaesem.vs v0, v3",
-            "vaesem.vs v0, v4",
-            "vaesem.vs v0, v5",
-            "vaesem.vs v0, v6",
-            "vaesem.vs v0, v7",
-            "vaesem.vs v0, v8",
-            "vaesem.vs v0, v9",
-            "vaesem.vs v0, v10",
-            "vaesem.vs v0, v11",
-            "vaesem.vs v0, v12",
-            "vaesem.vs v0, v13",
-            "vaesem.vs v0, v14",
-            "vaesef.vs v0, v15",
-            "vse32.v v0, (t0)", // Store the state
-            options(nostack),
-            in("t0") state_ptr,
-            inout("t1") round_keys_ptr,
-            out("v0") _,
-            out("v1") _,
-            out("v2") _,
-            out("v3") _,
-            out("v4") _,
-            out("v5") _,
-            out("v6") _,
-            out("v7") _,
-            out("v8") _,
-            out("v9") _,
-            out("v10") _,
-            out("v11") _,
-            out("v12") _,
-            out("v13") _,
-            out("v14") _,
-            out("v15") _,
-        );
+        if R < AES256_ROUNDS {
+            // The reduced-round variants load the round keys inside the round loop.
+            encrypt_rounds(state_ptr.cast(), round_keys_ptr.cast(), R);
+        } else {
+            asm!(
+                "vsetivli x0, 4, e32, m1, ta, ma",
+                "vle32.v v0, (t0)", // Load counter into a register
+                "vle32.v v1, (t1)", // Copy all round keys into the vector registers
+                "addi t1, t1, 16",
+                "vle32.v v2, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v3, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v4, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v5, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v6, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v7, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v8, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v9, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v10, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v11, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v12, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v13, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v14, (t1)",
+                "addi t1, t1, 16",
+                "vle32.v v15, (t1)",
+                "vaesz.vs v0, v1", // Whiten the counter
+                "vaesem.vs v0, v2", // Apply 14 rounds of AES
+                "vaesem.vs v0, v3",
+                "vaesem.vs v0, v4",
+                "vaesem.vs v0, v5",
+                "vaesem.vs v0, v6",
+                "vaesem.vs v0, v7",
+                "vaesem.vs v0, v8",
+                "vaesem.vs v0, v9",
+                "vaesem.vs v0, v10",
+                "vaesem.vs v0, v11",
+                "vaesem.vs v0, v12",
+                "vaesem.vs v0, v13",
+                "vaesem.vs v0, v14",
+                "vaesef.vs v0, v15",
+                "vse32.v v0, (t0)", // Store the state
+                options(nostack),
+                in("t0") state_ptr,
+                inout("t1") round_keys_ptr,
+                out("v0") _,
+                out("v1") _,
+                out("v2") _,
+                out("v3") _,
+                out("v4") _,
+                out("v5") _,
+                out("v6") _,
+                out("v7") _,
+                out("v8") _,
+                out("v9") _,
+                out("v10") _,
+                out("v11") _,
+                out("v12") _,
+                out("v13") _,
+                out("v14") _,
+                out("v15") _,
+            );
+        }
 
         // Return the encrypted counter as u128.
         state
     }
 }
 
+/// The AES-128 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) struct Aes128Cipher<const R: usize> {
+    round_keys: [u128; AES128_KEY_COUNT],
+}
+
+impl<const R: usize> Drop for Aes128Cipher<R> {
+    fn drop(&mut self) {
+        self.round_keys = [0; AES128_KEY_COUNT];
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<const R: usize> Aes128Cipher<R> {
+    pub(crate) unsafe fn new_impl(key: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
+        Self {
+            round_keys: aes128_key_expansion(u128::from_le_bytes(key)),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn encrypt_blocks_impl<const N: usize>(
+        &self,
+        blocks: [u128; N],
+    ) -> [u128; N] {
+        let round_keys_ptr = self.round_keys.as_ptr();
+        blocks.map(|mut state| {
+            encrypt_rounds((&mut state as *mut u128).cast(), round_keys_ptr.cast(), R);
+            state
+        })
+    }
+}
+
+/// The AES-256 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) struct Aes256Cipher<const R: usize> {
+    round_keys: [u128; AES256_KEY_COUNT],
+}
+
+impl<const R: usize> Drop for Aes256Cipher<R> {
+    fn drop(&mut self) {
+        self.round_keys = [0; AES256_KEY_COUNT];
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<const R: usize> Aes256Cipher<R> {
+    pub(crate) unsafe fn new_impl(key: [u8; 32]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
+        let mut key_0 = [0u8; 16];
+        let mut key_1 = [0u8; 16];
+
+        key_0.copy_from_slice(&key[0..16]);
+        key_1.copy_from_slice(&key[16..32]);
+
+        let key = [u128::from_le_bytes(key_0), u128::from_le_bytes(key_1)];
+
+        Self {
+            round_keys: aes256_key_expansion(key),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn encrypt_blocks_impl<const N: usize>(
+        &self,
+        blocks: [u128; N],
+    ) -> [u128; N] {
+        let round_keys_ptr = self.round_keys.as_ptr();
+        blocks.map(|mut state| {
+            encrypt_rounds((&mut state as *mut u128).cast(), round_keys_ptr.cast(), R);
+            state
+        })
+    }
+}
+
+/// Encrypts the block at `state_ptr` with the given number of rounds.
+#[inline(always)]
+unsafe fn encrypt_rounds(state_ptr: *mut u8, round_keys_ptr: *const u8, rounds: usize) {
+    asm!(
+        "vsetivli x0, 4, e32, m1, ta, ma",
+        "vle32.v v0, (t0)", // Load counter into a register
+        "vle32.v v1, (t1)", // Whiten the counter
+        "vaesz.vs v0, v1",
+        "addi t2, t2, -1",
+        "beqz t2, 3f",
+        "2:",
+        "addi t1, t1, 16", // Apply all but the last round of AES
+        "vle32.v v1, (t1)",
+        "vaesem.vs v0, v1",
+        "addi t2, t2, -1",
+        "bnez t2, 2b",
+        "3:",
+        "addi t1, t1, 16", // Apply the last round of AES
+        "vle32.v v1, (t1)",
+        "vaesef.vs v0, v1",
+        "vse32.v v0, (t0)", // Store the state
+        options(nostack),
+        in("t0") state_ptr,
+        inout("t1") round_keys_ptr => _,
+        inout("t2") rounds => _,
+        out("v0") _,
+        out("v1") _,
+    );
+}
+
 #[allow(unused_assignments)]
 unsafe fn aes128_key_expansion(key: u128) -> [u128; AES128_KEY_COUNT] {
     let mut expanded_keys = [0u128; AES128_KEY_COUNT];
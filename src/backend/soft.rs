@@ -21,7 +21,11 @@
 //! hardware based AES is found).
 //!
 
-use crate::constants::{AES128_KEY_SIZE, AES256_KEY_SIZE, AES_BLOCK_SIZE};
+use crate::backend::leftover::Leftover;
+use crate::constants::{
+    AES128_KEY_SIZE, AES128_ROUNDS, AES256_KEY_SIZE, AES256_ROUNDS, AES_BLOCK_SIZE,
+};
+use crate::exhaustion::Exhaustion;
 
 use core::cell::RefCell;
 
@@ -45,28 +49,31 @@ type FixsliceKeys256 = [u64; FIX_SLICE_256_KEYS_SIZE];
 type State = [u64; 8];
 
 #[derive(Clone)]
-pub struct Aes128Ctr64(RefCell<Aes128Ctr64Inner>);
+pub struct Aes128Ctr64R<const R: usize>(RefCell<Aes128Ctr64Inner>, Leftover, Exhaustion);
 
 #[derive(Clone)]
 struct Aes128Ctr64Inner {
     counter: [u64; 2],
+    key: [u8; AES128_KEY_SIZE],
     round_keys: FixsliceKeys128,
     batch_blocks: BatchBlocks,
     batch_num: usize,
 }
 
-impl Drop for Aes128Ctr64 {
+impl<const R: usize> Drop for Aes128Ctr64R<R> {
     fn drop(&mut self) {
         let mut inner = self.0.borrow_mut();
         inner.counter = [0, 0];
+        inner.key = [0; AES128_KEY_SIZE];
         inner.round_keys = [0; FIX_SLICE_128_KEYS_SIZE];
         inner.batch_blocks = [Block::default(); BLOCK_COUNT];
         inner.batch_num = 0;
+        self.1.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes128Ctr64 {
+impl<const R: usize> Aes128Ctr64R<R> {
     #[cfg(all(
         feature = "tls",
         not(any(
@@ -76,39 +83,98 @@ impl Aes128Ctr64 {
         ))
     ))]
     pub(crate) const fn zeroed() -> Self {
-        Self(RefCell::new(Aes128Ctr64Inner {
-            counter: [0; 2],
-            round_keys: [0; FIX_SLICE_128_KEYS_SIZE],
-            batch_blocks: [[0; AES_BLOCK_SIZE]; BLOCK_COUNT],
-            batch_num: BLOCK_COUNT,
-        }))
+        Self(
+            RefCell::new(Aes128Ctr64Inner {
+                counter: [0; 2],
+                key: [0; AES128_KEY_SIZE],
+                round_keys: [0; FIX_SLICE_128_KEYS_SIZE],
+                batch_blocks: [[0; AES_BLOCK_SIZE]; BLOCK_COUNT],
+                batch_num: BLOCK_COUNT,
+            }),
+            Leftover::new(),
+            Exhaustion::new(),
+        )
     }
 
     pub(crate) fn from_seed_impl(key: [u8; 16], nonce: [u8; 8], counter: [u8; 8]) -> Self {
-        let counter = [u64::from_le_bytes(counter), u64::from_le_bytes(nonce)];
-        let round_keys: FixsliceKeys128 = aes128_key_expansion(key);
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
 
-        Self(RefCell::new(Aes128Ctr64Inner {
-            counter,
-            round_keys,
-            batch_blocks: [Block::default(); BLOCK_COUNT],
-            batch_num: BLOCK_COUNT,
-        }))
+        let counter = [u64::from_le_bytes(counter), u64::from_le_bytes(nonce)];
+        let round_keys: FixsliceKeys128 = aes128_key_expansion::<R>(key);
+
+        Self(
+            RefCell::new(Aes128Ctr64Inner {
+                counter,
+                key,
+                round_keys,
+                batch_blocks: [Block::default(); BLOCK_COUNT],
+                batch_num: BLOCK_COUNT,
+            }),
+            Leftover::new(),
+            Exhaustion::new(),
+        )
     }
 
     pub(crate) fn seed_impl(&self, key: [u8; 16], nonce: [u8; 8], counter: [u8; 8]) {
         let mut inner = self.0.borrow_mut();
+        inner.key = key;
         inner.counter = [u64::from_le_bytes(counter), u64::from_le_bytes(nonce)];
-        inner.round_keys = aes128_key_expansion(key);
+        inner.round_keys = aes128_key_expansion::<R>(key);
+        inner.batch_num = BLOCK_COUNT;
+        self.1.clear();
+        self.2.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         false
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        let inner = self.0.borrow();
+        inner.key
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         let inner = self.0.borrow();
-        inner.counter[0]
+        // The counter already points past the blocks that are left in the batch.
+        inner.counter[0].wrapping_sub((BLOCK_COUNT - inner.batch_num) as u64)
+    }
+
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        let mut inner = self.0.borrow_mut();
+        inner.counter[0] = counter;
+        inner.batch_num = BLOCK_COUNT;
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        let inner = self.0.borrow();
+        inner.counter[1]
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        let counter = self.counter_impl();
+        let mut inner = self.0.borrow_mut();
+        inner.counter = [counter, stream];
+        inner.batch_num = BLOCK_COUNT;
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.1
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.2
+    }
+
+    /// The software backend already encrypts multiple blocks in one batch, so there is nothing to
+    /// gain by interleaving the blocks here.
+    pub(crate) fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        core::array::from_fn(|_| self.next_impl())
     }
 
     #[inline(never)]
@@ -140,7 +206,7 @@ impl Aes128Ctr64 {
         inner.batch_blocks[3][..8].copy_from_slice(&counter_3.to_le_bytes());
         inner.batch_blocks[3][8..].copy_from_slice(&nonce.to_le_bytes());
 
-        inner.batch_blocks = aes128_encrypt(&inner.round_keys, &inner.batch_blocks);
+        inner.batch_blocks = aes_encrypt::<R>(&inner.round_keys, &inner.batch_blocks);
 
         // Return the first encrypted counter as u128
         inner.batch_num = 1;
@@ -149,77 +215,131 @@ impl Aes128Ctr64 {
 }
 
 #[derive(Clone)]
-pub struct Aes128Ctr128(RefCell<Aes128Ctr128Inner>);
+pub struct Aes128Ctr128R<const R: usize>(RefCell<Aes128Ctr128Inner>, Leftover, Exhaustion);
 
 #[derive(Clone)]
 struct Aes128Ctr128Inner {
     counter: u128,
+    key: [u8; AES128_KEY_SIZE],
     round_keys: FixsliceKeys128,
     batch_blocks: BatchBlocks,
     batch_num: usize,
 }
 
-impl Drop for Aes128Ctr128 {
+impl<const R: usize> Drop for Aes128Ctr128R<R> {
     fn drop(&mut self) {
         let mut inner = self.0.borrow_mut();
         inner.counter = 0;
+        inner.key = [0; AES128_KEY_SIZE];
         inner.round_keys = [0; FIX_SLICE_128_KEYS_SIZE];
         inner.batch_blocks = [Block::default(); BLOCK_COUNT];
         inner.batch_num = 0;
+        self.1.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes128Ctr128 {
+impl<const R: usize> Aes128Ctr128R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes128_ctr128"))]
     pub(crate) fn zeroed() -> Self {
-        Self(RefCell::new(Aes128Ctr128Inner {
-            counter: 0,
-            round_keys: [0; FIX_SLICE_128_KEYS_SIZE],
-            batch_blocks: [Block::default(); BLOCK_COUNT],
-            batch_num: 0,
-        }))
+        Self(
+            RefCell::new(Aes128Ctr128Inner {
+                counter: 0,
+                key: [0; AES128_KEY_SIZE],
+                round_keys: [0; FIX_SLICE_128_KEYS_SIZE],
+                batch_blocks: [Block::default(); BLOCK_COUNT],
+                batch_num: BLOCK_COUNT,
+            }),
+            Leftover::new(),
+            Exhaustion::new(),
+        )
     }
 
     pub(crate) fn jump_impl(&self) -> Self {
         let clone = self.clone();
-        let mut inner = self.0.borrow_mut();
-        inner.counter += 1 << 64;
+        self.set_counter_impl(self.counter_impl() + (1 << 64));
+        self.1.clear();
+        self.2.clear();
         clone
     }
 
     pub(crate) fn long_jump_impl(&self) -> Self {
         let clone = self.clone();
-        let mut inner = self.0.borrow_mut();
-        inner.counter += 1 << 96;
+        self.set_counter_impl(self.counter_impl() + (1 << 96));
+        self.1.clear();
+        self.2.clear();
         clone
     }
 
     pub(crate) fn from_seed_impl(key: [u8; 16], counter: [u8; 16]) -> Self {
-        let counter = u128::from_le_bytes(counter);
-        let round_keys: FixsliceKeys128 = aes128_key_expansion(key);
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
 
-        Self(RefCell::new(Aes128Ctr128Inner {
-            counter,
-            round_keys,
-            batch_blocks: [Block::default(); BLOCK_COUNT],
-            batch_num: BLOCK_COUNT,
-        }))
+        let counter = u128::from_le_bytes(counter);
+        let round_keys: FixsliceKeys128 = aes128_key_expansion::<R>(key);
+
+        Self(
+            RefCell::new(Aes128Ctr128Inner {
+                counter,
+                key,
+                round_keys,
+                batch_blocks: [Block::default(); BLOCK_COUNT],
+                batch_num: BLOCK_COUNT,
+            }),
+            Leftover::new(),
+            Exhaustion::new(),
+        )
     }
 
     pub(crate) fn seed_impl(&self, key: [u8; 16], counter: [u8; 16]) {
         let mut inner = self.0.borrow_mut();
+        inner.key = key;
         inner.counter = u128::from_le_bytes(counter);
-        inner.round_keys = aes128_key_expansion(key);
+        inner.round_keys = aes128_key_expansion::<R>(key);
+        inner.batch_num = BLOCK_COUNT;
+        self.1.clear();
+        self.2.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         false
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        let inner = self.0.borrow();
+        inner.key
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         let inner = self.0.borrow();
-        inner.counter
+        // The counter already points past the blocks that are left in the batch.
+        inner
+            .counter
+            .wrapping_sub((BLOCK_COUNT - inner.batch_num) as u128)
+    }
+
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        let mut inner = self.0.borrow_mut();
+        inner.counter = counter;
+        inner.batch_num = BLOCK_COUNT;
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.1
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.2
+    }
+
+    /// The software backend already encrypts multiple blocks in one batch, so there is nothing to
+    /// gain by interleaving the blocks here.
+    pub(crate) fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        core::array::from_fn(|_| self.next_impl())
     }
 
     #[inline(never)]
@@ -246,7 +366,7 @@ impl Aes128Ctr128 {
         inner.batch_blocks[2].copy_from_slice(&counter_2.to_le_bytes());
         inner.batch_blocks[3].copy_from_slice(&counter_3.to_le_bytes());
 
-        inner.batch_blocks = aes128_encrypt(&inner.round_keys, &inner.batch_blocks);
+        inner.batch_blocks = aes_encrypt::<R>(&inner.round_keys, &inner.batch_blocks);
 
         // Return the first encrypted counter as u128
         inner.batch_num = 1;
@@ -255,63 +375,125 @@ impl Aes128Ctr128 {
 }
 
 #[derive(Clone)]
-pub struct Aes256Ctr64(RefCell<Aes256Ctr64Inner>);
+pub struct Aes256Ctr64R<const R: usize>(RefCell<Aes256Ctr64Inner>, Leftover, Exhaustion);
 
 #[derive(Clone)]
 struct Aes256Ctr64Inner {
     counter: [u64; 2],
+    key: [u8; AES256_KEY_SIZE],
     round_keys: FixsliceKeys256,
     batch_blocks: BatchBlocks,
     batch_num: usize,
 }
 
-impl Drop for Aes256Ctr64 {
+impl<const R: usize> Drop for Aes256Ctr64R<R> {
     fn drop(&mut self) {
         let mut inner = self.0.borrow_mut();
         inner.counter = [0, 0];
+        inner.key = [0; AES256_KEY_SIZE];
         inner.round_keys = [0; FIX_SLICE_256_KEYS_SIZE];
         inner.batch_blocks = [Block::default(); BLOCK_COUNT];
         inner.batch_num = 0;
+        self.1.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes256Ctr64 {
+impl<const R: usize> Aes256Ctr64R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes256_ctr64"))]
     pub(crate) fn zeroed() -> Self {
-        Self(RefCell::new(Aes256Ctr64Inner {
-            counter: [0, 0],
-            round_keys: [0; FIX_SLICE_256_KEYS_SIZE],
-            batch_blocks: [Block::default(); BLOCK_COUNT],
-            batch_num: 0,
-        }))
+        Self(
+            RefCell::new(Aes256Ctr64Inner {
+                counter: [0, 0],
+                key: [0; AES256_KEY_SIZE],
+                round_keys: [0; FIX_SLICE_256_KEYS_SIZE],
+                batch_blocks: [Block::default(); BLOCK_COUNT],
+                batch_num: BLOCK_COUNT,
+            }),
+            Leftover::new(),
+            Exhaustion::new(),
+        )
     }
 
     pub(crate) fn from_seed_impl(key: [u8; 32], nonce: [u8; 8], counter: [u8; 8]) -> Self {
-        let counter = [u64::from_le_bytes(counter), u64::from_le_bytes(nonce)];
-        let round_keys: FixsliceKeys256 = aes256_key_expansion(key);
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
 
-        Self(RefCell::new(Aes256Ctr64Inner {
-            counter,
-            round_keys,
-            batch_blocks: [Block::default(); BLOCK_COUNT],
-            batch_num: BLOCK_COUNT,
-        }))
+        let counter = [u64::from_le_bytes(counter), u64::from_le_bytes(nonce)];
+        let round_keys: FixsliceKeys256 = aes256_key_expansion::<R>(key);
+
+        Self(
+            RefCell::new(Aes256Ctr64Inner {
+                counter,
+                key,
+                round_keys,
+                batch_blocks: [Block::default(); BLOCK_COUNT],
+                batch_num: BLOCK_COUNT,
+            }),
+            Leftover::new(),
+            Exhaustion::new(),
+        )
     }
 
     pub(crate) fn seed_impl(&self, key: [u8; 32], nonce: [u8; 8], counter: [u8; 8]) {
         let mut inner = self.0.borrow_mut();
+        inner.key = key;
         inner.counter = [u64::from_le_bytes(counter), u64::from_le_bytes(nonce)];
-        inner.round_keys = aes256_key_expansion(key);
+        inner.round_keys = aes256_key_expansion::<R>(key);
+        inner.batch_num = BLOCK_COUNT;
+        self.1.clear();
+        self.2.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         false
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        let inner = self.0.borrow();
+        inner.key
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         let inner = self.0.borrow();
-        inner.counter[0]
+        // The counter already points past the blocks that are left in the batch.
+        inner.counter[0].wrapping_sub((BLOCK_COUNT - inner.batch_num) as u64)
+    }
+
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        let mut inner = self.0.borrow_mut();
+        inner.counter[0] = counter;
+        inner.batch_num = BLOCK_COUNT;
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        let inner = self.0.borrow();
+        inner.counter[1]
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        let counter = self.counter_impl();
+        let mut inner = self.0.borrow_mut();
+        inner.counter = [counter, stream];
+        inner.batch_num = BLOCK_COUNT;
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.1
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.2
+    }
+
+    /// The software backend already encrypts multiple blocks in one batch, so there is nothing to
+    /// gain by interleaving the blocks here.
+    pub(crate) fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        core::array::from_fn(|_| self.next_impl())
     }
 
     pub(crate) fn next_impl(&self) -> u128 {
@@ -342,7 +524,7 @@ impl Aes256Ctr64 {
         inner.batch_blocks[3][..8].copy_from_slice(&counter_3.to_le_bytes());
         inner.batch_blocks[3][8..].copy_from_slice(&nonce.to_le_bytes());
 
-        inner.batch_blocks = aes256_encrypt(&inner.round_keys, &inner.batch_blocks);
+        inner.batch_blocks = aes_encrypt::<R>(&inner.round_keys, &inner.batch_blocks);
 
         // Return the first encrypted counter as u128
         inner.batch_num = 1;
@@ -351,77 +533,131 @@ impl Aes256Ctr64 {
 }
 
 #[derive(Clone)]
-pub struct Aes256Ctr128(RefCell<Aes256Ctr128Inner>);
+pub struct Aes256Ctr128R<const R: usize>(RefCell<Aes256Ctr128Inner>, Leftover, Exhaustion);
 
 #[derive(Clone)]
 struct Aes256Ctr128Inner {
     pub(crate) counter: u128,
+    key: [u8; AES256_KEY_SIZE],
     round_keys: FixsliceKeys256,
     batch_blocks: BatchBlocks,
     batch_num: usize,
 }
 
-impl Drop for Aes256Ctr128 {
+impl<const R: usize> Drop for Aes256Ctr128R<R> {
     fn drop(&mut self) {
         let mut inner = self.0.borrow_mut();
         inner.counter = 0;
+        inner.key = [0; AES256_KEY_SIZE];
         inner.round_keys = [0; FIX_SLICE_256_KEYS_SIZE];
         inner.batch_blocks = [Block::default(); BLOCK_COUNT];
         inner.batch_num = 0;
+        self.1.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes256Ctr128 {
+impl<const R: usize> Aes256Ctr128R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes256_ctr128"))]
     pub(crate) fn zeroed() -> Self {
-        Self(RefCell::new(Aes256Ctr128Inner {
-            counter: 0,
-            round_keys: [0; FIX_SLICE_256_KEYS_SIZE],
-            batch_blocks: [Block::default(); BLOCK_COUNT],
-            batch_num: 0,
-        }))
+        Self(
+            RefCell::new(Aes256Ctr128Inner {
+                counter: 0,
+                key: [0; AES256_KEY_SIZE],
+                round_keys: [0; FIX_SLICE_256_KEYS_SIZE],
+                batch_blocks: [Block::default(); BLOCK_COUNT],
+                batch_num: BLOCK_COUNT,
+            }),
+            Leftover::new(),
+            Exhaustion::new(),
+        )
     }
 
     pub(crate) fn jump_impl(&self) -> Self {
         let clone = self.clone();
-        let mut inner = self.0.borrow_mut();
-        inner.counter += 1 << 64;
+        self.set_counter_impl(self.counter_impl() + (1 << 64));
+        self.1.clear();
+        self.2.clear();
         clone
     }
 
     pub(crate) fn long_jump_impl(&self) -> Self {
         let clone = self.clone();
-        let mut inner = self.0.borrow_mut();
-        inner.counter += 1 << 96;
+        self.set_counter_impl(self.counter_impl() + (1 << 96));
+        self.1.clear();
+        self.2.clear();
         clone
     }
 
     pub(crate) fn from_seed_impl(key: [u8; 32], counter: [u8; 16]) -> Self {
-        let counter = u128::from_le_bytes(counter);
-        let round_keys: FixsliceKeys256 = aes256_key_expansion(key);
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
 
-        Self(RefCell::new(Aes256Ctr128Inner {
-            counter,
-            round_keys,
-            batch_blocks: [Block::default(); BLOCK_COUNT],
-            batch_num: BLOCK_COUNT,
-        }))
+        let counter = u128::from_le_bytes(counter);
+        let round_keys: FixsliceKeys256 = aes256_key_expansion::<R>(key);
+
+        Self(
+            RefCell::new(Aes256Ctr128Inner {
+                counter,
+                key,
+                round_keys,
+                batch_blocks: [Block::default(); BLOCK_COUNT],
+                batch_num: BLOCK_COUNT,
+            }),
+            Leftover::new(),
+            Exhaustion::new(),
+        )
     }
 
     pub(crate) fn seed_impl(&self, key: [u8; 32], counter: [u8; 16]) {
         let mut inner = self.0.borrow_mut();
+        inner.key = key;
         inner.counter = u128::from_le_bytes(counter);
-        inner.round_keys = aes256_key_expansion(key);
+        inner.round_keys = aes256_key_expansion::<R>(key);
+        inner.batch_num = BLOCK_COUNT;
+        self.1.clear();
+        self.2.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         false
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        let inner = self.0.borrow();
+        inner.key
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         let inner = self.0.borrow();
-        inner.counter
+        // The counter already points past the blocks that are left in the batch.
+        inner
+            .counter
+            .wrapping_sub((BLOCK_COUNT - inner.batch_num) as u128)
+    }
+
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        let mut inner = self.0.borrow_mut();
+        inner.counter = counter;
+        inner.batch_num = BLOCK_COUNT;
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.1
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.2
+    }
+
+    /// The software backend already encrypts multiple blocks in one batch, so there is nothing to
+    /// gain by interleaving the blocks here.
+    pub(crate) fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        core::array::from_fn(|_| self.next_impl())
     }
 
     #[inline(never)]
@@ -448,7 +684,7 @@ impl Aes256Ctr128 {
         inner.batch_blocks[2].copy_from_slice(&counter_2.to_le_bytes());
         inner.batch_blocks[3].copy_from_slice(&counter_3.to_le_bytes());
 
-        inner.batch_blocks = aes256_encrypt(&inner.round_keys, &inner.batch_blocks);
+        inner.batch_blocks = aes_encrypt::<R>(&inner.round_keys, &inner.batch_blocks);
 
         // Return the first encrypted counter as u128
         inner.batch_num = 1;
@@ -456,7 +692,98 @@ impl Aes256Ctr128 {
     }
 }
 
-fn aes128_key_expansion(key: [u8; AES128_KEY_SIZE]) -> FixsliceKeys128 {
+/// The AES-128 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) struct Aes128Cipher<const R: usize> {
+    round_keys: FixsliceKeys128,
+}
+
+impl<const R: usize> Drop for Aes128Cipher<R> {
+    fn drop(&mut self) {
+        self.round_keys = [0; FIX_SLICE_128_KEYS_SIZE];
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<const R: usize> Aes128Cipher<R> {
+    pub(crate) fn new_impl(key: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
+        Self {
+            round_keys: aes128_key_expansion::<R>(key),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        false
+    }
+
+    pub(crate) fn encrypt_blocks_impl<const N: usize>(&self, blocks: [u128; N]) -> [u128; N] {
+        encrypt_blocks::<R, N>(&self.round_keys, blocks)
+    }
+}
+
+/// The AES-256 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) struct Aes256Cipher<const R: usize> {
+    round_keys: FixsliceKeys256,
+}
+
+impl<const R: usize> Drop for Aes256Cipher<R> {
+    fn drop(&mut self) {
+        self.round_keys = [0; FIX_SLICE_256_KEYS_SIZE];
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<const R: usize> Aes256Cipher<R> {
+    pub(crate) fn new_impl(key: [u8; 32]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
+        Self {
+            round_keys: aes256_key_expansion::<R>(key),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        false
+    }
+
+    pub(crate) fn encrypt_blocks_impl<const N: usize>(&self, blocks: [u128; N]) -> [u128; N] {
+        encrypt_blocks::<R, N>(&self.round_keys, blocks)
+    }
+}
+
+/// Encrypts the blocks in batches of four.
+fn encrypt_blocks<const R: usize, const N: usize>(rkeys: &[u64], blocks: [u128; N]) -> [u128; N] {
+    let mut output = [0u128; N];
+    for (output, blocks) in output
+        .chunks_mut(BLOCK_COUNT)
+        .zip(blocks.chunks(BLOCK_COUNT))
+    {
+        let mut batch_blocks = [[0u8; AES_BLOCK_SIZE]; BLOCK_COUNT];
+        for (batch_block, block) in batch_blocks.iter_mut().zip(blocks) {
+            *batch_block = block.to_le_bytes();
+        }
+        let batch_blocks = aes_encrypt::<R>(rkeys, &batch_blocks);
+        for (output, batch_block) in output.iter_mut().zip(batch_blocks) {
+            *output = u128::from_le_bytes(batch_block);
+        }
+    }
+    output
+}
+
+fn aes128_key_expansion<const R: usize>(key: [u8; AES128_KEY_SIZE]) -> FixsliceKeys128 {
     let mut rkeys = [0u64; FIX_SLICE_128_KEYS_SIZE];
 
     bitslice(&mut rkeys[..8], &key, &key, &key, &key);
@@ -483,12 +810,7 @@ fn aes128_key_expansion(key: [u8; AES128_KEY_SIZE]) -> FixsliceKeys128 {
     }
 
     // Adjust to match fixslicing format
-    for i in (8..72).step_by(32) {
-        inv_shift_rows_1(&mut rkeys[i..(i + 8)]);
-        inv_shift_rows_2(&mut rkeys[(i + 8)..(i + 16)]);
-        inv_shift_rows_3(&mut rkeys[(i + 16)..(i + 24)]);
-    }
-    inv_shift_rows_1(&mut rkeys[72..80]);
+    adjust_round_keys::<R>(&mut rkeys);
 
     // Account for NOTs removed from sub_bytes
     for i in 1..11 {
@@ -499,7 +821,7 @@ fn aes128_key_expansion(key: [u8; AES128_KEY_SIZE]) -> FixsliceKeys128 {
 }
 
 /// Fully bitsliced AES-256 key schedule to match the fully-fixsliced representation.
-fn aes256_key_expansion(key: [u8; AES256_KEY_SIZE]) -> FixsliceKeys256 {
+fn aes256_key_expansion<const R: usize>(key: [u8; AES256_KEY_SIZE]) -> FixsliceKeys256 {
     let mut rkeys = [0u64; 120];
 
     let mut low = [0u8; AES_BLOCK_SIZE];
@@ -541,12 +863,7 @@ fn aes256_key_expansion(key: [u8; AES256_KEY_SIZE]) -> FixsliceKeys256 {
     }
 
     // Adjust to match fixslicing format
-    for i in (8..104).step_by(32) {
-        inv_shift_rows_1(&mut rkeys[i..(i + 8)]);
-        inv_shift_rows_2(&mut rkeys[(i + 8)..(i + 16)]);
-        inv_shift_rows_3(&mut rkeys[(i + 16)..(i + 24)]);
-    }
-    inv_shift_rows_1(&mut rkeys[104..112]);
+    adjust_round_keys::<R>(&mut rkeys);
 
     // Account for NOTs removed from sub_bytes
     for i in 1..15 {
@@ -556,54 +873,24 @@ fn aes256_key_expansion(key: [u8; AES256_KEY_SIZE]) -> FixsliceKeys256 {
     rkeys
 }
 
-/// Fully-fixsliced AES-128 encryption (the ShiftRows is completely omitted).
-///
-/// Encrypts four blocks in-place and in parallel.
-fn aes128_encrypt(rkeys: &FixsliceKeys128, blocks: &BatchBlocks) -> BatchBlocks {
-    let mut state = State::default();
-
-    bitslice(&mut state, &blocks[0], &blocks[1], &blocks[2], &blocks[3]);
-
-    add_round_key(&mut state, &rkeys[..8]);
-
-    let mut rk_off = 8;
-    loop {
-        sub_bytes(&mut state);
-        mix_columns_1(&mut state);
-        add_round_key(&mut state, &rkeys[rk_off..(rk_off + 8)]);
-        rk_off += 8;
-
-        if rk_off == 80 {
-            break;
+/// Applies the inverse of the ShiftRows that are omitted in the first `R - 1` rounds to the round
+/// keys. The key of the last round stays untouched, since the last round restores the ShiftRows.
+fn adjust_round_keys<const R: usize>(rkeys: &mut [u64]) {
+    for round in 1..R {
+        let rkey = &mut rkeys[(round * 8)..(round * 8 + 8)];
+        match round % 4 {
+            1 => inv_shift_rows_1(rkey),
+            2 => inv_shift_rows_2(rkey),
+            3 => inv_shift_rows_3(rkey),
+            _ => {}
         }
-
-        sub_bytes(&mut state);
-        mix_columns_2(&mut state);
-        add_round_key(&mut state, &rkeys[rk_off..(rk_off + 8)]);
-        rk_off += 8;
-
-        sub_bytes(&mut state);
-        mix_columns_3(&mut state);
-        add_round_key(&mut state, &rkeys[rk_off..(rk_off + 8)]);
-        rk_off += 8;
-
-        sub_bytes(&mut state);
-        mix_columns_0(&mut state);
-        add_round_key(&mut state, &rkeys[rk_off..(rk_off + 8)]);
-        rk_off += 8;
     }
-
-    shift_rows_2(&mut state);
-    sub_bytes(&mut state);
-    add_round_key(&mut state, &rkeys[80..]);
-
-    inv_bitslice(&state)
 }
 
-/// Fully-fixsliced AES-256 encryption (the ShiftRows is completely omitted).
+/// Fully-fixsliced AES encryption with `R` rounds (the ShiftRows is completely omitted).
 ///
 /// Encrypts four blocks in-place and in parallel.
-fn aes256_encrypt(rkeys: &FixsliceKeys256, blocks: &BatchBlocks) -> BatchBlocks {
+fn aes_encrypt<const R: usize>(rkeys: &[u64], blocks: &BatchBlocks) -> BatchBlocks {
     let mut state = State::default();
 
     bitslice(&mut state, &blocks[0], &blocks[1], &blocks[2], &blocks[3]);
@@ -611,13 +898,13 @@ fn aes256_encrypt(rkeys: &FixsliceKeys256, blocks: &BatchBlocks) -> BatchBlocks
     add_round_key(&mut state, &rkeys[..8]);
 
     let mut rk_off = 8;
-    loop {
+    while rk_off < R * 8 {
         sub_bytes(&mut state);
         mix_columns_1(&mut state);
         add_round_key(&mut state, &rkeys[rk_off..(rk_off + 8)]);
         rk_off += 8;
 
-        if rk_off == 112 {
+        if rk_off == R * 8 {
             break;
         }
 
@@ -626,20 +913,34 @@ fn aes256_encrypt(rkeys: &FixsliceKeys256, blocks: &BatchBlocks) -> BatchBlocks
         add_round_key(&mut state, &rkeys[rk_off..(rk_off + 8)]);
         rk_off += 8;
 
+        if rk_off == R * 8 {
+            break;
+        }
+
         sub_bytes(&mut state);
         mix_columns_3(&mut state);
         add_round_key(&mut state, &rkeys[rk_off..(rk_off + 8)]);
         rk_off += 8;
 
+        if rk_off == R * 8 {
+            break;
+        }
+
         sub_bytes(&mut state);
         mix_columns_0(&mut state);
         add_round_key(&mut state, &rkeys[rk_off..(rk_off + 8)]);
         rk_off += 8;
     }
 
-    shift_rows_2(&mut state);
+    // The last round has no MixColumns and applies the ShiftRows that were omitted so far.
+    match R % 4 {
+        1 => shift_rows_1(&mut state),
+        2 => shift_rows_2(&mut state),
+        3 => shift_rows_3(&mut state),
+        _ => {}
+    }
     sub_bytes(&mut state);
-    add_round_key(&mut state, &rkeys[112..]);
+    add_round_key(&mut state, &rkeys[rk_off..(rk_off + 8)]);
 
     inv_bitslice(&state)
 }
@@ -6,7 +6,12 @@ use core::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
 use core::arch::x86_64::*;
 
-use crate::constants::{AES128_KEY_COUNT, AES128_KEY_SIZE, AES256_KEY_COUNT, AES256_KEY_SIZE};
+use crate::backend::leftover::Leftover;
+use crate::constants::{
+    AES128_KEY_COUNT, AES128_KEY_SIZE, AES128_ROUNDS, AES256_KEY_COUNT, AES256_KEY_SIZE,
+    AES256_ROUNDS,
+};
+use crate::exhaustion::Exhaustion;
 
 // Compile-time checks to verify that some casts are sound.
 const _: () = assert!(size_of::<__m128i>() == size_of::<u128>());
@@ -15,22 +20,26 @@ const _: () = assert!(align_of::<__m128i>() == align_of::<u128>());
 /// A random number generator based on the AES-128 block cipher thar runs in CTR mode and has a
 /// period of 64-bit.
 ///
-/// The full 10 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`Aes128Ctr64`](crate::Aes128Ctr64) is the variant with the full 10 rounds.
 #[derive(Clone)]
-pub struct Aes128Ctr64 {
+pub struct Aes128Ctr64R<const R: usize> {
     counter: Cell<__m128i>,
     round_keys: Cell<[__m128i; AES128_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes128Ctr64 {
+impl<const R: usize> Drop for Aes128Ctr64R<R> {
     fn drop(&mut self) {
         self.counter.set(unsafe { core::mem::zeroed() });
         self.round_keys.set(unsafe { core::mem::zeroed() });
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes128Ctr64 {
+impl<const R: usize> Aes128Ctr64R<R> {
     #[cfg(all(
         feature = "tls",
         not(any(
@@ -43,12 +52,21 @@ impl Aes128Ctr64 {
         Self {
             counter: Cell::new(unsafe { core::mem::zeroed() }),
             round_keys: Cell::new(unsafe { core::mem::zeroed() }),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     pub(crate) unsafe fn from_seed_impl(key: [u8; 16], nonce: [u8; 8], counter: [u8; 8]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
         let counter =
             ((u64::from_le_bytes(nonce) as u128) << 64) + u64::from_le_bytes(counter) as u128;
         let counter = unsafe { _mm_loadu_si128(counter.to_le_bytes().as_ptr().cast()) };
@@ -57,6 +75,8 @@ impl Aes128Ctr64 {
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -69,18 +89,52 @@ impl Aes128Ctr64 {
         let round_keys: [__m128i; AES128_KEY_COUNT] = aes128_key_expansion(key);
 
         self.counter.set(counter);
-        self.round_keys.set(round_keys)
+        self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        // The first round key is the key itself.
+        unsafe { core::mem::transmute(self.round_keys.get()[0]) }
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         let bytes: [u8; 16] = unsafe { *(&self.counter.get() as *const __m128i as *const _) };
         u128::from_le_bytes(bytes) as u64
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        let mut lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[0] = counter;
+        self.counter
+            .set(unsafe { core::mem::transmute::<[u64; 2], __m128i>(lanes) });
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        let lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[1]
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        let mut lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[1] = stream;
+        self.counter
+            .set(unsafe { core::mem::transmute::<[u64; 2], __m128i>(lanes) });
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
     #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
     #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
@@ -100,70 +154,120 @@ impl Aes128Ctr64 {
         // Whitening the counter.
         let mut state = _mm_xor_si128(counter, rks[0].get());
 
-        // We apply the AES encryption on the whitened counter.
-        state = _mm_aesenc_si128(state, rks[1].get());
-        state = _mm_aesenc_si128(state, rks[2].get());
-        state = _mm_aesenc_si128(state, rks[3].get());
-        state = _mm_aesenc_si128(state, rks[4].get());
-        state = _mm_aesenc_si128(state, rks[5].get());
-        state = _mm_aesenc_si128(state, rks[6].get());
-        state = _mm_aesenc_si128(state, rks[7].get());
-        state = _mm_aesenc_si128(state, rks[8].get());
-        state = _mm_aesenc_si128(state, rks[9].get());
-        state = _mm_aesenclast_si128(state, rks[10].get());
+        // We apply the R rounds of AES encryption on the whitened counter.
+        for rk in &rks[1..R] {
+            state = _mm_aesenc_si128(state, rk.get());
+        }
+        state = _mm_aesenclast_si128(state, rks[R].get());
 
         // Return the encrypted counter as u128.
         u128::from_le_bytes(*(&state as *const __m128i as *const _))
     }
+
+    #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        // SAFETY: `Cell<T>` has the same memory layout as `T`.
+        // Use `as_array_of_cells` once stable: https://github.com/rust-lang/rust/issues/88248
+        let rks = &*((&self.round_keys) as *const Cell<[_; AES128_KEY_COUNT]>
+            as *const [Cell<_>; AES128_KEY_COUNT]);
+
+        let mut counter = self.counter.get();
+
+        // Whitening the counters, while incrementing the lower 64 bits using SIMD.
+        let increment = _mm_set_epi64x(0, 1);
+        let mut state = [_mm_setzero_si128(); N];
+        for block in state.iter_mut() {
+            *block = _mm_xor_si128(counter, rks[0].get());
+            counter = _mm_add_epi64(counter, increment);
+        }
+        self.counter.set(counter);
+
+        // We apply the AES encryption round by round on all blocks, so that the independent
+        // blocks can be pipelined by the CPU.
+        for rk in &rks[1..R] {
+            let rk = rk.get();
+            for block in state.iter_mut() {
+                *block = _mm_aesenc_si128(*block, rk);
+            }
+        }
+        let rk = rks[R].get();
+        for block in state.iter_mut() {
+            *block = _mm_aesenclast_si128(*block, rk);
+        }
+
+        // Return the encrypted counters as u128.
+        state.map(|block| u128::from_le_bytes(*(&block as *const __m128i as *const _)))
+    }
 }
 
 /// A random number generator based on the AES-128 block cipher thar runs in CTR mode and has a
 /// period of 128-bit.
 ///
-/// The full 10 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`Aes128Ctr128`](crate::Aes128Ctr128) is the variant with the full 10 rounds.
 #[derive(Clone)]
-pub struct Aes128Ctr128 {
+pub struct Aes128Ctr128R<const R: usize> {
     counter: Cell<u128>,
     round_keys: Cell<[__m128i; AES128_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes128Ctr128 {
+impl<const R: usize> Drop for Aes128Ctr128R<R> {
     fn drop(&mut self) {
         self.counter.set(0);
         self.round_keys.set(unsafe { core::mem::zeroed() });
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes128Ctr128 {
+impl<const R: usize> Aes128Ctr128R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes128_ctr128"))]
     pub(crate) const fn zeroed() -> Self {
         Self {
             counter: Cell::new(0),
             round_keys: Cell::new(unsafe { core::mem::zeroed() }),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     pub(crate) fn jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 64));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     pub(crate) fn long_jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 96));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     pub(crate) unsafe fn from_seed_impl(key: [u8; 16], counter: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
         let counter = u128::from_le_bytes(counter);
         let round_keys: [__m128i; AES128_KEY_COUNT] = aes128_key_expansion(key);
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -174,17 +278,36 @@ impl Aes128Ctr128 {
         let round_keys: [__m128i; AES128_KEY_COUNT] = aes128_key_expansion(key);
 
         self.counter.set(counter);
-        self.round_keys.set(round_keys)
+        self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        // The first round key is the key itself.
+        unsafe { core::mem::transmute(self.round_keys.get()[0]) }
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         self.counter.get()
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        self.counter.set(counter);
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
     #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
     #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
@@ -201,53 +324,97 @@ impl Aes128Ctr128 {
         let counter = _mm_loadu_si128(counter.to_le_bytes().as_ptr().cast());
         let mut state = _mm_xor_si128(counter, rks[0].get());
 
-        // We apply the AES encryption on the whitened counter.
-        state = _mm_aesenc_si128(state, rks[1].get());
-        state = _mm_aesenc_si128(state, rks[2].get());
-        state = _mm_aesenc_si128(state, rks[3].get());
-        state = _mm_aesenc_si128(state, rks[4].get());
-        state = _mm_aesenc_si128(state, rks[5].get());
-        state = _mm_aesenc_si128(state, rks[6].get());
-        state = _mm_aesenc_si128(state, rks[7].get());
-        state = _mm_aesenc_si128(state, rks[8].get());
-        state = _mm_aesenc_si128(state, rks[9].get());
-        state = _mm_aesenclast_si128(state, rks[10].get());
+        // We apply the R rounds of AES encryption on the whitened counter.
+        for rk in &rks[1..R] {
+            state = _mm_aesenc_si128(state, rk.get());
+        }
+        state = _mm_aesenclast_si128(state, rks[R].get());
 
         // Return the encrypted counter as u128.
         u128::from_le_bytes(*(&state as *const __m128i as *const _))
     }
+
+    #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        // SAFETY: `Cell<T>` has the same memory layout as `T`.
+        // Use `as_array_of_cells` once stable: https://github.com/rust-lang/rust/issues/88248
+        let rks = &*((&self.round_keys) as *const Cell<[_; AES128_KEY_COUNT]>
+            as *const [Cell<_>; AES128_KEY_COUNT]);
+
+        let counter = self.counter.get();
+        self.counter.set(counter.wrapping_add(N as u128));
+
+        // Whitening the counters.
+        let mut state = [_mm_setzero_si128(); N];
+        for (i, block) in state.iter_mut().enumerate() {
+            let counter = counter.wrapping_add(i as u128);
+            let counter = _mm_loadu_si128(counter.to_le_bytes().as_ptr().cast());
+            *block = _mm_xor_si128(counter, rks[0].get());
+        }
+
+        // We apply the AES encryption round by round on all blocks, so that the independent
+        // blocks can be pipelined by the CPU.
+        for rk in &rks[1..R] {
+            let rk = rk.get();
+            for block in state.iter_mut() {
+                *block = _mm_aesenc_si128(*block, rk);
+            }
+        }
+        let rk = rks[R].get();
+        for block in state.iter_mut() {
+            *block = _mm_aesenclast_si128(*block, rk);
+        }
+
+        // Return the encrypted counters as u128.
+        state.map(|block| u128::from_le_bytes(*(&block as *const __m128i as *const _)))
+    }
 }
 
 /// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
 /// period of 64-bit.
 ///
-/// The full 14 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`Aes256Ctr64`](crate::Aes256Ctr64) is the variant with the full 14 rounds.
 #[derive(Clone)]
-pub struct Aes256Ctr64 {
+pub struct Aes256Ctr64R<const R: usize> {
     counter: Cell<__m128i>,
     round_keys: Cell<[__m128i; AES256_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes256Ctr64 {
+impl<const R: usize> Drop for Aes256Ctr64R<R> {
     fn drop(&mut self) {
         self.counter.set(unsafe { core::mem::zeroed() });
         self.round_keys.set(unsafe { core::mem::zeroed() });
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes256Ctr64 {
+impl<const R: usize> Aes256Ctr64R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes256_ctr64"))]
     pub(crate) const fn zeroed() -> Self {
         Self {
             counter: Cell::new(unsafe { core::mem::zeroed() }),
             round_keys: Cell::new(unsafe { core::mem::zeroed() }),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     pub(crate) unsafe fn from_seed_impl(key: [u8; 32], nonce: [u8; 8], counter: [u8; 8]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
         let counter =
             ((u64::from_le_bytes(nonce) as u128) << 64) + u64::from_le_bytes(counter) as u128;
         let counter = unsafe { _mm_loadu_si128(counter.to_le_bytes().as_ptr().cast()) };
@@ -256,6 +423,8 @@ impl Aes256Ctr64 {
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -268,18 +437,53 @@ impl Aes256Ctr64 {
         let round_keys: [__m128i; AES256_KEY_COUNT] = aes256_key_expansion(key);
 
         self.counter.set(counter);
-        self.round_keys.set(round_keys)
+        self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        // The first two round keys are the key itself.
+        let round_keys = self.round_keys.get();
+        unsafe { core::mem::transmute([round_keys[0], round_keys[1]]) }
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         let bytes: [u8; 16] = unsafe { *(&self.counter.get() as *const __m128i as *const _) };
         u128::from_le_bytes(bytes) as u64
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        let mut lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[0] = counter;
+        self.counter
+            .set(unsafe { core::mem::transmute::<[u64; 2], __m128i>(lanes) });
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        let lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[1]
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        let mut lanes: [u64; 2] = unsafe { core::mem::transmute(self.counter.get()) };
+        lanes[1] = stream;
+        self.counter
+            .set(unsafe { core::mem::transmute::<[u64; 2], __m128i>(lanes) });
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
     #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
     #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
@@ -299,74 +503,120 @@ impl Aes256Ctr64 {
         // Whitening the counter.
         let mut state = _mm_xor_si128(counter, rks[0].get());
 
-        // We apply the AES encryption on the whitened counter.
-        state = _mm_aesenc_si128(state, rks[1].get());
-        state = _mm_aesenc_si128(state, rks[2].get());
-        state = _mm_aesenc_si128(state, rks[3].get());
-        state = _mm_aesenc_si128(state, rks[4].get());
-        state = _mm_aesenc_si128(state, rks[5].get());
-        state = _mm_aesenc_si128(state, rks[6].get());
-        state = _mm_aesenc_si128(state, rks[7].get());
-        state = _mm_aesenc_si128(state, rks[8].get());
-        state = _mm_aesenc_si128(state, rks[9].get());
-        state = _mm_aesenc_si128(state, rks[10].get());
-        state = _mm_aesenc_si128(state, rks[11].get());
-        state = _mm_aesenc_si128(state, rks[12].get());
-        state = _mm_aesenc_si128(state, rks[13].get());
-        state = _mm_aesenclast_si128(state, rks[14].get());
+        // We apply the R rounds of AES encryption on the whitened counter.
+        for rk in &rks[1..R] {
+            state = _mm_aesenc_si128(state, rk.get());
+        }
+        state = _mm_aesenclast_si128(state, rks[R].get());
 
         // Return the encrypted counter as u128.
         u128::from_le_bytes(*(&state as *const __m128i as *const _))
     }
+
+    #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        // SAFETY: `Cell<T>` has the same memory layout as `T`.
+        // Use `as_array_of_cells` once stable: https://github.com/rust-lang/rust/issues/88248
+        let rks = &*((&self.round_keys) as *const Cell<[_; AES256_KEY_COUNT]>
+            as *const [Cell<_>; AES256_KEY_COUNT]);
+
+        let mut counter = self.counter.get();
+
+        // Whitening the counters, while incrementing the lower 64 bits using SIMD.
+        let increment = _mm_set_epi64x(0, 1);
+        let mut state = [_mm_setzero_si128(); N];
+        for block in state.iter_mut() {
+            *block = _mm_xor_si128(counter, rks[0].get());
+            counter = _mm_add_epi64(counter, increment);
+        }
+        self.counter.set(counter);
+
+        // We apply the AES encryption round by round on all blocks, so that the independent
+        // blocks can be pipelined by the CPU.
+        for rk in &rks[1..R] {
+            let rk = rk.get();
+            for block in state.iter_mut() {
+                *block = _mm_aesenc_si128(*block, rk);
+            }
+        }
+        let rk = rks[R].get();
+        for block in state.iter_mut() {
+            *block = _mm_aesenclast_si128(*block, rk);
+        }
+
+        // Return the encrypted counters as u128.
+        state.map(|block| u128::from_le_bytes(*(&block as *const __m128i as *const _)))
+    }
 }
 
 /// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
 /// period of 128-bit.
 ///
-/// The full 14 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`Aes256Ctr128`](crate::Aes256Ctr128) is the variant with the full 14 rounds.
 #[derive(Clone)]
-pub struct Aes256Ctr128 {
+pub struct Aes256Ctr128R<const R: usize> {
     counter: Cell<u128>,
     round_keys: Cell<[__m128i; AES256_KEY_COUNT]>,
+    leftover: Leftover,
+    exhaustion: Exhaustion,
 }
 
-impl Drop for Aes256Ctr128 {
+impl<const R: usize> Drop for Aes256Ctr128R<R> {
     fn drop(&mut self) {
         self.counter.set(0);
         self.round_keys.set(unsafe { core::mem::zeroed() });
+        self.leftover.clear();
         core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl Aes256Ctr128 {
+impl<const R: usize> Aes256Ctr128R<R> {
     #[cfg(all(feature = "tls", feature = "tls_aes256_ctr128"))]
     pub(crate) const fn zeroed() -> Self {
         Self {
             counter: Cell::new(0),
             round_keys: Cell::new(unsafe { core::mem::zeroed() }),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
     pub(crate) fn jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 64));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     pub(crate) fn long_jump_impl(&self) -> Self {
         let clone = self.clone();
         self.counter.set(self.counter.get() + (1 << 96));
+        self.leftover.clear();
+        self.exhaustion.clear();
         clone
     }
 
     #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
     pub(crate) unsafe fn from_seed_impl(key: [u8; 32], counter: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
         let counter = u128::from_le_bytes(counter);
         let round_keys: [__m128i; 15] = aes256_key_expansion(key);
         Self {
             counter: Cell::new(counter),
             round_keys: Cell::new(round_keys),
+            leftover: Leftover::new(),
+            exhaustion: Exhaustion::new(),
         }
     }
 
@@ -377,17 +627,37 @@ impl Aes256Ctr128 {
         let round_keys: [__m128i; 15] = aes256_key_expansion(key);
 
         self.counter.set(counter);
-        self.round_keys.set(round_keys)
+        self.round_keys.set(round_keys);
+        self.leftover.clear();
+        self.exhaustion.clear();
     }
 
     pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
         true
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        // The first two round keys are the key itself.
+        let round_keys = self.round_keys.get();
+        unsafe { core::mem::transmute([round_keys[0], round_keys[1]]) }
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         self.counter.get()
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        self.counter.set(counter);
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        &self.leftover
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        &self.exhaustion
+    }
+
     #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
     #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
     #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
@@ -404,25 +674,169 @@ impl Aes256Ctr128 {
         let counter = _mm_loadu_si128(counter.to_le_bytes().as_ptr().cast());
         let mut state = _mm_xor_si128(counter, rks[0].get());
 
-        // We apply the AES encryption on the whitened counter.
-        state = _mm_aesenc_si128(state, rks[1].get());
-        state = _mm_aesenc_si128(state, rks[2].get());
-        state = _mm_aesenc_si128(state, rks[3].get());
-        state = _mm_aesenc_si128(state, rks[4].get());
-        state = _mm_aesenc_si128(state, rks[5].get());
-        state = _mm_aesenc_si128(state, rks[6].get());
-        state = _mm_aesenc_si128(state, rks[7].get());
-        state = _mm_aesenc_si128(state, rks[8].get());
-        state = _mm_aesenc_si128(state, rks[9].get());
-        state = _mm_aesenc_si128(state, rks[10].get());
-        state = _mm_aesenc_si128(state, rks[11].get());
-        state = _mm_aesenc_si128(state, rks[12].get());
-        state = _mm_aesenc_si128(state, rks[13].get());
-        state = _mm_aesenclast_si128(state, rks[14].get());
+        // We apply the R rounds of AES encryption on the whitened counter.
+        for rk in &rks[1..R] {
+            state = _mm_aesenc_si128(state, rk.get());
+        }
+        state = _mm_aesenclast_si128(state, rks[R].get());
 
         // Return the encrypted counter as u128.
         u128::from_le_bytes(*(&state as *const __m128i as *const _))
     }
+
+    #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    pub(crate) unsafe fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        // SAFETY: `Cell<T>` has the same memory layout as `T`.
+        // Use `as_array_of_cells` once stable: https://github.com/rust-lang/rust/issues/88248
+        let rks = &*((&self.round_keys) as *const Cell<[_; AES256_KEY_COUNT]>
+            as *const [Cell<_>; AES256_KEY_COUNT]);
+
+        let counter = self.counter.get();
+        self.counter.set(counter.wrapping_add(N as u128));
+
+        // Whitening the counters.
+        let mut state = [_mm_setzero_si128(); N];
+        for (i, block) in state.iter_mut().enumerate() {
+            let counter = counter.wrapping_add(i as u128);
+            let counter = _mm_loadu_si128(counter.to_le_bytes().as_ptr().cast());
+            *block = _mm_xor_si128(counter, rks[0].get());
+        }
+
+        // We apply the AES encryption round by round on all blocks, so that the independent
+        // blocks can be pipelined by the CPU.
+        for rk in &rks[1..R] {
+            let rk = rk.get();
+            for block in state.iter_mut() {
+                *block = _mm_aesenc_si128(*block, rk);
+            }
+        }
+        let rk = rks[R].get();
+        for block in state.iter_mut() {
+            *block = _mm_aesenclast_si128(*block, rk);
+        }
+
+        // Return the encrypted counters as u128.
+        state.map(|block| u128::from_le_bytes(*(&block as *const __m128i as *const _)))
+    }
+}
+
+/// The AES-128 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) struct Aes128Cipher<const R: usize> {
+    round_keys: [__m128i; AES128_KEY_COUNT],
+}
+
+impl<const R: usize> Drop for Aes128Cipher<R> {
+    fn drop(&mut self) {
+        self.round_keys = unsafe { core::mem::zeroed() };
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<const R: usize> Aes128Cipher<R> {
+    #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    pub(crate) unsafe fn new_impl(key: [u8; 16]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES128_ROUNDS,
+                "AES-128 supports 1 to 10 rounds"
+            )
+        };
+
+        Self {
+            round_keys: aes128_key_expansion(key),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        true
+    }
+
+    #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    pub(crate) unsafe fn encrypt_blocks_impl<const N: usize>(
+        &self,
+        blocks: [u128; N],
+    ) -> [u128; N] {
+        encrypt_blocks::<R, N>(&self.round_keys, blocks)
+    }
+}
+
+/// The AES-256 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) struct Aes256Cipher<const R: usize> {
+    round_keys: [__m128i; AES256_KEY_COUNT],
+}
+
+impl<const R: usize> Drop for Aes256Cipher<R> {
+    fn drop(&mut self) {
+        self.round_keys = unsafe { core::mem::zeroed() };
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<const R: usize> Aes256Cipher<R> {
+    #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    pub(crate) unsafe fn new_impl(key: [u8; 32]) -> Self {
+        const {
+            assert!(
+                R >= 1 && R <= AES256_ROUNDS,
+                "AES-256 supports 1 to 14 rounds"
+            )
+        };
+
+        Self {
+            round_keys: aes256_key_expansion(key),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        true
+    }
+
+    #[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
+    #[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
+    #[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+    pub(crate) unsafe fn encrypt_blocks_impl<const N: usize>(
+        &self,
+        blocks: [u128; N],
+    ) -> [u128; N] {
+        encrypt_blocks::<R, N>(&self.round_keys, blocks)
+    }
+}
+
+#[cfg_attr(all(target_feature = "sse2", target_feature = "aes"), inline(always))]
+#[cfg_attr(not(target_feature = "sse2"), target_feature(enable = "sse2"))]
+#[cfg_attr(not(target_feature = "aes"), target_feature(enable = "aes"))]
+unsafe fn encrypt_blocks<const R: usize, const N: usize>(
+    rks: &[__m128i],
+    blocks: [u128; N],
+) -> [u128; N] {
+    // Whitening the blocks.
+    let mut state = [_mm_setzero_si128(); N];
+    for (state, block) in state.iter_mut().zip(blocks) {
+        let block = _mm_loadu_si128(block.to_le_bytes().as_ptr().cast());
+        *state = _mm_xor_si128(block, rks[0]);
+    }
+
+    // We apply the AES encryption round by round on all blocks, so that the independent
+    // blocks can be pipelined by the CPU.
+    for rk in &rks[1..R] {
+        for block in state.iter_mut() {
+            *block = _mm_aesenc_si128(*block, *rk);
+        }
+    }
+    for block in state.iter_mut() {
+        *block = _mm_aesenclast_si128(*block, rks[R]);
+    }
+
+    // Return the encrypted blocks as u128.
+    state.map(|block| u128::from_le_bytes(*(&block as *const __m128i as *const _)))
 }
 
 #[target_feature(enable = "aes")]
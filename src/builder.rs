@@ -0,0 +1,272 @@
+//! Provides a builder that condenses several entropy inputs into a seed.
+
+use core::fmt;
+
+use crate::cipher::wipe;
+use crate::constants::{
+    AES128_KEY_SIZE, AES128_ROUNDS, AES256_KEY_SIZE, AES256_ROUNDS, AES_BLOCK_SIZE,
+};
+use crate::entropy::EntropySource;
+use crate::hardware::HardwareEntropy;
+use crate::kdf::{expand, Cmac};
+use crate::{Aes128Cipher, Aes256Cipher};
+
+/// The default and maximum entropy in bits, that the inputs of a [`SeedBuilder`] must claim. The
+/// extractor condenses the inputs into a 256-bit key, so it can't provide more entropy.
+pub const DEFAULT_ENTROPY_THRESHOLD: u32 = 256;
+
+/// The number of bytes that [`SeedBuilder::os_entropy()`] and
+/// [`SeedBuilder::hardware_entropy()`] draw from their source.
+const SOURCE_LENGTH: usize = 32;
+
+/// The label of the expansion of the extracted key into the seed.
+const LABEL: &[u8] = b"rand_aes::SeedBuilder";
+
+/// The kind of an input, which is part of its encoding.
+#[derive(Copy, Clone)]
+enum Input {
+    Bytes = 1,
+    Source = 2,
+    #[cfg(feature = "getrandom")]
+    Os = 3,
+    Hardware = 4,
+    #[cfg(feature = "std")]
+    Timestamp = 5,
+}
+
+/// The error that is returned by [`SeedBuilder::build()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SeedBuilderError {
+    /// The inputs claim less entropy than the threshold of the builder.
+    InsufficientEntropy {
+        /// The entropy in bits that the inputs claim.
+        claimed: u32,
+        /// The threshold of the builder in bits.
+        required: u32,
+    },
+}
+
+impl fmt::Display for SeedBuilderError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeedBuilderError::InsufficientEntropy { claimed, required } => write!(
+                fmt,
+                "insufficient entropy: the inputs claim {claimed} bits, but {required} bits are required"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SeedBuilderError {}
+
+/// Condenses several entropy inputs into a seed, so that the seed is secure as long as the inputs
+/// together provide enough entropy, even if no single input can be fully trusted.
+///
+/// Every input claims a number of bits of entropy, which is at most eight bits per byte.
+/// [`SeedBuilder::build()`] refuses to create a seed if the claims of all inputs add up to less
+/// than the threshold, which defaults to [`DEFAULT_ENTROPY_THRESHOLD`]. An entropy source that
+/// fails doesn't count towards the threshold.
+///
+/// # Construction
+///
+/// Every input is encoded as its kind (one byte), its length (eight bytes, little endian) and its
+/// bytes. The encoded inputs are extracted into a 256-bit key with two AES-128-CMACs under an
+/// all-zero key, that are separated by the prefixes `0x01` and `0x02`. The key is then expanded
+/// into the seed with the KDF in counter mode of NIST SP 800-108 and AES-256-CMAC, using the label
+/// `b"rand_aes::SeedBuilder"` and an empty context, like the
+/// [seed derivation](crate::seeds#derivation).
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::seeds::*;
+///
+/// let seed: Aes128Ctr64Seed = SeedBuilder::new()
+///     .with_threshold(128)
+///     .hardware_entropy()
+///     .os_entropy()
+///     .timestamp()
+///     .bytes(b"machine-id-1234", 0)
+///     .build()
+///     .unwrap();
+/// ```
+pub struct SeedBuilder {
+    extractors: [Cmac<Aes128Cipher<AES128_ROUNDS>>; 2],
+    entropy: u32,
+    threshold: u32,
+}
+
+impl SeedBuilder {
+    /// Creates a new builder without any inputs and the default threshold.
+    pub fn new() -> Self {
+        let mut extractors = [
+            Cmac::new(&[0u8; AES128_KEY_SIZE]),
+            Cmac::new(&[0u8; AES128_KEY_SIZE]),
+        ];
+        extractors[0].update(&[0x01]);
+        extractors[1].update(&[0x02]);
+
+        Self {
+            extractors,
+            entropy: 0,
+            threshold: DEFAULT_ENTROPY_THRESHOLD,
+        }
+    }
+
+    /// Sets the entropy in bits, that the inputs must claim before a seed can be built.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the threshold is larger than [`DEFAULT_ENTROPY_THRESHOLD`].
+    pub fn with_threshold(mut self, bits: u32) -> Self {
+        assert!(
+            bits <= DEFAULT_ENTROPY_THRESHOLD,
+            "the threshold can't be larger than {DEFAULT_ENTROPY_THRESHOLD} bits"
+        );
+        self.threshold = bits;
+        self
+    }
+
+    /// Adds the given bytes, that claim to contain the given entropy in bits.
+    pub fn bytes(mut self, bytes: &[u8], entropy: u32) -> Self {
+        self.begin(Input::Bytes, bytes.len());
+        self.update(bytes);
+        self.claim(bytes.len(), entropy);
+        self
+    }
+
+    /// Adds the given number of bytes from the entropy source, that claim to contain the given
+    /// entropy in bits. If the source fails, the input claims no entropy.
+    pub fn source(mut self, source: &mut impl EntropySource, length: usize, entropy: u32) -> Self {
+        if self.add_source(Input::Source, source, length) {
+            self.claim(length, entropy);
+        }
+        self
+    }
+
+    /// Adds 32 bytes of the entropy source of the OS, that claim 256 bits of entropy.
+    #[cfg(feature = "getrandom")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+    pub fn os_entropy(mut self) -> Self {
+        if self.add_source(Input::Os, &mut crate::OsEntropy, SOURCE_LENGTH) {
+            self.claim(SOURCE_LENGTH, 8 * SOURCE_LENGTH as u32);
+        }
+        self
+    }
+
+    /// Adds 32 bytes of the random number generator of the CPU, that claim 256 bits of entropy.
+    /// See [`HardwareEntropy`] for the supported CPUs. If the CPU has no random number generator,
+    /// the input claims no entropy.
+    ///
+    /// Use [`SeedBuilder::source()`] with a [`HardwareEntropy`] to claim less entropy.
+    pub fn hardware_entropy(mut self) -> Self {
+        if let Ok(mut hardware) = HardwareEntropy::new() {
+            if self.add_source(Input::Hardware, &mut hardware, SOURCE_LENGTH) {
+                self.claim(SOURCE_LENGTH, 8 * SOURCE_LENGTH as u32);
+            }
+        }
+        self
+    }
+
+    /// Adds the current system time with nanosecond resolution. The time is easy to guess, so it
+    /// claims no entropy, but it makes the seeds of otherwise identical inputs unique.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn timestamp(mut self) -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default()
+            .to_le_bytes();
+
+        self.begin(Input::Timestamp, nanos.len());
+        self.update(&nanos);
+        self
+    }
+
+    /// Returns the entropy in bits, that the inputs claim so far.
+    pub fn entropy(&self) -> u32 {
+        self.entropy
+    }
+
+    /// Builds the seed from the inputs.
+    ///
+    /// Returns [`SeedBuilderError::InsufficientEntropy`] if the inputs claim less entropy than
+    /// the threshold.
+    pub fn build<S: Default + AsMut<[u8]>>(self) -> Result<S, SeedBuilderError> {
+        if self.entropy < self.threshold {
+            return Err(SeedBuilderError::InsufficientEntropy {
+                claimed: self.entropy,
+                required: self.threshold,
+            });
+        }
+
+        let [first, second] = self.extractors;
+        let mut key = [0u8; AES256_KEY_SIZE];
+        key[..AES_BLOCK_SIZE].copy_from_slice(&first.finalize());
+        key[AES_BLOCK_SIZE..].copy_from_slice(&second.finalize());
+
+        let mut seed = S::default();
+        expand::<Aes256Cipher<AES256_ROUNDS>>(&key, LABEL, &[], seed.as_mut());
+
+        wipe(&mut key);
+
+        Ok(seed)
+    }
+
+    fn begin(&mut self, input: Input, length: usize) {
+        self.update(&[input as u8]);
+        self.update(&(length as u64).to_le_bytes());
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.extractors
+            .iter_mut()
+            .for_each(|extractor| extractor.update(bytes));
+    }
+
+    /// Adds the input of the entropy source. Returns `false` if the source failed.
+    fn add_source(&mut self, input: Input, source: &mut impl EntropySource, length: usize) -> bool {
+        self.begin(input, length);
+
+        let mut buffer = [0u8; 64];
+        let mut remaining = length;
+        let mut success = true;
+        while remaining > 0 {
+            let chunk = &mut buffer[..remaining.min(64)];
+            if source.fill(chunk).is_err() {
+                // The input keeps its length, so that the encoding stays unambiguous.
+                chunk.fill(0);
+                success = false;
+            }
+            self.update(chunk);
+            remaining -= chunk.len();
+        }
+
+        wipe(&mut buffer);
+
+        success
+    }
+
+    /// Adds the claimed entropy of an input, which is at most eight bits per byte.
+    fn claim(&mut self, length: usize, entropy: u32) {
+        let maximum = u32::try_from(length.saturating_mul(8)).unwrap_or(u32::MAX);
+        self.entropy = self.entropy.saturating_add(entropy.min(maximum));
+    }
+}
+
+impl Default for SeedBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for SeedBuilder {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("SeedBuilder")
+            .field("entropy", &self.entropy)
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,73 @@
+//! Provides a common interface for the AES block ciphers of the selected backend.
+
+use crate::constants::{AES128_KEY_SIZE, AES256_KEY_SIZE};
+use crate::implementation::safely_call;
+use crate::{Aes128Cipher, Aes256Cipher};
+
+/// An AES block cipher with a fixed number of rounds, that encrypts independent blocks.
+pub(crate) trait BlockCipher {
+    const KEY_SIZE: usize;
+
+    /// Expands the key, which must be `KEY_SIZE` bytes long, into the round keys.
+    fn new(key: &[u8]) -> Self;
+
+    fn is_hardware_accelerated(&self) -> bool;
+
+    fn encrypt_blocks<const N: usize>(&self, blocks: [u128; N]) -> [u128; N];
+}
+
+/// Overwrites the bytes of a key with zeros. The volatile writes and the fence keep the compiler
+/// from removing the writes to a buffer, that isn't read afterward.
+pub(crate) fn wipe(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: The pointer is created from a valid mutable reference.
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+impl<const R: usize> BlockCipher for Aes128Cipher<R> {
+    const KEY_SIZE: usize = AES128_KEY_SIZE;
+
+    fn new(key: &[u8]) -> Self {
+        let mut key_bytes = [0u8; AES128_KEY_SIZE];
+        key_bytes.copy_from_slice(key);
+        let cipher = {
+            safely_call! { Aes128Cipher::new_impl(key_bytes) }
+        };
+        wipe(&mut key_bytes);
+        cipher
+    }
+
+    fn is_hardware_accelerated(&self) -> bool {
+        self.is_hardware_accelerated_impl()
+    }
+
+    #[inline(always)]
+    fn encrypt_blocks<const N: usize>(&self, blocks: [u128; N]) -> [u128; N] {
+        safely_call! { self.encrypt_blocks_impl(blocks) }
+    }
+}
+
+impl<const R: usize> BlockCipher for Aes256Cipher<R> {
+    const KEY_SIZE: usize = AES256_KEY_SIZE;
+
+    fn new(key: &[u8]) -> Self {
+        let mut key_bytes = [0u8; AES256_KEY_SIZE];
+        key_bytes.copy_from_slice(key);
+        let cipher = {
+            safely_call! { Aes256Cipher::new_impl(key_bytes) }
+        };
+        wipe(&mut key_bytes);
+        cipher
+    }
+
+    fn is_hardware_accelerated(&self) -> bool {
+        self.is_hardware_accelerated_impl()
+    }
+
+    #[inline(always)]
+    fn encrypt_blocks<const N: usize>(&self, blocks: [u128; N]) -> [u128; N] {
+        safely_call! { self.encrypt_blocks_impl(blocks) }
+    }
+}
@@ -0,0 +1,486 @@
+//! Provides the CTR_DRBG deterministic random bit generator as specified in NIST SP 800-90A.
+
+use crate::cipher::BlockCipher;
+use crate::constants::{AES128_ROUNDS, AES256_KEY_SIZE, AES256_ROUNDS, AES_BLOCK_SIZE};
+use crate::{Aes128Cipher, Aes256Cipher};
+
+#[cfg(feature = "getrandom")]
+use crate::secure_bytes;
+
+/// The largest seed length of the supported DRBG (the AES-256 key plus one block).
+const MAX_SEED_LENGTH: usize = AES256_KEY_SIZE + AES_BLOCK_SIZE;
+
+/// The number of generate requests allowed between two reseeds (2^48).
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+/// The largest number of bytes a single generate request may return (2^19 bits).
+const MAX_REQUEST_SIZE: usize = 1 << 16;
+
+/// The largest length in bytes of the entropy input, the personalization string and the
+/// additional input (2^35 bits).
+const MAX_INPUT_LENGTH: u64 = 1 << 32;
+
+/// The number of blocks that are encrypted at once when generating the output.
+const BULK_BLOCKS: usize = 8;
+
+/// The fixed key of the derivation function: 0x00, 0x01, ..., 0x1F.
+const DF_KEY: [u8; AES256_KEY_SIZE] = {
+    let mut key = [0u8; AES256_KEY_SIZE];
+    let mut i = 0;
+    while i < key.len() {
+        key[i] = i as u8;
+        i += 1;
+    }
+    key
+};
+
+/// The error that is returned if a request to a DRBG can't be fulfilled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DrbgError {
+    /// The entropy input is shorter than the security strength of the DRBG.
+    EntropyTooShort,
+    /// The nonce is shorter than half the security strength of the DRBG.
+    NonceTooShort,
+    /// An input is longer than the 2^35 bits SP 800-90A allows.
+    InputTooLong,
+    /// More than 2^19 bits were requested at once.
+    RequestTooLarge,
+    /// The DRBG generated 2^48 requests since it was last seeded and must be reseeded.
+    ReseedRequired,
+}
+
+impl core::fmt::Display for DrbgError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let message = match self {
+            DrbgError::EntropyTooShort => "the entropy input is shorter than the security strength",
+            DrbgError::NonceTooShort => "the nonce is shorter than half the security strength",
+            DrbgError::InputTooLong => "the input is longer than 2^35 bits",
+            DrbgError::RequestTooLarge => "more than 2^19 bits were requested at once",
+            DrbgError::ReseedRequired => "the DRBG must be reseeded",
+        };
+        fmt.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DrbgError {}
+
+/// Converts the bytes of a block into the value our ciphers encrypt.
+#[inline(always)]
+fn block(bytes: &[u8]) -> u128 {
+    let mut block = [0u8; AES_BLOCK_SIZE];
+    block.copy_from_slice(bytes);
+    u128::from_le_bytes(block)
+}
+
+/// Runs the BCC function of SP 800-90A for all blocks of the derivation function at once. The
+/// chains only differ in their first block, so the remaining blocks are encrypted interleaved.
+struct Bcc<'a, C: BlockCipher> {
+    cipher: &'a C,
+    chains: [u128; 3],
+    block: [u8; AES_BLOCK_SIZE],
+    length: usize,
+}
+
+impl<'a, C: BlockCipher> Bcc<'a, C> {
+    fn new(cipher: &'a C) -> Self {
+        // The first block of each chain is its big endian index padded with zeros.
+        let chains = core::array::from_fn(|i| {
+            let mut iv = [0u8; AES_BLOCK_SIZE];
+            iv[..4].copy_from_slice(&(i as u32).to_be_bytes());
+            u128::from_le_bytes(iv)
+        });
+
+        Self {
+            cipher,
+            chains: cipher.encrypt_blocks(chains),
+            block: [0u8; AES_BLOCK_SIZE],
+            length: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let count = data.len().min(AES_BLOCK_SIZE - self.length);
+            self.block[self.length..self.length + count].copy_from_slice(&data[..count]);
+            self.length += count;
+            data = &data[count..];
+
+            if self.length == AES_BLOCK_SIZE {
+                let block = u128::from_le_bytes(self.block);
+                self.chains = self
+                    .cipher
+                    .encrypt_blocks(self.chains.map(|chain| chain ^ block));
+                self.length = 0;
+            }
+        }
+    }
+
+    fn finalize(mut self) -> [u8; MAX_SEED_LENGTH] {
+        self.update(&[0x80]);
+        if self.length != 0 {
+            self.update(&[0u8; AES_BLOCK_SIZE][self.length..]);
+        }
+
+        let mut output = [0u8; MAX_SEED_LENGTH];
+        for (chunk, chain) in output.chunks_exact_mut(AES_BLOCK_SIZE).zip(self.chains) {
+            chunk.copy_from_slice(&chain.to_le_bytes());
+        }
+        output
+    }
+}
+
+/// The CTR_DRBG mechanism with a derivation function, generic over the block cipher.
+struct CtrDrbg<C: BlockCipher> {
+    cipher: C,
+    /// The counter block `V`, as a big endian number.
+    v: u128,
+    reseed_counter: u64,
+}
+
+impl<C: BlockCipher> Drop for CtrDrbg<C> {
+    fn drop(&mut self) {
+        self.v = 0;
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<C: BlockCipher> CtrDrbg<C> {
+    const SEED_LENGTH: usize = C::KEY_SIZE + AES_BLOCK_SIZE;
+
+    fn instantiate(
+        entropy_input: &[u8],
+        nonce: &[u8],
+        personalization_string: &[u8],
+    ) -> Result<Self, DrbgError> {
+        Self::check_entropy_input(entropy_input)?;
+        if nonce.len() < C::KEY_SIZE / 2 {
+            return Err(DrbgError::NonceTooShort);
+        }
+        Self::check_input(nonce)?;
+        Self::check_input(personalization_string)?;
+
+        let seed_material = Self::derive(&[entropy_input, nonce, personalization_string])?;
+
+        let mut drbg = Self {
+            cipher: C::new(&[0u8; MAX_SEED_LENGTH][..C::KEY_SIZE]),
+            v: 0,
+            reseed_counter: 1,
+        };
+        drbg.update(&seed_material);
+
+        Ok(drbg)
+    }
+
+    fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) -> Result<(), DrbgError> {
+        Self::check_entropy_input(entropy_input)?;
+        Self::check_input(additional_input)?;
+
+        let seed_material = Self::derive(&[entropy_input, additional_input])?;
+        self.update(&seed_material);
+        self.reseed_counter = 1;
+
+        Ok(())
+    }
+
+    fn generate(&mut self, output: &mut [u8], additional_input: &[u8]) -> Result<(), DrbgError> {
+        if self.reseed_counter > RESEED_INTERVAL {
+            return Err(DrbgError::ReseedRequired);
+        }
+        if output.len() > MAX_REQUEST_SIZE {
+            return Err(DrbgError::RequestTooLarge);
+        }
+        Self::check_input(additional_input)?;
+
+        let additional_input = match additional_input.is_empty() {
+            true => [0u8; MAX_SEED_LENGTH],
+            false => {
+                let additional_input = Self::derive(&[additional_input])?;
+                self.update(&additional_input);
+                additional_input
+            }
+        };
+
+        let mut bulk_chunks = output.chunks_exact_mut(AES_BLOCK_SIZE * BULK_BLOCKS);
+        for bulk_chunk in &mut bulk_chunks {
+            let blocks: [u128; BULK_BLOCKS] = self.next_blocks();
+            for (chunk, block) in bulk_chunk.chunks_exact_mut(AES_BLOCK_SIZE).zip(blocks) {
+                chunk.copy_from_slice(&block.to_le_bytes());
+            }
+        }
+
+        for chunk in bulk_chunks.into_remainder().chunks_mut(AES_BLOCK_SIZE) {
+            let [block] = self.next_blocks();
+            chunk.copy_from_slice(&block.to_le_bytes()[..chunk.len()]);
+        }
+
+        self.update(&additional_input);
+        self.reseed_counter += 1;
+
+        Ok(())
+    }
+
+    fn check_entropy_input(entropy_input: &[u8]) -> Result<(), DrbgError> {
+        match entropy_input.len() < C::KEY_SIZE {
+            true => Err(DrbgError::EntropyTooShort),
+            false => Self::check_input(entropy_input),
+        }
+    }
+
+    fn check_input(input: &[u8]) -> Result<(), DrbgError> {
+        match input.len() as u64 > MAX_INPUT_LENGTH {
+            true => Err(DrbgError::InputTooLong),
+            false => Ok(()),
+        }
+    }
+
+    /// Encrypts the next `N` values of the counter block.
+    #[inline(always)]
+    fn next_blocks<const N: usize>(&mut self) -> [u128; N] {
+        let counters = core::array::from_fn(|i| self.v.wrapping_add(i as u128 + 1).swap_bytes());
+        self.v = self.v.wrapping_add(N as u128);
+        self.cipher.encrypt_blocks(counters)
+    }
+
+    /// The CTR_DRBG_Update function, which derives a new key and counter block.
+    fn update(&mut self, provided_data: &[u8; MAX_SEED_LENGTH]) {
+        // AES-128 only uses the first two blocks. The counter block is replaced afterward, so
+        // the additional block doesn't advance it.
+        let blocks: [u128; 3] = self.next_blocks();
+
+        let mut temp = [0u8; MAX_SEED_LENGTH];
+        for (chunk, block) in temp.chunks_exact_mut(AES_BLOCK_SIZE).zip(blocks) {
+            chunk.copy_from_slice(&block.to_le_bytes());
+        }
+        for (temp, data) in temp.iter_mut().zip(provided_data) {
+            *temp ^= data;
+        }
+
+        self.cipher = C::new(&temp[..C::KEY_SIZE]);
+        self.v = u128::from_be_bytes(temp[C::KEY_SIZE..Self::SEED_LENGTH].try_into().unwrap());
+    }
+
+    /// The Block_Cipher_df function, which derives `SEED_LENGTH` bytes from the concatenation of
+    /// the given inputs.
+    fn derive(inputs: &[&[u8]]) -> Result<[u8; MAX_SEED_LENGTH], DrbgError> {
+        let length = inputs.iter().map(|input| input.len()).sum::<usize>();
+        let length = u32::try_from(length).map_err(|_| DrbgError::InputTooLong)?;
+
+        let cipher = C::new(&DF_KEY[..C::KEY_SIZE]);
+        let mut bcc = Bcc::new(&cipher);
+        bcc.update(&length.to_be_bytes());
+        bcc.update(&(Self::SEED_LENGTH as u32).to_be_bytes());
+        for input in inputs {
+            bcc.update(input);
+        }
+        let temp = bcc.finalize();
+
+        let cipher = C::new(&temp[..C::KEY_SIZE]);
+        let mut x = block(&temp[C::KEY_SIZE..C::KEY_SIZE + AES_BLOCK_SIZE]);
+
+        let mut output = [0u8; MAX_SEED_LENGTH];
+        for chunk in output[..Self::SEED_LENGTH].chunks_exact_mut(AES_BLOCK_SIZE) {
+            [x] = cipher.encrypt_blocks([x]);
+            chunk.copy_from_slice(&x.to_le_bytes());
+        }
+
+        Ok(output)
+    }
+}
+
+macro_rules! impl_drbg {
+    ($name:ident, $key_size:expr) => {
+        impl $name {
+            /// Instantiates the DRBG from the given entropy input, nonce and personalization
+            /// string.
+            ///
+            /// The entropy input must be at least as long as the key and the nonce at least half
+            /// as long. The personalization string may be empty.
+            pub fn instantiate(
+                entropy_input: &[u8],
+                nonce: &[u8],
+                personalization_string: &[u8],
+            ) -> Result<Self, DrbgError> {
+                CtrDrbg::instantiate(entropy_input, nonce, personalization_string).map(Self)
+            }
+
+            /// Instantiates the DRBG with entropy input and nonce from the entropy source of the
+            /// OS.
+            #[cfg(feature = "getrandom")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+            pub fn from_entropy(personalization_string: &[u8]) -> Result<Self, DrbgError> {
+                let entropy_input: [u8; $key_size] = secure_bytes();
+                let nonce: [u8; $key_size / 2] = secure_bytes();
+                Self::instantiate(&entropy_input, &nonce, personalization_string)
+            }
+
+            /// Reseeds the DRBG with the given entropy input and additional input.
+            ///
+            /// The entropy input must be at least as long as the key. The additional input may be
+            /// empty.
+            pub fn reseed(
+                &mut self,
+                entropy_input: &[u8],
+                additional_input: &[u8],
+            ) -> Result<(), DrbgError> {
+                self.0.reseed(entropy_input, additional_input)
+            }
+
+            /// Reseeds the DRBG with entropy input from the entropy source of the OS.
+            #[cfg(feature = "getrandom")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+            pub fn reseed_from_entropy(
+                &mut self,
+                additional_input: &[u8],
+            ) -> Result<(), DrbgError> {
+                let entropy_input: [u8; $key_size] = secure_bytes();
+                self.reseed(&entropy_input, additional_input)
+            }
+
+            /// Fills the output with random bytes. The additional input may be empty.
+            ///
+            /// At most 2^16 bytes can be requested at once. Returns
+            /// [`DrbgError::ReseedRequired`] once the DRBG must be reseeded.
+            pub fn generate(
+                &mut self,
+                output: &mut [u8],
+                additional_input: &[u8],
+            ) -> Result<(), DrbgError> {
+                self.0.generate(output, additional_input)
+            }
+
+            /// Fills the output with random bytes after reseeding the DRBG with the given
+            /// entropy input and additional input, which provides prediction resistance.
+            ///
+            /// The entropy input must be fresh and at least as long as the key.
+            pub fn generate_with_prediction_resistance(
+                &mut self,
+                entropy_input: &[u8],
+                output: &mut [u8],
+                additional_input: &[u8],
+            ) -> Result<(), DrbgError> {
+                if output.len() > MAX_REQUEST_SIZE {
+                    return Err(DrbgError::RequestTooLarge);
+                }
+                self.0.reseed(entropy_input, additional_input)?;
+                self.0.generate(output, &[])
+            }
+
+            /// Returns the number of generate requests since the DRBG was last seeded, plus one.
+            pub fn reseed_counter(&self) -> u64 {
+                self.0.reseed_counter
+            }
+
+            /// Returns `true` if the DRBG is using hardware accelerated AES.
+            pub fn is_hardware_accelerated(&self) -> bool {
+                self.0.cipher.is_hardware_accelerated()
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+                fmt.debug_struct(stringify!($name))
+                    .field("reseed_counter", &self.0.reseed_counter)
+                    .finish_non_exhaustive()
+            }
+        }
+
+        #[cfg(all(feature = "rand_core", feature = "getrandom"))]
+        #[cfg_attr(docsrs, doc(cfg(all(feature = "rand_core", feature = "getrandom"))))]
+        impl rand_core::RngCore for $name {
+            fn next_u32(&mut self) -> u32 {
+                let mut bytes = [0u8; 4];
+                rand_core::RngCore::fill_bytes(self, &mut bytes);
+                u32::from_le_bytes(bytes)
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                let mut bytes = [0u8; 8];
+                rand_core::RngCore::fill_bytes(self, &mut bytes);
+                u64::from_le_bytes(bytes)
+            }
+
+            /// Splits the output into requests of at most 2^16 bytes and reseeds from the
+            /// entropy source of the OS when needed.
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                for chunk in dest.chunks_mut(MAX_REQUEST_SIZE) {
+                    if self.0.reseed_counter > RESEED_INTERVAL {
+                        self.reseed_from_entropy(&[])
+                            .unwrap_or_else(|error| panic!("{error}"));
+                    }
+                    self.generate(chunk, &[])
+                        .unwrap_or_else(|error| panic!("{error}"));
+                }
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                rand_core::RngCore::fill_bytes(self, dest);
+                Ok(())
+            }
+        }
+
+        #[cfg(all(feature = "rand_core", feature = "getrandom"))]
+        #[cfg_attr(docsrs, doc(cfg(all(feature = "rand_core", feature = "getrandom"))))]
+        impl rand_core::CryptoRng for $name {}
+    };
+}
+
+/// The CTR_DRBG of NIST SP 800-90A using AES-128 and a derivation function.
+///
+/// Unlike the PRNG of this crate, it has a security strength of 128 bits, must be reseeded with
+/// fresh entropy after 2^48 requests and optionally provides prediction resistance. It uses the
+/// same hardware accelerated AES backends as the PRNG.
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::Aes128CtrDrbg;
+///
+/// let mut drbg = Aes128CtrDrbg::instantiate(&[1; 16], &[2; 8], b"example").unwrap();
+///
+/// let mut key = [0u8; 16];
+/// drbg.generate(&mut key, &[]).unwrap();
+/// ```
+pub struct Aes128CtrDrbg(CtrDrbg<Aes128Cipher<AES128_ROUNDS>>);
+
+/// The CTR_DRBG of NIST SP 800-90A using AES-256 and a derivation function.
+///
+/// Unlike the PRNG of this crate, it has a security strength of 256 bits, must be reseeded with
+/// fresh entropy after 2^48 requests and optionally provides prediction resistance. It uses the
+/// same hardware accelerated AES backends as the PRNG.
+pub struct Aes256CtrDrbg(CtrDrbg<Aes256Cipher<AES256_ROUNDS>>);
+
+impl_drbg!(Aes128CtrDrbg, 16);
+impl_drbg!(Aes256CtrDrbg, 32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reseed_required() {
+        let mut drbg = Aes128CtrDrbg::instantiate(&[0; 16], &[0; 8], &[]).unwrap();
+        drbg.0.reseed_counter = RESEED_INTERVAL;
+
+        let mut output = [0u8; 16];
+        assert_eq!(drbg.generate(&mut output, &[]), Ok(()));
+        assert_eq!(
+            drbg.generate(&mut output, &[]),
+            Err(DrbgError::ReseedRequired)
+        );
+
+        drbg.reseed(&[1; 16], &[]).unwrap();
+        assert_eq!(drbg.reseed_counter(), 1);
+        assert_eq!(drbg.generate(&mut output, &[]), Ok(()));
+    }
+
+    #[test]
+    fn test_counter_wraps() {
+        let mut drbg = Aes128CtrDrbg::instantiate(&[0; 16], &[0; 8], &[]).unwrap();
+        drbg.0.v = u128::MAX;
+
+        let [block] = drbg.0.next_blocks();
+        assert_eq!(drbg.0.v, 0);
+        assert_eq!([block], drbg.0.cipher.encrypt_blocks([0]));
+    }
+}
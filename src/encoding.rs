@@ -0,0 +1,218 @@
+//! Provides the hex and base64 encoding of the seeds.
+
+use core::fmt;
+
+const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The error that is returned if a seed couldn't be parsed from a string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParseSeedError {
+    /// The string doesn't have the number of characters of the encoded seed.
+    InvalidLength {
+        /// The number of characters of the encoded seed.
+        expected: usize,
+        /// The number of characters of the string.
+        found: usize,
+    },
+    /// The string contains a character that isn't part of the encoding.
+    InvalidCharacter {
+        /// The invalid character.
+        character: char,
+        /// The position of the character in the string, counted in characters.
+        position: usize,
+    },
+    /// The padding of a base64 string is invalid or the unused bits of its last character are
+    /// not zero.
+    InvalidPadding,
+}
+
+impl fmt::Display for ParseSeedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseSeedError::InvalidLength { expected, found } => write!(
+                fmt,
+                "invalid seed length: expected {expected} characters, found {found}"
+            ),
+            ParseSeedError::InvalidCharacter {
+                character,
+                position,
+            } => write!(
+                fmt,
+                "invalid character {character:?} at position {position} of the seed"
+            ),
+            ParseSeedError::InvalidPadding => fmt.write_str("invalid base64 padding of the seed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseSeedError {}
+
+/// Displays a seed as a hex string, created by `reveal()` of the seeds. The `Debug` output
+/// contains the name of the seed type.
+#[derive(Copy, Clone)]
+pub struct Reveal<'a> {
+    pub(crate) name: &'static str,
+    pub(crate) bytes: &'a [u8],
+}
+
+impl fmt::Display for Reveal<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(fmt, self.bytes)
+    }
+}
+
+impl fmt::Debug for Reveal<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple(self.name)
+            .field(&format_args!("{self}"))
+            .finish()
+    }
+}
+
+/// Displays a seed as a base64 string with padding, created by `base64()` of the seeds.
+#[derive(Copy, Clone)]
+pub struct Base64<'a>(pub(crate) &'a [u8]);
+
+impl fmt::Display for Base64<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for chunk in self.0.chunks(3) {
+            let mut group = [0u8; 3];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let value = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+
+            let mut chars = [b'='; 4];
+            for (i, char) in chars.iter_mut().take(chunk.len() + 1).enumerate() {
+                *char = BASE64_CHARS[(value >> (18 - 6 * i)) as usize & 0x3F];
+            }
+            // SAFETY: The characters are ASCII.
+            fmt.write_str(unsafe { core::str::from_utf8_unchecked(&chars) })?;
+        }
+        Ok(())
+    }
+}
+
+fn write_hex(fmt: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes {
+        let chars = [
+            HEX_CHARS[(byte >> 4) as usize],
+            HEX_CHARS[(byte & 0xF) as usize],
+        ];
+        // SAFETY: The characters are ASCII.
+        fmt.write_str(unsafe { core::str::from_utf8_unchecked(&chars) })?;
+    }
+    Ok(())
+}
+
+/// Returns the error for the invalid character at the given byte index.
+fn invalid_character(string: &str, index: usize) -> ParseSeedError {
+    // The string is only checked for ASCII characters, so the index could be inside of a
+    // multi-byte character.
+    let start = (0..=index)
+        .rev()
+        .find(|&i| string.is_char_boundary(i))
+        .unwrap_or(0);
+
+    ParseSeedError::InvalidCharacter {
+        character: string[start..].chars().next().unwrap_or_default(),
+        position: string[..start].chars().count(),
+    }
+}
+
+fn check_length(string: &str, expected: usize) -> Result<(), ParseSeedError> {
+    if string.len() == expected {
+        return Ok(());
+    }
+
+    let found = string.chars().count();
+    if found == expected {
+        // The string has the right number of characters, but contains non-ASCII characters.
+        let index = string
+            .bytes()
+            .position(|byte| !byte.is_ascii())
+            .unwrap_or(0);
+        return Err(invalid_character(string, index));
+    }
+
+    Err(ParseSeedError::InvalidLength { expected, found })
+}
+
+pub(crate) fn decode_hex<const N: usize>(string: &str) -> Result<[u8; N], ParseSeedError> {
+    fn nibble(char: u8) -> Option<u8> {
+        match char {
+            b'0'..=b'9' => Some(char - b'0'),
+            b'a'..=b'f' => Some(char - b'a' + 10),
+            b'A'..=b'F' => Some(char - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    check_length(string, N * 2)?;
+
+    let chars = string.as_bytes();
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let high = nibble(chars[i * 2]).ok_or_else(|| invalid_character(string, i * 2))?;
+        let low = nibble(chars[i * 2 + 1]).ok_or_else(|| invalid_character(string, i * 2 + 1))?;
+        *byte = (high << 4) | low;
+    }
+    Ok(bytes)
+}
+
+pub(crate) fn decode_base64<const N: usize>(string: &str) -> Result<[u8; N], ParseSeedError> {
+    fn sextet(char: u8) -> Option<u8> {
+        match char {
+            b'A'..=b'Z' => Some(char - b'A'),
+            b'a'..=b'z' => Some(char - b'a' + 26),
+            b'0'..=b'9' => Some(char - b'0' + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let length = N.div_ceil(3) * 4;
+    let padding = (3 - N % 3) % 3;
+    check_length(string, length)?;
+
+    let mut bytes = [0u8; N];
+    let mut position = 0;
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for (i, &char) in string.as_bytes().iter().enumerate() {
+        if i >= length - padding {
+            if char != b'=' {
+                return Err(match sextet(char) {
+                    Some(_) => ParseSeedError::InvalidPadding,
+                    None => invalid_character(string, i),
+                });
+            }
+            continue;
+        }
+
+        let value = match sextet(char) {
+            Some(value) => value,
+            None if char == b'=' => return Err(ParseSeedError::InvalidPadding),
+            None => return Err(invalid_character(string, i)),
+        };
+
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes[position] = (buffer >> bits) as u8;
+            position += 1;
+        }
+    }
+
+    // The unused bits of the last character must be zero, so that every seed has exactly one
+    // encoding.
+    if buffer & ((1 << bits) - 1) != 0 {
+        return Err(ParseSeedError::InvalidPadding);
+    }
+
+    Ok(bytes)
+}
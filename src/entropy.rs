@@ -0,0 +1,156 @@
+//! Provides the entropy sources used to seed the random number generators.
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The error that is returned if an entropy source couldn't provide entropy.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EntropyError {
+    /// The entropy source isn't available on the current target.
+    Unavailable,
+    /// The entropy source failed to provide entropy.
+    Failed,
+    /// The entropy source failed a health test and must not be used anymore.
+    HealthTestFailed,
+}
+
+impl core::fmt::Display for EntropyError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let message = match self {
+            EntropyError::Unavailable => "the entropy source is not available",
+            EntropyError::Failed => "the entropy source failed to provide entropy",
+            EntropyError::HealthTestFailed => "the entropy source failed a health test",
+        };
+        fmt.write_str(message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EntropyError {}
+
+/// A source of entropy, that is used to seed the random number generators with
+/// [`Random::from_entropy_source()`](crate::Random::from_entropy_source).
+///
+/// This allows to seed the generators on targets without an OS provided entropy source, for
+/// example from the hardware TRNG of a microcontroller. The trait is implemented for closures
+/// with the signature of [`EntropySource::fill()`].
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::*;
+///
+/// struct Trng;
+///
+/// impl EntropySource for Trng {
+///     fn fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+///         // Read the bytes from the hardware TRNG.
+///         bytes.fill(0x42);
+///         Ok(())
+///     }
+/// }
+///
+/// let rng = Aes128Ctr64::from_entropy_source(&mut Trng).unwrap();
+/// let _ = rng.u64();
+/// ```
+pub trait EntropySource {
+    /// Fills the given bytes with entropy.
+    ///
+    /// The bytes must only be used if this returns `Ok`.
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError>;
+
+    /// Combines this source with the given source by XORing their output.
+    ///
+    /// The combined output is as unpredictable as the output of the better source, so a broken or
+    /// compromised source can't weaken the other one. It fails if either source fails.
+    fn xor<S: EntropySource>(self, other: S) -> Xor<Self, S>
+    where
+        Self: Sized,
+    {
+        Xor {
+            first: self,
+            second: other,
+        }
+    }
+
+    /// Uses the given source if this source fails.
+    fn or<S: EntropySource>(self, other: S) -> Or<Self, S>
+    where
+        Self: Sized,
+    {
+        Or {
+            first: self,
+            second: other,
+        }
+    }
+}
+
+impl<F> EntropySource for F
+where
+    F: FnMut(&mut [u8]) -> Result<(), EntropyError>,
+{
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self(bytes)
+    }
+}
+
+/// Combines two entropy sources by XORing their output, created by [`EntropySource::xor()`].
+#[derive(Clone, Debug)]
+pub struct Xor<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: EntropySource, B: EntropySource> EntropySource for Xor<A, B> {
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.first.fill(bytes)?;
+
+        let mut buffer = [0u8; 64];
+        let result = bytes.chunks_mut(buffer.len()).try_for_each(|chunk| {
+            let buffer = &mut buffer[..chunk.len()];
+            self.second.fill(buffer)?;
+            chunk
+                .iter_mut()
+                .zip(buffer.iter())
+                .for_each(|(byte, other)| *byte ^= other);
+            Ok(())
+        });
+
+        #[cfg(feature = "zeroize")]
+        buffer.zeroize();
+
+        result
+    }
+}
+
+/// Uses a second entropy source if the first one fails, created by [`EntropySource::or()`].
+#[derive(Clone, Debug)]
+pub struct Or<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: EntropySource, B: EntropySource> EntropySource for Or<A, B> {
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.first.fill(bytes).or_else(|_| self.second.fill(bytes))
+    }
+}
+
+/// The entropy source of the OS.
+#[cfg(feature = "getrandom")]
+#[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct OsEntropy;
+
+#[cfg(feature = "getrandom")]
+impl EntropySource for OsEntropy {
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        getrandom::getrandom(bytes).map_err(|error| {
+            if error == getrandom::Error::UNSUPPORTED {
+                EntropyError::Unavailable
+            } else {
+                EntropyError::Failed
+            }
+        })
+    }
+}
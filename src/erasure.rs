@@ -0,0 +1,296 @@
+//! Provides the random number generators with fast key erasure.
+
+use core::cell::RefCell;
+
+use crate::backend::leftover::Leftover;
+use crate::cipher::{wipe, BlockCipher};
+use crate::constants::{AES128_ROUNDS, AES256_KEY_SIZE, AES256_ROUNDS, AES_BLOCK_SIZE};
+use crate::implementation::leftover_methods;
+use crate::kdf::FROM_U64_CONTEXT;
+use crate::{seeds, Aes128Cipher, Aes256Cipher, Random};
+
+/// The number of blocks that are generated with one key. Bernstein's construction uses 768 bytes.
+const BATCH_BLOCKS: usize = 48;
+
+/// A batch of blocks and the key of the next batch.
+struct Batch<C: BlockCipher> {
+    cipher: C,
+    blocks: [u128; BATCH_BLOCKS],
+    position: usize,
+}
+
+impl<C: BlockCipher> Drop for Batch<C> {
+    fn drop(&mut self) {
+        self.blocks = [0; BATCH_BLOCKS];
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl<C: BlockCipher> Batch<C> {
+    /// The number of blocks of each batch that become the next key.
+    const KEY_BLOCKS: usize = C::KEY_SIZE / AES_BLOCK_SIZE;
+
+    fn new(key: &[u8]) -> Self {
+        let mut batch = Self {
+            cipher: C::new(key),
+            blocks: [0; BATCH_BLOCKS],
+            position: BATCH_BLOCKS,
+        };
+        // The first refill already erases the round keys of the seed.
+        batch.refill();
+        batch
+    }
+
+    /// Encrypts the next batch and replaces the key with its first blocks.
+    fn refill(&mut self) {
+        // Every batch uses a new key, so the counters can start at zero.
+        self.blocks = self
+            .cipher
+            .encrypt_blocks(core::array::from_fn(|i| i as u128));
+
+        let mut key = [0u8; AES256_KEY_SIZE];
+        for (chunk, block) in key
+            .chunks_exact_mut(AES_BLOCK_SIZE)
+            .zip(&mut self.blocks[..Self::KEY_BLOCKS])
+        {
+            chunk.copy_from_slice(&core::mem::take(block).to_le_bytes());
+        }
+
+        // Dropping the old cipher erases its round keys.
+        self.cipher = C::new(&key[..C::KEY_SIZE]);
+        wipe(&mut key);
+        self.position = Self::KEY_BLOCKS;
+    }
+
+    /// Hands out the next block and erases it from the batch.
+    #[inline(always)]
+    fn next(&mut self) -> u128 {
+        if self.position == BATCH_BLOCKS {
+            self.refill();
+        }
+
+        let block = core::mem::take(&mut self.blocks[self.position]);
+        self.position += 1;
+        block
+    }
+}
+
+/// A random number generator based on the AES-128 block cipher, that provides forward secrecy
+/// with fast key erasure.
+///
+/// The generator encrypts batches of 48 counter blocks. The first block of each batch becomes the
+/// key of the next batch, the round keys of the old key are erased and the remaining blocks are
+/// handed out, each erased once it was returned. Capturing the state of the generator therefore
+/// doesn't reveal any output that was already returned.
+///
+/// The counter of the generator is the position of the next block in the current batch, since the
+/// counter of every batch starts at zero with a new key.
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::*;
+///
+/// let rng = Aes128FastKeyErasure::from_seed(seeds::Aes128FastKeyErasureSeed::new([1; 16]));
+/// let _ = rng.u32();
+/// ```
+pub struct Aes128FastKeyErasure {
+    batch: RefCell<Batch<Aes128Cipher<AES128_ROUNDS>>>,
+    leftover: Leftover,
+}
+
+/// A random number generator based on the AES-256 block cipher, that provides forward secrecy
+/// with fast key erasure.
+///
+/// The generator encrypts batches of 48 counter blocks. The first two blocks of each batch become
+/// the key of the next batch, the round keys of the old key are erased and the remaining blocks
+/// are handed out, each erased once it was returned. Capturing the state of the generator
+/// therefore doesn't reveal any output that was already returned.
+///
+/// The counter of the generator is the position of the next block in the current batch, since the
+/// counter of every batch starts at zero with a new key.
+pub struct Aes256FastKeyErasure {
+    batch: RefCell<Batch<Aes256Cipher<AES256_ROUNDS>>>,
+    leftover: Leftover,
+}
+
+macro_rules! impl_fast_key_erasure {
+    ($name:ident, $seed:ident) => {
+        impl $name {
+            fn leftover_impl(&self) -> &Leftover {
+                &self.leftover
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                // The unused bits are output that wasn't returned yet.
+                self.leftover.clear();
+            }
+        }
+
+        impl Random for $name {
+            type Seed = seeds::$seed;
+            type Counter = u64;
+
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let rng = Self {
+                    batch: RefCell::new(Batch::new(seed.as_ref())),
+                    leftover: Leftover::new(),
+                };
+                wipe(seed.as_mut());
+                rng
+            }
+
+            fn seed(&self, mut seed: Self::Seed) {
+                *self.batch.borrow_mut() = Batch::new(seed.as_ref());
+                self.leftover.clear();
+                wipe(seed.as_mut());
+            }
+
+            #[cfg(feature = "getrandom")]
+            fn from_entropy() -> Self {
+                Random::from_seed(seeds::$seed::from_entropy())
+            }
+
+            #[cfg(feature = "getrandom")]
+            fn seed_from_entropy(&self) {
+                Random::seed(self, seeds::$seed::from_entropy())
+            }
+
+            fn from_u64(state: u64) -> Self {
+                Random::from_seed(seeds::$seed::derive(FROM_U64_CONTEXT, &state.to_le_bytes()))
+            }
+
+            fn is_hardware_accelerated(&self) -> bool {
+                self.batch.borrow().cipher.is_hardware_accelerated()
+            }
+
+            fn counter(&self) -> Self::Counter {
+                self.batch.borrow().position as u64
+            }
+
+            #[inline(always)]
+            fn next(&self) -> u128 {
+                self.batch.borrow_mut().next()
+            }
+
+            leftover_methods!();
+
+            fn fill_bytes(&self, slice: &mut [u8]) {
+                let mut batch = self.batch.borrow_mut();
+                for chunk in slice.chunks_mut(AES_BLOCK_SIZE) {
+                    let random_bytes = batch.next().to_le_bytes();
+                    chunk.copy_from_slice(&random_bytes[..chunk.len()]);
+                }
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+                fmt.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+
+        #[cfg(feature = "rand_core")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+        impl rand_core::RngCore for $name {
+            #[inline(always)]
+            fn next_u32(&mut self) -> u32 {
+                Random::u32(self)
+            }
+
+            #[inline(always)]
+            fn next_u64(&mut self) -> u64 {
+                Random::u64(self)
+            }
+
+            #[inline(always)]
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                Random::fill_bytes(self, dest);
+            }
+
+            #[inline(always)]
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                Random::fill_bytes(self, dest);
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "rand_core")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+        impl rand_core::SeedableRng for $name {
+            type Seed = seeds::$seed;
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                Random::from_seed(seed)
+            }
+        }
+    };
+}
+
+impl_fast_key_erasure!(Aes128FastKeyErasure, Aes128FastKeyErasureSeed);
+impl_fast_key_erasure!(Aes256FastKeyErasure, Aes256FastKeyErasureSeed);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_returned_blocks_are_erased() {
+        let rng = Aes128FastKeyErasure::from_seed([0; 16].into());
+
+        for _ in 0..BATCH_BLOCKS * 2 {
+            let block = rng.next();
+            let batch = rng.batch.borrow();
+
+            assert_ne!(block, 0);
+            assert!(batch.blocks[..batch.position]
+                .iter()
+                .all(|block| *block == 0));
+        }
+    }
+
+    #[test]
+    fn test_old_key_is_erased() {
+        type Cipher = Aes128Cipher<AES128_ROUNDS>;
+
+        let derive_key = |cipher: &Cipher| cipher.encrypt_blocks([0u128])[0].to_le_bytes();
+        let probe: [u128; 4] = core::array::from_fn(|i| i as u128);
+
+        let mut batch = Batch::<Cipher>::new(&[7; 16]);
+        let mut key = derive_key(&Cipher::new(&[7; 16]));
+
+        for _ in 0..3 {
+            let old_key = key;
+            key = derive_key(&batch.cipher);
+            batch.refill();
+
+            // Neither the old key nor the current key remain in the blocks of the batch.
+            assert!(batch.blocks[..Batch::<Cipher>::KEY_BLOCKS]
+                .iter()
+                .all(|block| *block == 0));
+            assert!(!batch
+                .blocks
+                .iter()
+                .any(|block| block.to_le_bytes() == old_key || block.to_le_bytes() == key));
+
+            // The cipher is created from the new key, the old round keys are gone.
+            assert_eq!(
+                batch.cipher.encrypt_blocks(probe),
+                Cipher::new(&key).encrypt_blocks(probe)
+            );
+            assert_ne!(
+                batch.cipher.encrypt_blocks(probe),
+                Cipher::new(&old_key).encrypt_blocks(probe)
+            );
+        }
+    }
+
+    #[test]
+    fn test_wipe() {
+        let mut key = [0xAB; 32];
+        wipe(&mut key);
+        assert_eq!(key, [0; 32]);
+    }
+}
@@ -0,0 +1,86 @@
+use core::cell::Cell;
+
+/// Defines what happens once the counter of a random number generator is exhausted.
+///
+/// A counter is exhausted once the block of its last value was generated. For the 64-bit counter
+/// variants this happens after at most 2^64 blocks, for the 128-bit counter variants after at most
+/// 2^128 blocks. Continuing from there would repeat the already generated stream.
+///
+/// The policy is the same on every backend. The default policy is [`ExhaustionPolicy::Panic`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ExhaustionPolicy {
+    /// [`Random::next()`](crate::Random::next) and all methods based on it panic once the counter
+    /// is exhausted. [`Random::try_next()`](crate::Random::try_next) returns
+    /// [`CounterExhausted`] instead.
+    ///
+    /// An exhausted generator keeps its last counter value. Changing the position of the
+    /// generator (for example by re-seeding or calling
+    /// [`Seek::set_counter()`](crate::Seek::set_counter)) makes it usable again.
+    #[default]
+    Panic,
+    /// The counter silently wraps around and the stream repeats.
+    Wrap,
+    /// The counter wraps around and the nonce is incremented, which continues with the next
+    /// stream (see [`Stream`](crate::Stream)). The 128-bit counter variants have no nonce and
+    /// wrap around like [`ExhaustionPolicy::Wrap`].
+    ///
+    /// The next stream is the stream that [`Stream::next_stream()`](crate::Stream::next_stream)
+    /// hands out next, so the output overlaps with the output of a PRNG that uses it. Only use
+    /// this policy if no other PRNG uses the following streams.
+    RollOver,
+}
+
+/// The error that is returned once the counter of a random number generator is exhausted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CounterExhausted;
+
+impl core::fmt::Display for CounterExhausted {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt.write_str("the counter of the random number generator is exhausted")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CounterExhausted {}
+
+/// Holds the exhaustion policy of a generator and whether its counter is exhausted.
+#[derive(Clone)]
+pub(crate) struct Exhaustion {
+    policy: Cell<ExhaustionPolicy>,
+    exhausted: Cell<bool>,
+}
+
+impl Exhaustion {
+    pub(crate) const fn new() -> Self {
+        Self {
+            policy: Cell::new(ExhaustionPolicy::Panic),
+            exhausted: Cell::new(false),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn policy(&self) -> ExhaustionPolicy {
+        self.policy.get()
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_policy(&self, policy: ExhaustionPolicy) {
+        self.policy.set(policy);
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.exhausted.get()
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_exhausted(&self) {
+        self.exhausted.set(true);
+    }
+
+    /// Marks the counter as not exhausted. The policy is kept.
+    #[inline(always)]
+    pub(crate) fn clear(&self) {
+        self.exhausted.set(false);
+    }
+}
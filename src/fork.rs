@@ -0,0 +1,277 @@
+//! Provides the detection of a fork() of the process.
+
+use core::cell::Cell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::entropy::{EntropyError, EntropySource};
+use crate::exhaustion::{CounterExhausted, ExhaustionPolicy};
+use crate::state::{RoundsMismatch, State};
+use crate::{Checkpoint, Random, Seek};
+
+/// The number of forks, that happened since the fork handler was registered.
+static FORKS: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(unix)]
+unsafe extern "C" fn on_fork_child() {
+    // Only async-signal-safe operations are allowed in the child handler.
+    FORKS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns the number of forks, that happened in the ancestry of the current process. A process
+/// that observes a different value than before is a forked child.
+///
+/// The first call registers the fork handler. Forks that happen before can't be detected.
+#[inline(always)]
+pub(crate) fn fork_count() -> usize {
+    #[cfg(unix)]
+    {
+        static REGISTER: std::sync::Once = std::sync::Once::new();
+        REGISTER.call_once(|| {
+            // SAFETY: The handler only increments an atomic counter.
+            let result = unsafe { libc::pthread_atfork(None, None, Some(on_fork_child)) };
+            assert_eq!(result, 0, "Can't register the fork handler");
+        });
+    }
+
+    FORKS.load(Ordering::Relaxed)
+}
+
+/// A wrapper that reseeds a random number generator from the entropy source of the OS once the
+/// process was forked.
+///
+/// A forked child inherits the state of the generator and would produce the same output as its
+/// parent. The wrapper detects the fork with a `pthread_atfork()` handler and reseeds the
+/// generator in the child before its next output. Checking for a fork costs a single atomic
+/// load. Targets without fork() never reseed.
+///
+/// # Panics
+///
+/// Panics if the entropy source of the OS fails in the forked child, since continuing would
+/// repeat the output of the parent.
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::*;
+///
+/// let rng = ForkSafe::new(Aes128Ctr64::from_entropy());
+/// let _ = rng.u64();
+/// ```
+pub struct ForkSafe<R: Random> {
+    rng: R,
+    forks: Cell<usize>,
+}
+
+impl<R: Random> ForkSafe<R> {
+    /// Wraps the given generator.
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            forks: Cell::new(fork_count()),
+        }
+    }
+
+    /// Returns a reference to the wrapped generator.
+    pub fn inner(&self) -> &R {
+        &self.rng
+    }
+
+    /// Returns the wrapped generator.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    /// Reseeds the generator if the process was forked since the last check.
+    #[inline(always)]
+    fn check(&self) {
+        let forks = fork_count();
+        if forks != self.forks.get() {
+            self.forks.set(forks);
+            self.rng.seed_from_entropy();
+        }
+    }
+}
+
+impl<R: Random> Random for ForkSafe<R> {
+    type Seed = R::Seed;
+    type Counter = R::Counter;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(R::from_seed(seed))
+    }
+
+    fn seed(&self, seed: Self::Seed) {
+        self.forks.set(fork_count());
+        self.rng.seed(seed);
+    }
+
+    fn from_entropy() -> Self {
+        Self::new(R::from_entropy())
+    }
+
+    fn seed_from_entropy(&self) {
+        self.forks.set(fork_count());
+        self.rng.seed_from_entropy();
+    }
+
+    fn from_entropy_source(source: &mut impl EntropySource) -> Result<Self, EntropyError>
+    where
+        Self::Seed: AsMut<[u8]> + Default,
+    {
+        R::from_entropy_source(source).map(Self::new)
+    }
+
+    fn from_u64(state: u64) -> Self
+    where
+        Self::Seed: AsMut<[u8]> + Default,
+    {
+        Self::new(R::from_u64(state))
+    }
+
+    fn from_random(rng: &impl Random) -> Self
+    where
+        Self::Seed: AsMut<[u8]> + Default,
+    {
+        Self::new(R::from_random(rng))
+    }
+
+    fn is_hardware_accelerated(&self) -> bool {
+        self.rng.is_hardware_accelerated()
+    }
+
+    fn counter(&self) -> Self::Counter {
+        self.rng.counter()
+    }
+
+    #[inline(always)]
+    fn next(&self) -> u128 {
+        self.check();
+        self.rng.next()
+    }
+
+    #[inline(always)]
+    fn try_next(&self) -> Result<u128, CounterExhausted> {
+        self.check();
+        self.rng.try_next()
+    }
+
+    #[inline(always)]
+    fn next_blocks<const N: usize>(&self) -> [u128; N] {
+        self.check();
+        self.rng.next_blocks()
+    }
+
+    #[inline(always)]
+    fn u8(&self) -> u8 {
+        self.check();
+        self.rng.u8()
+    }
+
+    #[inline(always)]
+    fn u16(&self) -> u16 {
+        self.check();
+        self.rng.u16()
+    }
+
+    #[inline(always)]
+    fn u32(&self) -> u32 {
+        self.check();
+        self.rng.u32()
+    }
+
+    #[inline(always)]
+    fn u64(&self) -> u64 {
+        self.check();
+        self.rng.u64()
+    }
+
+    #[inline(always)]
+    fn bool(&self) -> bool {
+        self.check();
+        self.rng.bool()
+    }
+
+    fn fill_bytes(&self, slice: &mut [u8]) {
+        self.check();
+        self.rng.fill_bytes(slice);
+    }
+}
+
+impl<R: Seek> Seek for ForkSafe<R> {
+    fn set_counter(&self, counter: Self::Counter) {
+        self.rng.set_counter(counter);
+    }
+
+    fn advance(&self, n: Self::Counter) {
+        self.rng.advance(n);
+    }
+
+    fn rewind(&self, n: Self::Counter) {
+        self.rng.rewind(n);
+    }
+
+    fn remaining(&self) -> u128 {
+        self.rng.remaining()
+    }
+
+    fn exhaustion_policy(&self) -> ExhaustionPolicy {
+        self.rng.exhaustion_policy()
+    }
+
+    fn set_exhaustion_policy(&self, policy: ExhaustionPolicy) {
+        self.rng.set_exhaustion_policy(policy);
+    }
+}
+
+impl<R: Checkpoint> Checkpoint for ForkSafe<R> {
+    fn snapshot(&self) -> State<Self::Seed> {
+        self.rng.snapshot()
+    }
+
+    fn restore(&self, state: &State<Self::Seed>) -> Result<(), RoundsMismatch> {
+        self.rng.restore(state)?;
+        self.forks.set(fork_count());
+        Ok(())
+    }
+}
+
+impl<R: Random> core::fmt::Debug for ForkSafe<R> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt.debug_struct("ForkSafe").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "rand_core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+impl<R: Random> rand_core::RngCore for ForkSafe<R> {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        Random::u32(self)
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        Random::u64(self)
+    }
+
+    #[inline(always)]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        Random::fill_bytes(self, dest);
+    }
+
+    #[inline(always)]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        Random::fill_bytes(self, dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand_core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+impl<R: Random + rand_core::SeedableRng> rand_core::SeedableRng for ForkSafe<R> {
+    type Seed = <R as rand_core::SeedableRng>::Seed;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(rand_core::SeedableRng::from_seed(seed))
+    }
+}
@@ -0,0 +1,357 @@
+//! Provides an entropy source that uses the random number generator of the CPU.
+
+use crate::entropy::{EntropyError, EntropySource};
+
+/// The number of attempts of RDRAND and RNDR. They only fail if the DRBG of the CPU can't keep up
+/// with the requests, which Intel considers a hardware failure after 10 attempts.
+#[allow(unused)]
+const DRBG_ATTEMPTS: usize = 10;
+
+/// The number of attempts of RDSEED and RNDRRS. They wait for fresh output of the entropy
+/// conditioner and fail regularly under load.
+#[allow(unused)]
+const SEED_ATTEMPTS: usize = 128;
+
+/// The number of words that the startup health test draws.
+#[allow(unused)]
+const STARTUP_WORDS: usize = 8;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Instruction {
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    RdSeed,
+    #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+    RdRand,
+    #[cfg(target_arch = "aarch64")]
+    Rndrrs,
+    #[cfg(target_arch = "aarch64")]
+    Rndr,
+}
+
+impl Instruction {
+    /// Returns the best supported instruction. `seed` prefers the instructions that return
+    /// fresh output of the entropy conditioner.
+    fn detect(seed: bool) -> Option<Self> {
+        #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+        {
+            if seed && x86::has_rdseed() {
+                return Some(Instruction::RdSeed);
+            }
+            if x86::has_rdrand() {
+                return Some(Instruction::RdRand);
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        if aarch64::has_rndr() {
+            return Some(if seed {
+                Instruction::Rndrrs
+            } else {
+                Instruction::Rndr
+            });
+        }
+
+        let _ = seed;
+        None
+    }
+
+    /// Returns the next word of the instruction, or `None` if all attempts failed.
+    fn word(self) -> Option<u64> {
+        // SAFETY: The instruction was detected as supported.
+        match self {
+            #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+            Instruction::RdSeed => attempt(SEED_ATTEMPTS, || unsafe { x86::rdseed() }),
+            #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+            Instruction::RdRand => attempt(DRBG_ATTEMPTS, || unsafe { x86::rdrand() }),
+            #[cfg(target_arch = "aarch64")]
+            Instruction::Rndrrs => attempt(SEED_ATTEMPTS, || unsafe { aarch64::rndrrs() }),
+            #[cfg(target_arch = "aarch64")]
+            Instruction::Rndr => attempt(DRBG_ATTEMPTS, || unsafe { aarch64::rndr() }),
+        }
+    }
+}
+
+#[allow(unused)]
+fn attempt(attempts: usize, mut f: impl FnMut() -> Option<u64>) -> Option<u64> {
+    for _ in 0..attempts {
+        if let Some(word) = f() {
+            return Some(word);
+        }
+        core::hint::spin_loop();
+    }
+    None
+}
+
+/// The repetition count test of NIST SP 800-90B with a cutoff of two for 64-bit words. A working
+/// random number generator repeats a word with a probability of 2^-64, while a broken one often
+/// gets stuck on a constant value.
+#[derive(Copy, Clone, Debug)]
+struct RepetitionTest {
+    last: Option<u64>,
+    failed: bool,
+}
+
+impl RepetitionTest {
+    fn new() -> Self {
+        Self {
+            last: None,
+            failed: false,
+        }
+    }
+
+    /// Checks the next word. Once the test failed, it stays failed.
+    fn check(&mut self, word: u64) -> Result<u64, EntropyError> {
+        if self.failed || self.last == Some(word) {
+            self.failed = true;
+            return Err(EntropyError::HealthTestFailed);
+        }
+        self.last = Some(word);
+        Ok(word)
+    }
+}
+
+/// An entropy source that uses the random number generator of the CPU.
+///
+/// On x86 and x86_64, [`HardwareEntropy::new()`] uses RDSEED, or RDRAND if the CPU doesn't
+/// support RDSEED. On aarch64 it uses RNDRRS of `FEAT_RNG`. [`HardwareEntropy::drbg()`] uses
+/// RDRAND and RNDR instead, which return the output of a DRBG in the CPU, that is regularly
+/// reseeded by the entropy conditioner. They are faster and fail less often under load.
+///
+/// The instructions are detected at runtime with the `std` feature. In `no_std`, x86 uses CPUID
+/// directly, while aarch64 requires the `rand` target feature at compile time, since the ID
+/// registers can't be read at every exception level. On all other targets the source is not
+/// available.
+///
+/// Failed instructions are retried a few times. The source runs a startup health test on
+/// creation and checks every word with the repetition count test of NIST SP 800-90B, which
+/// detects a random number generator that got stuck on a constant value. Once a health test
+/// failed, the source returns [`EntropyError::HealthTestFailed`] forever.
+///
+/// The source can be combined with the OS entropy source with [`EntropySource::xor()`], so that
+/// the seed stays secure as long as one of them works.
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::*;
+///
+/// if let Ok(hardware) = HardwareEntropy::new() {
+///     let mut source = hardware.xor(OsEntropy);
+///     let rng = Aes128Ctr64::from_entropy_source(&mut source).unwrap();
+///     let _ = rng.u64();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct HardwareEntropy {
+    instruction: Instruction,
+    test: RepetitionTest,
+}
+
+impl HardwareEntropy {
+    /// Creates the entropy source, that prefers the instructions that return fresh output of
+    /// the entropy conditioner.
+    ///
+    /// Returns [`EntropyError::Unavailable`] if the CPU has no random number generator and
+    /// [`EntropyError::HealthTestFailed`] if it fails the startup health test.
+    pub fn new() -> Result<Self, EntropyError> {
+        Self::with_instruction(Instruction::detect(true))
+    }
+
+    /// Creates the entropy source, that uses the DRBG of the CPU.
+    ///
+    /// Returns [`EntropyError::Unavailable`] if the CPU has no random number generator and
+    /// [`EntropyError::HealthTestFailed`] if it fails the startup health test.
+    pub fn drbg() -> Result<Self, EntropyError> {
+        Self::with_instruction(Instruction::detect(false))
+    }
+
+    /// Returns `true` if the CPU has a random number generator.
+    pub fn is_available() -> bool {
+        Instruction::detect(false).is_some()
+    }
+
+    fn with_instruction(instruction: Option<Instruction>) -> Result<Self, EntropyError> {
+        let mut source = Self {
+            instruction: instruction.ok_or(EntropyError::Unavailable)?,
+            test: RepetitionTest::new(),
+        };
+
+        // The startup test draws a few words, which are discarded.
+        for _ in 0..STARTUP_WORDS {
+            source.next_word()?;
+        }
+
+        Ok(source)
+    }
+
+    fn next_word(&mut self) -> Result<u64, EntropyError> {
+        let word = self.instruction.word().ok_or(EntropyError::Failed)?;
+        self.test.check(word)
+    }
+}
+
+impl EntropySource for HardwareEntropy {
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        for chunk in bytes.chunks_mut(8) {
+            let word = self.next_word()?;
+            chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+        }
+
+        // The repetition test keeps the last word, so it is replaced with a word that isn't
+        // part of the output.
+        self.next_word()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    pub(super) fn has_rdrand() -> bool {
+        #[cfg(feature = "std")]
+        let detected = std::arch::is_x86_feature_detected!("rdrand");
+        #[cfg(not(feature = "std"))]
+        let detected = cpuid(1).is_some_and(|result| result.ecx & (1 << 30) != 0);
+
+        cfg!(target_feature = "rdrand") || detected
+    }
+
+    pub(super) fn has_rdseed() -> bool {
+        #[cfg(feature = "std")]
+        let detected = std::arch::is_x86_feature_detected!("rdseed");
+        #[cfg(not(feature = "std"))]
+        let detected = cpuid(7).is_some_and(|result| result.ebx & (1 << 18) != 0);
+
+        cfg!(target_feature = "rdseed") || detected
+    }
+
+    /// Returns the result of CPUID for the given leaf, if the CPU supports it.
+    #[cfg(not(feature = "std"))]
+    #[allow(unused_unsafe)]
+    fn cpuid(leaf: u32) -> Option<CpuidResult> {
+        // SAFETY: CPUID is supported by every x86 CPU since the Pentium.
+        unsafe {
+            let (max_leaf, _) = __get_cpuid_max(0);
+            (leaf <= max_leaf).then(|| __cpuid_count(leaf, 0))
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "rdrand")]
+    #[allow(unused_unsafe)]
+    pub(super) unsafe fn rdrand() -> Option<u64> {
+        let mut word = 0;
+        (unsafe { _rdrand64_step(&mut word) } == 1).then_some(word)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "rdseed")]
+    #[allow(unused_unsafe)]
+    pub(super) unsafe fn rdseed() -> Option<u64> {
+        let mut word = 0;
+        (unsafe { _rdseed64_step(&mut word) } == 1).then_some(word)
+    }
+
+    #[cfg(target_arch = "x86")]
+    #[target_feature(enable = "rdrand")]
+    #[allow(unused_unsafe)]
+    pub(super) unsafe fn rdrand() -> Option<u64> {
+        let (mut low, mut high) = (0, 0);
+        let success = unsafe { _rdrand32_step(&mut low) == 1 && _rdrand32_step(&mut high) == 1 };
+        success.then(|| ((high as u64) << 32) | low as u64)
+    }
+
+    #[cfg(target_arch = "x86")]
+    #[target_feature(enable = "rdseed")]
+    #[allow(unused_unsafe)]
+    pub(super) unsafe fn rdseed() -> Option<u64> {
+        let (mut low, mut high) = (0, 0);
+        let success = unsafe { _rdseed32_step(&mut low) == 1 && _rdseed32_step(&mut high) == 1 };
+        success.then(|| ((high as u64) << 32) | low as u64)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use core::arch::asm;
+
+    pub(super) fn has_rndr() -> bool {
+        #[cfg(feature = "std")]
+        let detected = std::arch::is_aarch64_feature_detected!("rand");
+        #[cfg(not(feature = "std"))]
+        let detected = false;
+
+        cfg!(target_feature = "rand") || detected
+    }
+
+    /// Reads RNDR, which returns the output of the DRBG of the CPU.
+    pub(super) unsafe fn rndr() -> Option<u64> {
+        let word: u64;
+        let success: u64;
+        // The register is encoded by its number, so that the assembler doesn't require the
+        // `rand` target feature. A failure sets the Z flag.
+        unsafe {
+            asm!(
+                "mrs {word}, s3_3_c2_c4_0",
+                "cset {success}, ne",
+                word = out(reg) word,
+                success = out(reg) success,
+                options(nomem, nostack),
+            );
+        }
+        (success != 0).then_some(word)
+    }
+
+    /// Reads RNDRRS, which reseeds the DRBG of the CPU before it returns its output.
+    pub(super) unsafe fn rndrrs() -> Option<u64> {
+        let word: u64;
+        let success: u64;
+        unsafe {
+            asm!(
+                "mrs {word}, s3_3_c2_c4_1",
+                "cset {success}, ne",
+                word = out(reg) word,
+                success = out(reg) success,
+                options(nomem, nostack),
+            );
+        }
+        (success != 0).then_some(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repetition_test() {
+        let mut test = RepetitionTest::new();
+        assert_eq!(test.check(1), Ok(1));
+        assert_eq!(test.check(2), Ok(2));
+        assert_eq!(test.check(1), Ok(1));
+
+        assert_eq!(test.check(1), Err(EntropyError::HealthTestFailed));
+        // The test stays failed.
+        assert_eq!(test.check(3), Err(EntropyError::HealthTestFailed));
+    }
+
+    #[test]
+    fn test_stuck_generator() {
+        let mut test = RepetitionTest::new();
+        let results: [_; 4] = core::array::from_fn(|_| test.check(u64::MAX));
+        assert_eq!(
+            results,
+            [
+                Ok(u64::MAX),
+                Err(EntropyError::HealthTestFailed),
+                Err(EntropyError::HealthTestFailed),
+                Err(EntropyError::HealthTestFailed),
+            ]
+        );
+    }
+}
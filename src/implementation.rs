@@ -1,7 +1,17 @@
-use crate::{seeds, Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64, Jump, Random};
+use crate::exhaustion::{CounterExhausted, ExhaustionPolicy};
+use crate::kdf::FROM_U64_CONTEXT;
+use crate::state::{RoundsMismatch, State};
+use crate::{
+    seeds, Aes128Ctr128R, Aes128Ctr64R, Aes256Ctr128R, Aes256Ctr64R, Checkpoint, Jump, Random,
+    Seek, Stream,
+};
 
+#[cfg(feature = "getrandom")]
+use crate::cipher::wipe;
 #[cfg(feature = "getrandom")]
 use crate::secure_bytes;
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
 
 macro_rules! safely_call {
     ($what:expr) => {
@@ -47,31 +57,192 @@ macro_rules! safely_call {
     };
 }
 
-impl core::fmt::Debug for Aes128Ctr64 {
+pub(crate) use safely_call;
+
+/// Implements the narrow methods of the [`Random`] trait, which use the unused bits of the
+/// last generated block first.
+macro_rules! leftover_methods {
+    () => {
+        #[inline(always)]
+        fn u8(&self) -> u8 {
+            self.leftover_impl().take::<8>(|| Random::next(self)) as u8
+        }
+
+        #[inline(always)]
+        fn u16(&self) -> u16 {
+            self.leftover_impl().take::<16>(|| Random::next(self)) as u16
+        }
+
+        #[inline(always)]
+        fn u32(&self) -> u32 {
+            self.leftover_impl().take::<32>(|| Random::next(self)) as u32
+        }
+
+        #[inline(always)]
+        fn u64(&self) -> u64 {
+            self.leftover_impl().take::<64>(|| Random::next(self)) as u64
+        }
+
+        #[inline(always)]
+        fn bool(&self) -> bool {
+            self.leftover_impl().take::<1>(|| Random::next(self)) & 1 == 0
+        }
+    };
+}
+
+pub(crate) use leftover_methods;
+
+/// Implements the methods of the [`Random`] trait, which depend on the position of the counter.
+macro_rules! counter_methods {
+    ($prng:ident, $counter:ty) => {
+        #[inline(always)]
+        fn next(&self) -> u128 {
+            if self.counter_impl() == <$counter>::MAX {
+                return self.next_at_end().unwrap_or_else(|error| panic!("{error}"));
+            }
+            safely_call! { $prng::next_impl(self) }
+        }
+
+        #[inline(always)]
+        fn try_next(&self) -> Result<u128, CounterExhausted> {
+            if self.counter_impl() == <$counter>::MAX {
+                return self.next_at_end();
+            }
+            Ok({
+                safely_call! { $prng::next_impl(self) }
+            })
+        }
+
+        fn next_blocks<const N: usize>(&self) -> [u128; N] {
+            // The bulk path must not cross the last counter value.
+            if self.counter_impl().checked_add(N as $counter).is_none() {
+                return core::array::from_fn(|_| Random::next(self));
+            }
+            safely_call! { $prng::next_blocks_impl(self) }
+        }
+    };
+}
+
+/// Implements the [`Seek`] trait.
+macro_rules! impl_seek {
+    ($prng:ident, $counter:ty) => {
+        impl<const R: usize> Seek for $prng<R> {
+            fn set_counter(&self, counter: Self::Counter) {
+                self.set_counter_impl(counter);
+                self.leftover_impl().clear();
+                self.exhaustion_impl().clear();
+            }
+
+            fn advance(&self, n: Self::Counter) {
+                self.set_counter(self.counter_impl().wrapping_add(n))
+            }
+
+            fn rewind(&self, n: Self::Counter) {
+                self.set_counter(self.counter_impl().wrapping_sub(n))
+            }
+
+            fn remaining(&self) -> u128 {
+                if self.exhaustion_impl().is_exhausted() {
+                    return 0;
+                }
+                ((<$counter>::MAX - self.counter_impl()) as u128).saturating_add(1)
+            }
+
+            fn exhaustion_policy(&self) -> ExhaustionPolicy {
+                self.exhaustion_impl().policy()
+            }
+
+            fn set_exhaustion_policy(&self, policy: ExhaustionPolicy) {
+                self.exhaustion_impl().set_policy(policy)
+            }
+        }
+    };
+}
+
+impl_seek!(Aes128Ctr64R, u64);
+impl_seek!(Aes128Ctr128R, u128);
+impl_seek!(Aes256Ctr64R, u64);
+impl_seek!(Aes256Ctr128R, u128);
+
+/// Implements the slow path of the [`Random`] trait that generates the block of the last counter
+/// value and then applies the [`ExhaustionPolicy`].
+macro_rules! impl_next_at_end {
+    ($prng:ident, $counter:ty, $roll_over:expr) => {
+        impl<const R: usize> $prng<R> {
+            #[cold]
+            #[inline(never)]
+            fn next_at_end(&self) -> Result<u128, CounterExhausted> {
+                let exhaustion = self.exhaustion_impl();
+                match exhaustion.policy() {
+                    ExhaustionPolicy::Panic if exhaustion.is_exhausted() => Err(CounterExhausted),
+                    ExhaustionPolicy::Panic => {
+                        let block = {
+                            safely_call! { $prng::next_impl(self) }
+                        };
+                        // The counter keeps its last value, so that the stream can't repeat.
+                        self.set_counter_impl(<$counter>::MAX);
+                        exhaustion.set_exhausted();
+                        Ok(block)
+                    }
+                    ExhaustionPolicy::Wrap => Ok({
+                        safely_call! { $prng::next_impl(self) }
+                    }),
+                    ExhaustionPolicy::RollOver => {
+                        let block = {
+                            safely_call! { $prng::next_impl(self) }
+                        };
+                        let roll_over: fn(&Self) = $roll_over;
+                        roll_over(self);
+                        Ok(block)
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_next_at_end!(Aes128Ctr64R, u64, |this| {
+    this.set_stream_impl(this.stream_impl().wrapping_add(1))
+});
+impl_next_at_end!(Aes128Ctr128R, u128, |_| {});
+impl_next_at_end!(Aes256Ctr64R, u64, |this| {
+    this.set_stream_impl(this.stream_impl().wrapping_add(1))
+});
+impl_next_at_end!(Aes256Ctr128R, u128, |_| {});
+
+impl<const R: usize> core::fmt::Debug for Aes128Ctr64R<R> {
     fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
-        fmt.debug_struct("Aes128Ctr64").finish_non_exhaustive()
+        fmt.debug_struct("Aes128Ctr64")
+            .field("rounds", &R)
+            .finish_non_exhaustive()
     }
 }
 
-impl core::fmt::Debug for Aes128Ctr128 {
+impl<const R: usize> core::fmt::Debug for Aes128Ctr128R<R> {
     fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
-        fmt.debug_struct("Aes128Ctr128").finish_non_exhaustive()
+        fmt.debug_struct("Aes128Ctr128")
+            .field("rounds", &R)
+            .finish_non_exhaustive()
     }
 }
 
-impl core::fmt::Debug for Aes256Ctr64 {
+impl<const R: usize> core::fmt::Debug for Aes256Ctr64R<R> {
     fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
-        fmt.debug_struct("Aes256Ctr64").finish_non_exhaustive()
+        fmt.debug_struct("Aes256Ctr64")
+            .field("rounds", &R)
+            .finish_non_exhaustive()
     }
 }
 
-impl core::fmt::Debug for Aes256Ctr128 {
+impl<const R: usize> core::fmt::Debug for Aes256Ctr128R<R> {
     fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
-        fmt.debug_struct("Aes256Ctr128").finish_non_exhaustive()
+        fmt.debug_struct("Aes256Ctr128")
+            .field("rounds", &R)
+            .finish_non_exhaustive()
     }
 }
 
-impl Random for Aes128Ctr64 {
+impl<const R: usize> Random for Aes128Ctr64R<R> {
     type Seed = seeds::Aes128Ctr64Seed;
     type Counter = u64;
 
@@ -84,7 +255,18 @@ impl Random for Aes128Ctr64 {
         nonce_bytes.copy_from_slice(&seed.as_ref()[16..24]);
         counter_bytes.copy_from_slice(&seed.as_ref()[24..32]);
 
-        safely_call! { Aes128Ctr64::from_seed_impl(seed_bytes, nonce_bytes, counter_bytes) }
+        let rng = {
+            safely_call! { Aes128Ctr64R::from_seed_impl(seed_bytes, nonce_bytes, counter_bytes) }
+        };
+
+        #[cfg(feature = "zeroize")]
+        {
+            seed_bytes.zeroize();
+            nonce_bytes.zeroize();
+            counter_bytes.zeroize();
+        }
+
+        rng
     }
 
     fn seed(&self, seed: Self::Seed) {
@@ -97,17 +279,41 @@ impl Random for Aes128Ctr64 {
         counter_bytes.copy_from_slice(&seed.as_ref()[24..32]);
 
         safely_call! { self.seed_impl(seed_bytes, nonce_bytes, counter_bytes) }
+
+        #[cfg(feature = "zeroize")]
+        {
+            seed_bytes.zeroize();
+            nonce_bytes.zeroize();
+            counter_bytes.zeroize();
+        }
     }
 
     #[cfg(feature = "getrandom")]
     fn from_entropy() -> Self {
-        let bytes: [u8; 32] = secure_bytes();
-        Random::from_seed(bytes.into())
+        let mut bytes: [u8; 32] = secure_bytes();
+        let rng = Random::from_seed(bytes.into());
+        wipe(&mut bytes);
+        rng
     }
 
     #[cfg(feature = "getrandom")]
     fn seed_from_entropy(&self) {
-        safely_call! { self.seed_impl(secure_bytes(), secure_bytes(), secure_bytes()) }
+        let mut seed_bytes: [u8; 16] = secure_bytes();
+        let mut nonce_bytes: [u8; 8] = secure_bytes();
+        let mut counter_bytes: [u8; 8] = secure_bytes();
+
+        safely_call! { self.seed_impl(seed_bytes, nonce_bytes, counter_bytes) }
+
+        wipe(&mut seed_bytes);
+        wipe(&mut nonce_bytes);
+        wipe(&mut counter_bytes);
+    }
+
+    fn from_u64(state: u64) -> Self {
+        Random::from_seed(seeds::Aes128Ctr64Seed::derive(
+            FROM_U64_CONTEXT,
+            &state.to_le_bytes(),
+        ))
     }
 
     fn is_hardware_accelerated(&self) -> bool {
@@ -118,13 +324,12 @@ impl Random for Aes128Ctr64 {
         self.counter_impl()
     }
 
-    #[inline(always)]
-    fn next(&self) -> u128 {
-        safely_call! { Aes128Ctr64::next_impl(self) }
-    }
+    counter_methods!(Aes128Ctr64R, u64);
+
+    leftover_methods!();
 }
 
-impl Random for Aes128Ctr128 {
+impl<const R: usize> Random for Aes128Ctr128R<R> {
     type Seed = seeds::Aes128Ctr128Seed;
     type Counter = u128;
 
@@ -135,7 +340,17 @@ impl Random for Aes128Ctr128 {
         seed_bytes.copy_from_slice(&seed.as_ref()[..16]);
         counter_bytes.copy_from_slice(&seed.as_ref()[16..32]);
 
-        safely_call! { Aes128Ctr128::from_seed_impl(seed_bytes, counter_bytes) }
+        let rng = {
+            safely_call! { Aes128Ctr128R::from_seed_impl(seed_bytes, counter_bytes) }
+        };
+
+        #[cfg(feature = "zeroize")]
+        {
+            seed_bytes.zeroize();
+            counter_bytes.zeroize();
+        }
+
+        rng
     }
 
     fn seed(&self, seed: Self::Seed) {
@@ -146,17 +361,38 @@ impl Random for Aes128Ctr128 {
         counter_bytes.copy_from_slice(&seed.as_ref()[16..32]);
 
         safely_call! { self.seed_impl(seed_bytes, counter_bytes) }
+
+        #[cfg(feature = "zeroize")]
+        {
+            seed_bytes.zeroize();
+            counter_bytes.zeroize();
+        }
     }
 
     #[cfg(feature = "getrandom")]
     fn from_entropy() -> Self {
-        let bytes: [u8; 32] = secure_bytes();
-        Random::from_seed(bytes.into())
+        let mut bytes: [u8; 32] = secure_bytes();
+        let rng = Random::from_seed(bytes.into());
+        wipe(&mut bytes);
+        rng
     }
 
     #[cfg(feature = "getrandom")]
     fn seed_from_entropy(&self) {
-        safely_call! { self.seed_impl(secure_bytes(), secure_bytes()) }
+        let mut seed_bytes: [u8; 16] = secure_bytes();
+        let mut counter_bytes: [u8; 16] = secure_bytes();
+
+        safely_call! { self.seed_impl(seed_bytes, counter_bytes) }
+
+        wipe(&mut seed_bytes);
+        wipe(&mut counter_bytes);
+    }
+
+    fn from_u64(state: u64) -> Self {
+        Random::from_seed(seeds::Aes128Ctr128Seed::derive(
+            FROM_U64_CONTEXT,
+            &state.to_le_bytes(),
+        ))
     }
 
     fn is_hardware_accelerated(&self) -> bool {
@@ -167,13 +403,12 @@ impl Random for Aes128Ctr128 {
         self.counter_impl()
     }
 
-    #[inline(always)]
-    fn next(&self) -> u128 {
-        safely_call! { Aes128Ctr128::next_impl(self) }
-    }
+    counter_methods!(Aes128Ctr128R, u128);
+
+    leftover_methods!();
 }
 
-impl Random for Aes256Ctr64 {
+impl<const R: usize> Random for Aes256Ctr64R<R> {
     type Seed = seeds::Aes256Ctr64Seed;
     type Counter = u64;
 
@@ -186,7 +421,18 @@ impl Random for Aes256Ctr64 {
         nonce_bytes.copy_from_slice(&seed.as_ref()[32..40]);
         counter_bytes.copy_from_slice(&seed.as_ref()[40..48]);
 
-        safely_call! { Aes256Ctr64::from_seed_impl(seed_bytes, nonce_bytes, counter_bytes) }
+        let rng = {
+            safely_call! { Aes256Ctr64R::from_seed_impl(seed_bytes, nonce_bytes, counter_bytes) }
+        };
+
+        #[cfg(feature = "zeroize")]
+        {
+            seed_bytes.zeroize();
+            nonce_bytes.zeroize();
+            counter_bytes.zeroize();
+        }
+
+        rng
     }
 
     fn seed(&self, seed: Self::Seed) {
@@ -199,17 +445,41 @@ impl Random for Aes256Ctr64 {
         counter_bytes.copy_from_slice(&seed.as_ref()[40..48]);
 
         safely_call! { self.seed_impl(seed_bytes, nonce_bytes, counter_bytes) }
+
+        #[cfg(feature = "zeroize")]
+        {
+            seed_bytes.zeroize();
+            nonce_bytes.zeroize();
+            counter_bytes.zeroize();
+        }
     }
 
     #[cfg(feature = "getrandom")]
     fn from_entropy() -> Self {
-        let bytes: [u8; 48] = secure_bytes();
-        Random::from_seed(bytes.into())
+        let mut bytes: [u8; 48] = secure_bytes();
+        let rng = Random::from_seed(bytes.into());
+        wipe(&mut bytes);
+        rng
     }
 
     #[cfg(feature = "getrandom")]
     fn seed_from_entropy(&self) {
-        safely_call! { self.seed_impl(secure_bytes(), secure_bytes(), secure_bytes()) }
+        let mut seed_bytes: [u8; 32] = secure_bytes();
+        let mut nonce_bytes: [u8; 8] = secure_bytes();
+        let mut counter_bytes: [u8; 8] = secure_bytes();
+
+        safely_call! { self.seed_impl(seed_bytes, nonce_bytes, counter_bytes) }
+
+        wipe(&mut seed_bytes);
+        wipe(&mut nonce_bytes);
+        wipe(&mut counter_bytes);
+    }
+
+    fn from_u64(state: u64) -> Self {
+        Random::from_seed(seeds::Aes256Ctr64Seed::derive(
+            FROM_U64_CONTEXT,
+            &state.to_le_bytes(),
+        ))
     }
 
     fn is_hardware_accelerated(&self) -> bool {
@@ -220,13 +490,12 @@ impl Random for Aes256Ctr64 {
         self.counter_impl()
     }
 
-    #[inline(always)]
-    fn next(&self) -> u128 {
-        safely_call! { Aes256Ctr64::next_impl(self) }
-    }
+    counter_methods!(Aes256Ctr64R, u64);
+
+    leftover_methods!();
 }
 
-impl Random for Aes256Ctr128 {
+impl<const R: usize> Random for Aes256Ctr128R<R> {
     type Seed = seeds::Aes256Ctr128Seed;
     type Counter = u128;
 
@@ -237,7 +506,17 @@ impl Random for Aes256Ctr128 {
         seed_bytes.copy_from_slice(&seed.as_ref()[..32]);
         counter_bytes.copy_from_slice(&seed.as_ref()[32..48]);
 
-        safely_call! { Aes256Ctr128::from_seed_impl(seed_bytes, counter_bytes) }
+        let rng = {
+            safely_call! { Aes256Ctr128R::from_seed_impl(seed_bytes, counter_bytes) }
+        };
+
+        #[cfg(feature = "zeroize")]
+        {
+            seed_bytes.zeroize();
+            counter_bytes.zeroize();
+        }
+
+        rng
     }
 
     fn seed(&self, seed: Self::Seed) {
@@ -248,17 +527,38 @@ impl Random for Aes256Ctr128 {
         counter_bytes.copy_from_slice(&seed.as_ref()[32..48]);
 
         safely_call! { self.seed_impl(seed_bytes, counter_bytes) }
+
+        #[cfg(feature = "zeroize")]
+        {
+            seed_bytes.zeroize();
+            counter_bytes.zeroize();
+        }
     }
 
     #[cfg(feature = "getrandom")]
     fn from_entropy() -> Self {
-        let bytes: [u8; 48] = secure_bytes();
-        Random::from_seed(bytes.into())
+        let mut bytes: [u8; 48] = secure_bytes();
+        let rng = Random::from_seed(bytes.into());
+        wipe(&mut bytes);
+        rng
     }
 
     #[cfg(feature = "getrandom")]
     fn seed_from_entropy(&self) {
-        safely_call! { self.seed_impl(secure_bytes(), secure_bytes()) }
+        let mut seed_bytes: [u8; 32] = secure_bytes();
+        let mut counter_bytes: [u8; 16] = secure_bytes();
+
+        safely_call! { self.seed_impl(seed_bytes, counter_bytes) }
+
+        wipe(&mut seed_bytes);
+        wipe(&mut counter_bytes);
+    }
+
+    fn from_u64(state: u64) -> Self {
+        Random::from_seed(seeds::Aes256Ctr128Seed::derive(
+            FROM_U64_CONTEXT,
+            &state.to_le_bytes(),
+        ))
     }
 
     fn is_hardware_accelerated(&self) -> bool {
@@ -269,13 +569,76 @@ impl Random for Aes256Ctr128 {
         self.counter_impl()
     }
 
-    #[inline(always)]
-    fn next(&self) -> u128 {
-        safely_call! { Aes256Ctr128::next_impl(self) }
+    counter_methods!(Aes256Ctr128R, u128);
+
+    leftover_methods!();
+}
+
+impl<const R: usize> Checkpoint for Aes128Ctr64R<R> {
+    fn snapshot(&self) -> State<Self::Seed> {
+        let seed = seeds::Aes128Ctr64Seed::new(
+            self.key_impl(),
+            self.stream_impl().to_le_bytes(),
+            self.counter_impl(),
+        );
+        State::new(seed, R, self.leftover_impl(), self.exhaustion_impl())
+    }
+
+    fn restore(&self, state: &State<Self::Seed>) -> Result<(), RoundsMismatch> {
+        state.check_rounds(R)?;
+        Random::seed(self, state.seed().clone());
+        state.apply(self.leftover_impl(), self.exhaustion_impl());
+        Ok(())
     }
 }
 
-impl Jump for Aes128Ctr128 {
+impl<const R: usize> Checkpoint for Aes128Ctr128R<R> {
+    fn snapshot(&self) -> State<Self::Seed> {
+        let seed = seeds::Aes128Ctr128Seed::new(self.key_impl(), self.counter_impl());
+        State::new(seed, R, self.leftover_impl(), self.exhaustion_impl())
+    }
+
+    fn restore(&self, state: &State<Self::Seed>) -> Result<(), RoundsMismatch> {
+        state.check_rounds(R)?;
+        Random::seed(self, state.seed().clone());
+        state.apply(self.leftover_impl(), self.exhaustion_impl());
+        Ok(())
+    }
+}
+
+impl<const R: usize> Checkpoint for Aes256Ctr64R<R> {
+    fn snapshot(&self) -> State<Self::Seed> {
+        let seed = seeds::Aes256Ctr64Seed::new(
+            self.key_impl(),
+            self.stream_impl().to_le_bytes(),
+            self.counter_impl(),
+        );
+        State::new(seed, R, self.leftover_impl(), self.exhaustion_impl())
+    }
+
+    fn restore(&self, state: &State<Self::Seed>) -> Result<(), RoundsMismatch> {
+        state.check_rounds(R)?;
+        Random::seed(self, state.seed().clone());
+        state.apply(self.leftover_impl(), self.exhaustion_impl());
+        Ok(())
+    }
+}
+
+impl<const R: usize> Checkpoint for Aes256Ctr128R<R> {
+    fn snapshot(&self) -> State<Self::Seed> {
+        let seed = seeds::Aes256Ctr128Seed::new(self.key_impl(), self.counter_impl());
+        State::new(seed, R, self.leftover_impl(), self.exhaustion_impl())
+    }
+
+    fn restore(&self, state: &State<Self::Seed>) -> Result<(), RoundsMismatch> {
+        state.check_rounds(R)?;
+        Random::seed(self, state.seed().clone());
+        state.apply(self.leftover_impl(), self.exhaustion_impl());
+        Ok(())
+    }
+}
+
+impl<const R: usize> Jump for Aes128Ctr128R<R> {
     fn jump(&self) -> Self {
         self.jump_impl()
     }
@@ -285,7 +648,7 @@ impl Jump for Aes128Ctr128 {
     }
 }
 
-impl Jump for Aes256Ctr128 {
+impl<const R: usize> Jump for Aes256Ctr128R<R> {
     fn jump(&self) -> Self {
         self.jump_impl()
     }
@@ -295,17 +658,61 @@ impl Jump for Aes256Ctr128 {
     }
 }
 
+impl<const R: usize> Stream for Aes128Ctr64R<R> {
+    fn stream(&self) -> u64 {
+        self.stream_impl()
+    }
+
+    fn with_stream(&self, stream: u64) -> Self {
+        let clone = self.clone();
+        clone.set_stream_impl(stream);
+        clone.leftover_impl().clear();
+        clone.exhaustion_impl().clear();
+        clone
+    }
+
+    fn next_stream(&self) -> Self {
+        let clone = self.clone();
+        self.set_stream_impl(self.stream_impl().wrapping_add(1));
+        self.leftover_impl().clear();
+        self.exhaustion_impl().clear();
+        clone
+    }
+}
+
+impl<const R: usize> Stream for Aes256Ctr64R<R> {
+    fn stream(&self) -> u64 {
+        self.stream_impl()
+    }
+
+    fn with_stream(&self, stream: u64) -> Self {
+        let clone = self.clone();
+        clone.set_stream_impl(stream);
+        clone.leftover_impl().clear();
+        clone.exhaustion_impl().clear();
+        clone
+    }
+
+    fn next_stream(&self) -> Self {
+        let clone = self.clone();
+        self.set_stream_impl(self.stream_impl().wrapping_add(1));
+        self.leftover_impl().clear();
+        self.exhaustion_impl().clear();
+        clone
+    }
+}
+
 #[cfg(feature = "rand_core")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
-impl rand_core::RngCore for Aes128Ctr64 {
+impl<const R: usize> rand_core::RngCore for Aes128Ctr64R<R> {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
-        safely_call! { self.next_impl() as u32 }
+        Random::u32(self)
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
-        safely_call! { self.next_impl() as u64 }
+        Random::u64(self)
     }
 
     #[inline(always)]
@@ -322,15 +729,15 @@ impl rand_core::RngCore for Aes128Ctr64 {
 
 #[cfg(feature = "rand_core")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
-impl rand_core::RngCore for Aes128Ctr128 {
+impl<const R: usize> rand_core::RngCore for Aes128Ctr128R<R> {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
-        safely_call! { self.next_impl() as u32 }
+        Random::u32(self)
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
-        safely_call! { self.next_impl() as u64 }
+        Random::u64(self)
     }
 
     #[inline(always)]
@@ -347,15 +754,15 @@ impl rand_core::RngCore for Aes128Ctr128 {
 
 #[cfg(feature = "rand_core")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
-impl rand_core::RngCore for Aes256Ctr64 {
+impl<const R: usize> rand_core::RngCore for Aes256Ctr64R<R> {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
-        safely_call! { self.next_impl() as u32 }
+        Random::u32(self)
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
-        safely_call! { self.next_impl() as u64 }
+        Random::u64(self)
     }
 
     #[inline(always)]
@@ -372,15 +779,15 @@ impl rand_core::RngCore for Aes256Ctr64 {
 
 #[cfg(feature = "rand_core")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
-impl rand_core::RngCore for Aes256Ctr128 {
+impl<const R: usize> rand_core::RngCore for Aes256Ctr128R<R> {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
-        safely_call! { self.next_impl() as u32 }
+        Random::u32(self)
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
-        safely_call! { self.next_impl() as u64 }
+        Random::u64(self)
     }
 
     #[inline(always)]
@@ -397,7 +804,7 @@ impl rand_core::RngCore for Aes256Ctr128 {
 
 #[cfg(feature = "rand_core")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
-impl rand_core::SeedableRng for Aes128Ctr64 {
+impl<const R: usize> rand_core::SeedableRng for Aes128Ctr64R<R> {
     type Seed = seeds::Aes128Ctr64Seed;
 
     fn from_seed(seed: Self::Seed) -> Self {
@@ -407,7 +814,7 @@ impl rand_core::SeedableRng for Aes128Ctr64 {
 
 #[cfg(feature = "rand_core")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
-impl rand_core::SeedableRng for Aes128Ctr128 {
+impl<const R: usize> rand_core::SeedableRng for Aes128Ctr128R<R> {
     type Seed = seeds::Aes128Ctr128Seed;
 
     fn from_seed(seed: Self::Seed) -> Self {
@@ -417,7 +824,7 @@ impl rand_core::SeedableRng for Aes128Ctr128 {
 
 #[cfg(feature = "rand_core")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
-impl rand_core::SeedableRng for Aes256Ctr64 {
+impl<const R: usize> rand_core::SeedableRng for Aes256Ctr64R<R> {
     type Seed = seeds::Aes256Ctr64Seed;
 
     fn from_seed(seed: Self::Seed) -> Self {
@@ -427,10 +834,100 @@ impl rand_core::SeedableRng for Aes256Ctr64 {
 
 #[cfg(feature = "rand_core")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
-impl rand_core::SeedableRng for Aes256Ctr128 {
+impl<const R: usize> rand_core::SeedableRng for Aes256Ctr128R<R> {
     type Seed = seeds::Aes256Ctr128Seed;
 
     fn from_seed(seed: Self::Seed) -> Self {
         Random::from_seed(seed)
     }
 }
+
+/// Implements the traits of rand_core 0.9. `TryRngCore` is implemented by rand_core for every
+/// `RngCore` with an infallible error.
+#[cfg(feature = "rand_core_0_9")]
+macro_rules! impl_rand_core_0_9 {
+    ($name:ident, $seed:ident) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand_core_0_9")))]
+        impl<const R: usize> rand_core_0_9::RngCore for $name<R> {
+            #[inline(always)]
+            fn next_u32(&mut self) -> u32 {
+                Random::u32(self)
+            }
+
+            #[inline(always)]
+            fn next_u64(&mut self) -> u64 {
+                Random::u64(self)
+            }
+
+            #[inline(always)]
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                Random::fill_bytes(self, dest);
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand_core_0_9")))]
+        impl<const R: usize> rand_core_0_9::SeedableRng for $name<R> {
+            type Seed = seeds::$seed;
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                Random::from_seed(seed)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "rand_core_0_9")]
+impl_rand_core_0_9!(Aes128Ctr64R, Aes128Ctr64Seed);
+#[cfg(feature = "rand_core_0_9")]
+impl_rand_core_0_9!(Aes128Ctr128R, Aes128Ctr128Seed);
+#[cfg(feature = "rand_core_0_9")]
+impl_rand_core_0_9!(Aes256Ctr64R, Aes256Ctr64Seed);
+#[cfg(feature = "rand_core_0_9")]
+impl_rand_core_0_9!(Aes256Ctr128R, Aes256Ctr128Seed);
+
+/// The number of blocks that `BlockRngCore::generate()` creates at once, using the bulk path of
+/// [`Random::next_blocks()`].
+#[cfg(any(feature = "rand_core", feature = "rand_core_0_9"))]
+const RESULTS_BLOCKS: usize = 8;
+
+/// Implements `BlockRngCore` of the given rand_core version, which buffers multiple blocks as
+/// 32-bit words for `BlockRng`. The words of a block are ordered from its least to its most
+/// significant bits, so a `BlockRng` returns the same bytes as [`Random::fill_bytes()`].
+#[cfg(any(feature = "rand_core", feature = "rand_core_0_9"))]
+macro_rules! impl_block_rng_core {
+    ($rand_core:ident, $feature:literal, $name:ident) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = $feature)))]
+        impl<const R: usize> $rand_core::block::BlockRngCore for $name<R> {
+            type Item = u32;
+            type Results = [u32; RESULTS_BLOCKS * 4];
+
+            #[inline]
+            fn generate(&mut self, results: &mut Self::Results) {
+                let blocks: [u128; RESULTS_BLOCKS] = self.next_blocks();
+                for (words, block) in results.chunks_exact_mut(4).zip(blocks) {
+                    for (i, word) in words.iter_mut().enumerate() {
+                        *word = (block >> (32 * i)) as u32;
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "rand_core")]
+impl_block_rng_core!(rand_core, "rand_core", Aes128Ctr64R);
+#[cfg(feature = "rand_core")]
+impl_block_rng_core!(rand_core, "rand_core", Aes128Ctr128R);
+#[cfg(feature = "rand_core")]
+impl_block_rng_core!(rand_core, "rand_core", Aes256Ctr64R);
+#[cfg(feature = "rand_core")]
+impl_block_rng_core!(rand_core, "rand_core", Aes256Ctr128R);
+
+#[cfg(feature = "rand_core_0_9")]
+impl_block_rng_core!(rand_core_0_9, "rand_core_0_9", Aes128Ctr64R);
+#[cfg(feature = "rand_core_0_9")]
+impl_block_rng_core!(rand_core_0_9, "rand_core_0_9", Aes128Ctr128R);
+#[cfg(feature = "rand_core_0_9")]
+impl_block_rng_core!(rand_core_0_9, "rand_core_0_9", Aes256Ctr64R);
+#[cfg(feature = "rand_core_0_9")]
+impl_block_rng_core!(rand_core_0_9, "rand_core_0_9", Aes256Ctr128R);
@@ -0,0 +1,160 @@
+//! Provides an adapter that implements `std::io::Read` for the random number generators.
+
+use std::io::Read;
+
+use crate::Random;
+
+/// The number of blocks that the reader generates at once, using the bulk path of
+/// [`Random::next_blocks()`].
+const BULK_BLOCKS: usize = 8;
+
+const BUFFER_SIZE: usize = BULK_BLOCKS * 16;
+
+/// Fills the bytes, whose length must be a multiple of [`BUFFER_SIZE`], with the next blocks of
+/// the generator in little endian byte order.
+pub(crate) fn fill_blocks(rng: &impl Random, bytes: &mut [u8]) {
+    for bulk_chunk in bytes.chunks_exact_mut(BUFFER_SIZE) {
+        let blocks: [u128; BULK_BLOCKS] = rng.next_blocks();
+        for (chunk, block) in bulk_chunk.chunks_exact_mut(16).zip(blocks) {
+            chunk.copy_from_slice(&block.to_le_bytes());
+        }
+    }
+}
+
+/// Buffers the bytes of the last bulk fill, that were not yet read.
+struct Buffer {
+    bytes: [u8; BUFFER_SIZE],
+    position: usize,
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        self.bytes = [0; BUFFER_SIZE];
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl Buffer {
+    fn read(&mut self, bytes: &mut [u8]) -> usize {
+        let available = &self.bytes[self.position..];
+        let length = available.len().min(bytes.len());
+        bytes[..length].copy_from_slice(&available[..length]);
+        self.position += length;
+        length
+    }
+}
+
+/// An adapter that implements [`Read`] for a random number generator, so that random bytes can
+/// be streamed into files, sockets or anything else that consumes a reader.
+///
+/// The reader generates its bytes in bulk fills of eight blocks. Reads of at least that size are
+/// filled directly, the rest of a bulk fill is buffered for the next read. No bytes are skipped,
+/// so the output doesn't depend on the size of the reads: it is the sequence of the blocks of
+/// [`Random::next()`] in little endian byte order.
+///
+/// Without a limit, the reader never reaches the end of its output, so
+/// [`Read::read_to_end()`] doesn't return. [`Reader::with_limit()`] ends the output after a
+/// number of bytes.
+///
+/// The TLS instance can be read with a [`TlsRng`](crate::tls::TlsRng).
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::*;
+/// use std::io::Read;
+///
+/// let reader = Reader::new(Aes128Ctr64::from_entropy()).with_limit(1000);
+/// let bytes = reader.bytes().collect::<Result<Vec<u8>, _>>().unwrap();
+/// assert_eq!(bytes.len(), 1000);
+/// ```
+pub struct Reader<R> {
+    rng: R,
+    buffer: Buffer,
+    limit: Option<u64>,
+}
+
+impl<R> Reader<R> {
+    /// Creates a reader for the given generator without a limit.
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            buffer: Buffer {
+                bytes: [0; BUFFER_SIZE],
+                position: BUFFER_SIZE,
+            },
+            limit: None,
+        }
+    }
+
+    /// Ends the output of the reader after the given number of bytes.
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns the number of bytes the reader can still return, or `None` if it has no limit.
+    pub fn limit(&self) -> Option<u64> {
+        self.limit
+    }
+
+    /// Returns a reference to the generator.
+    pub fn get_ref(&self) -> &R {
+        &self.rng
+    }
+
+    /// Returns the generator. The buffered bytes are lost.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    fn read_with(&mut self, bytes: &mut [u8], fill: impl Fn(&R, &mut [u8])) -> usize {
+        let length = match self.limit {
+            Some(limit) => bytes
+                .len()
+                .min(usize::try_from(limit).unwrap_or(usize::MAX)),
+            None => bytes.len(),
+        };
+        let bytes = &mut bytes[..length];
+
+        let buffered = self.buffer.read(bytes);
+        let remaining = &mut bytes[buffered..];
+
+        let bulk = remaining.len() - remaining.len() % BUFFER_SIZE;
+        let (bulk, tail) = remaining.split_at_mut(bulk);
+        fill(&self.rng, bulk);
+
+        if !tail.is_empty() {
+            fill(&self.rng, &mut self.buffer.bytes);
+            self.buffer.position = 0;
+            self.buffer.read(tail);
+        }
+
+        if let Some(limit) = self.limit.as_mut() {
+            *limit -= length as u64;
+        }
+
+        length
+    }
+}
+
+impl<R: Random> Read for Reader<R> {
+    fn read(&mut self, bytes: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.read_with(bytes, |rng, bytes| fill_blocks(rng, bytes)))
+    }
+}
+
+#[cfg(all(feature = "tls", not(feature = "verification")))]
+impl Read for Reader<crate::tls::TlsRng> {
+    fn read(&mut self, bytes: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.read_with(bytes, |_, bytes| crate::tls::fill_blocks(bytes)))
+    }
+}
+
+impl<R> core::fmt::Debug for Reader<R> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt.debug_struct("Reader")
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
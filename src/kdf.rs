@@ -0,0 +1,174 @@
+//! Provides AES-CMAC and the key derivation function used to derive seeds.
+
+use crate::cipher::{wipe, BlockCipher};
+use crate::constants::{AES128_KEY_SIZE, AES128_ROUNDS, AES_BLOCK_SIZE};
+use crate::Aes128Cipher;
+
+/// The context of the seed derivation used by [`Random::from_u64()`](crate::Random::from_u64).
+pub(crate) const FROM_U64_CONTEXT: &[u8] = b"rand_aes::Random::from_u64";
+
+/// The label of the seed derivation used by the default implementation of
+/// [`Random::from_u64()`](crate::Random::from_u64), which doesn't know the name of the seed type.
+pub(crate) const SEED_LABEL: &[u8] = b"Seed";
+
+/// The reduction polynomial of GF(2^128) used by CMAC to derive the subkeys.
+const R_128: u128 = 0x87;
+
+/// AES-CMAC as specified in NIST SP 800-38B and RFC 4493.
+pub(crate) struct Cmac<C: BlockCipher> {
+    cipher: C,
+    /// The chaining value of the CBC-MAC.
+    state: u128,
+    /// The last, not yet processed block. It's only processed once more data follows, since the
+    /// last block is masked with a subkey.
+    block: [u8; AES_BLOCK_SIZE],
+    length: usize,
+}
+
+impl<C: BlockCipher> Cmac<C> {
+    pub(crate) fn new(key: &[u8]) -> Self {
+        Self {
+            cipher: C::new(key),
+            state: 0,
+            block: [0u8; AES_BLOCK_SIZE],
+            length: 0,
+        }
+    }
+
+    pub(crate) fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.length == AES_BLOCK_SIZE {
+                self.state = self.encrypt(self.state ^ u128::from_le_bytes(self.block));
+                self.length = 0;
+            }
+
+            let count = data.len().min(AES_BLOCK_SIZE - self.length);
+            self.block[self.length..self.length + count].copy_from_slice(&data[..count]);
+            self.length += count;
+            data = &data[count..];
+        }
+    }
+
+    pub(crate) fn finalize(mut self) -> [u8; AES_BLOCK_SIZE] {
+        let k1 = double(self.encrypt(0).swap_bytes());
+
+        let subkey = if self.length == AES_BLOCK_SIZE {
+            k1
+        } else {
+            self.block[self.length] = 0x80;
+            self.block[self.length + 1..].fill(0);
+            double(k1)
+        };
+
+        let last = u128::from_le_bytes(self.block) ^ subkey.swap_bytes();
+        self.encrypt(self.state ^ last).to_le_bytes()
+    }
+
+    #[inline(always)]
+    fn encrypt(&self, block: u128) -> u128 {
+        let [block] = self.cipher.encrypt_blocks([block]);
+        block
+    }
+}
+
+impl<C: BlockCipher> Drop for Cmac<C> {
+    fn drop(&mut self) {
+        // SAFETY: The pointer is created from a valid mutable reference.
+        unsafe { core::ptr::write_volatile(&mut self.state, 0) };
+        wipe(&mut self.block);
+    }
+}
+
+/// Multiplies the big endian number with x in GF(2^128).
+fn double(value: u128) -> u128 {
+    let carry = value >> 127;
+    (value << 1) ^ (carry * R_128)
+}
+
+/// Derives `N` bytes from the given input with AES-128-CMAC.
+///
+/// The derivation uses the two-step key derivation of NIST SP 800-56C: The input is first
+/// extracted into a key with `K = AES-CMAC(0^128, input)`. The output is then expanded with the
+/// KDF in counter mode of NIST SP 800-108 as the concatenation of the blocks
+/// `AES-CMAC(K, [i]_32 || label || 0x00 || context || [8 * N]_32)` for `i = 1, 2, ...`, where
+/// the integers are encoded in big endian.
+///
+/// The construction is part of the stable API, so the output for a given input never changes.
+pub(crate) fn derive<const N: usize>(label: &[u8], context: &[u8], input: &[u8]) -> [u8; N] {
+    let mut output = [0u8; N];
+    derive_into(label, context, input, &mut output);
+    output
+}
+
+/// Derives the output from the given input with AES-128-CMAC, like [`derive()`].
+pub(crate) fn derive_into(label: &[u8], context: &[u8], input: &[u8], output: &mut [u8]) {
+    let mut extract = Cmac::<Aes128Cipher<AES128_ROUNDS>>::new(&[0u8; AES128_KEY_SIZE]);
+    extract.update(input);
+    let mut key = extract.finalize();
+
+    expand::<Aes128Cipher<AES128_ROUNDS>>(&key, label, context, output);
+    wipe(&mut key);
+}
+
+/// Expands the key into the output with the KDF in counter mode of NIST SP 800-108, using
+/// AES-CMAC with the given cipher as the PRF.
+pub(crate) fn expand<C: BlockCipher>(key: &[u8], label: &[u8], context: &[u8], output: &mut [u8]) {
+    let length = (output.len() as u32 * 8).to_be_bytes();
+
+    for (i, chunk) in output.chunks_mut(AES_BLOCK_SIZE).enumerate() {
+        let mut expand = Cmac::<C>::new(key);
+        expand.update(&(i as u32 + 1).to_be_bytes());
+        expand.update(label);
+        expand.update(&[0x00]);
+        expand.update(context);
+        expand.update(&length);
+
+        let mut block = expand.finalize();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+        wipe(&mut block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    fn cmac(message: &[u8]) -> [u8; 16] {
+        let mut cmac =
+            Cmac::<Aes128Cipher<AES128_ROUNDS>>::new(&hex!("2b7e151628aed2a6abf7158809cf4f3c"));
+        cmac.update(message);
+        cmac.finalize()
+    }
+
+    const MESSAGE: [u8; 64] = hex!(
+        "6bc1bee22e409f96e93d7e117393172a ae2d8a571e03ac9c9eb76fac45af8e51"
+        "30c81c46a35ce411e5fbc1191a0a52ef f69f2445df4f9b17ad2b417be66c3710"
+    );
+
+    // Test vectors of RFC 4493, section 4.
+    #[test]
+    fn test_cmac_rfc4493() {
+        assert_eq!(cmac(&[]), hex!("bb1d6929e95937287fa37d129b756746"));
+        assert_eq!(
+            cmac(&MESSAGE[..16]),
+            hex!("070a16b46b4d4144f79bdd9dd04a287c")
+        );
+        assert_eq!(
+            cmac(&MESSAGE[..40]),
+            hex!("dfa66747de9ae63030ca32611497c827")
+        );
+        assert_eq!(cmac(&MESSAGE), hex!("51f0bebf7e3b9d92fc49741779363cfe"));
+    }
+
+    #[test]
+    fn test_cmac_split_updates() {
+        for split in 0..MESSAGE.len() {
+            let mut cmac =
+                Cmac::<Aes128Cipher<AES128_ROUNDS>>::new(&hex!("2b7e151628aed2a6abf7158809cf4f3c"));
+            cmac.update(&MESSAGE[..split]);
+            cmac.update(&MESSAGE[split..]);
+            assert_eq!(cmac.finalize(), hex!("51f0bebf7e3b9d92fc49741779363cfe"));
+        }
+    }
+}
@@ -9,7 +9,8 @@
 //!  4. [`Aes256Ctr128`]: Utilizes AES-256 encryption with a 128-bit counter.
 //!
 //! Common functionality is provided using the [`Random`] trait or the optionally provided
-//! [`rand_core::RngCore`] and [`rand_core::SeedableRng`] traits.
+//! [`rand_core::RngCore`] and [`rand_core::SeedableRng`] traits. The `rand_core_0_9` feature
+//! implements the same traits of rand_core 0.9, which can be used at the same time.
 //!
 //! ## Optimal Performance
 //!
@@ -39,6 +40,37 @@
 //! requires at least 5 rounds to be successfully cleared. AES-128 uses 10 rounds, whereas
 //! AES-256 uses 14 rounds.
 //!
+//! For key generation and other cryptographic use, the crate provides the CTR_DRBG of NIST
+//! SP 800-90A as [`Aes128CtrDrbg`] and [`Aes256CtrDrbg`]. It uses the same AES backends, but
+//! requires reseeding with fresh entropy and optionally provides prediction resistance.
+//!
+//! The PRNG keep the same key for their whole life, so anyone who captures their state can
+//! reconstruct all past output. [`Aes128FastKeyErasure`] and [`Aes256FastKeyErasure`] provide
+//! forward secrecy with Bernstein's fast-key-erasure construction instead, which replaces the key
+//! after every batch of output.
+//!
+//! [`Reseeding`] wraps a PRNG and reseeds it from an [`EntropySource`] after a configurable number
+//! of output bytes or a configurable duration. If the entropy source fails, it continues with the
+//! current state and retries the reseed once it's due again, instead of panicking.
+//!
+//! The generators can be seeded from any [`EntropySource`] with [`Random::from_entropy_source()`],
+//! for example from the hardware TRNG of a microcontroller in `no_std`, where the OS entropy
+//! source of the `getrandom` feature isn't available. [`HardwareEntropy`] uses the random number
+//! generator of the CPU (RDSEED and RDRAND on x86, RNDRRS and RNDR on aarch64) and can be combined
+//! with the OS entropy source by [`EntropySource::xor()`].
+//!
+//! A forked child process inherits the state of its parent and would repeat its output. With the
+//! `fork_detection` feature, `ForkSafe` and the TLS instance reseed themselves from the OS after
+//! a fork.
+//!
+//! ## Reduced Rounds
+//!
+//! Workloads that value throughput over a cryptographic margin can use the variants with a
+//! reduced number of rounds: [`Aes128Ctr64R`], [`Aes128Ctr128R`], [`Aes256Ctr64R`] and
+//! [`Aes256Ctr128R`]. The round count is a const generic parameter, for example
+//! `Aes128Ctr128R<5>`, and produces the same output on every backend. These variants are no
+//! longer AES and must not be used for anything security related.
+//!
 //! ## Parallel Stream Generation
 //!
 //! The 128-bit counter PRNG support efficient parallel stream generation through the [`Jump`] trait.
@@ -58,6 +90,35 @@
 //!
 //! These functions are particularly useful in scenarios requiring multiple independent PRNG streams,
 //! such as parallel Monte Carlo simulations or distributed computing tasks.
+//!
+//! ### Streams
+//!
+//! The 64-bit counter PRNG support parallel stream generation through the [`Stream`] trait. The
+//! [`Stream::with_stream()`] and [`Stream::next_stream()`] functions select the nonce of the
+//! counter block, which allows up to 2^64 non-overlapping streams with a period of 2^64 each, as
+//! long as no PRNG uses [`ExhaustionPolicy::RollOver`], which continues with the next stream.
+//!
+//! ### Deterministic Parallel Generation
+//!
+//! With the `rayon` feature, the [`Parallel`] trait fills slices and iterates over values of the
+//! 128-bit counter PRNG in parallel, by splitting the counter space by the index of the blocks.
+//! The output is identical to the sequential output of the same PRNG, independent of the number
+//! of threads rayon uses.
+//!
+//! ## Sharing Between Threads
+//!
+//! The generators keep their state in `Cell`s, so they are `!Sync`. The [`SharedAes128Ctr64`],
+//! [`SharedAes128Ctr128`], [`SharedAes256Ctr64`] and [`SharedAes256Ctr128`] generators are `Sync`
+//! variants, that reserve their counter values with an atomic compare-exchange and encrypt them
+//! locally. Any number of threads can so draw from the same stream without a lock. Targets without
+//! 64-bit atomics use a spin lock instead.
+//!
+//! ## Counter Exhaustion
+//!
+//! Once the counter of a PRNG reaches its last value, continuing would repeat the already
+//! generated stream. [`Seek::remaining()`] returns how many blocks can be generated until then
+//! and the [`ExhaustionPolicy`] defines what happens afterward. By default, the PRNG panics, while
+//! [`Random::try_next()`] returns an error instead.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(feature = "verification", allow(unused))]
@@ -71,6 +132,19 @@ pub mod tls;
 
 mod traits;
 
+mod encoding;
+
+mod entropy;
+
+mod hardware;
+
+mod exhaustion;
+
+mod state;
+
+#[cfg(feature = "serde")]
+mod serialization;
+
 mod backend;
 
 #[cfg(all(
@@ -113,7 +187,27 @@ pub(crate) mod runtime;
         feature = "force_runtime_detection",
     ),
 ))]
-pub use runtime::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64};
+pub(crate) use runtime::{Aes128Cipher, Aes256Cipher};
+#[cfg(all(
+    feature = "std",
+    not(target_arch = "riscv64"),
+    any(
+        not(any(
+            all(
+                any(target_arch = "x86_64", target_arch = "x86"),
+                target_feature = "sse2",
+                target_feature = "aes",
+            ),
+            all(
+                target_arch = "aarch64",
+                target_feature = "neon",
+                target_feature = "aes",
+            ),
+        )),
+        feature = "force_runtime_detection",
+    ),
+))]
+pub use runtime::{Aes128Ctr128R, Aes128Ctr64R, Aes256Ctr128R, Aes256Ctr64R};
 
 #[cfg(all(
     target_arch = "aarch64",
@@ -123,7 +217,16 @@ pub use runtime::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64};
     not(feature = "force_software"),
     not(feature = "verification"),
 ))]
-pub use backend::aarch64::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64};
+pub(crate) use backend::aarch64::{Aes128Cipher, Aes256Cipher};
+#[cfg(all(
+    target_arch = "aarch64",
+    target_feature = "neon",
+    target_feature = "aes",
+    not(feature = "force_runtime_detection"),
+    not(feature = "force_software"),
+    not(feature = "verification"),
+))]
+pub use backend::aarch64::{Aes128Ctr128R, Aes128Ctr64R, Aes256Ctr128R, Aes256Ctr64R};
 
 #[cfg(all(
     target_arch = "riscv64",
@@ -132,7 +235,15 @@ pub use backend::aarch64::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64}
     not(feature = "force_software"),
     not(feature = "verification"),
 ))]
-pub use backend::riscv64::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64};
+pub(crate) use backend::riscv64::{Aes128Cipher, Aes256Cipher};
+#[cfg(all(
+    target_arch = "riscv64",
+    feature = "experimental_riscv",
+    not(feature = "force_runtime_detection"),
+    not(feature = "force_software"),
+    not(feature = "verification"),
+))]
+pub use backend::riscv64::{Aes128Ctr128R, Aes128Ctr64R, Aes256Ctr128R, Aes256Ctr64R};
 
 #[cfg(all(
     any(target_arch = "x86_64", target_arch = "x86"),
@@ -142,7 +253,16 @@ pub use backend::riscv64::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64}
     not(feature = "force_software"),
     not(feature = "verification"),
 ))]
-pub use backend::x86::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64};
+pub(crate) use backend::x86::{Aes128Cipher, Aes256Cipher};
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "x86"),
+    target_feature = "sse2",
+    target_feature = "aes",
+    not(feature = "force_runtime_detection"),
+    not(feature = "force_software"),
+    not(feature = "verification"),
+))]
+pub use backend::x86::{Aes128Ctr128R, Aes128Ctr64R, Aes256Ctr128R, Aes256Ctr64R};
 
 #[cfg(all(
     any(
@@ -156,16 +276,141 @@ pub use backend::x86::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64};
     not(feature = "force_runtime_detection"),
     not(feature = "verification"),
 ))]
-pub use backend::soft::{Aes128Ctr128, Aes128Ctr64, Aes256Ctr128, Aes256Ctr64};
+pub(crate) use backend::soft::{Aes128Cipher, Aes256Cipher};
+#[cfg(all(
+    any(
+        not(any(
+            target_arch = "aarch64",
+            all(target_arch = "riscv64", feature = "experimental_riscv"),
+            any(target_arch = "x86_64", target_arch = "x86"),
+        )),
+        feature = "force_software",
+    ),
+    not(feature = "force_runtime_detection"),
+    not(feature = "verification"),
+))]
+pub use backend::soft::{Aes128Ctr128R, Aes128Ctr64R, Aes256Ctr128R, Aes256Ctr64R};
+
+/// A random number generator based on the AES-128 block cipher that runs in CTR mode and has a
+/// period of 64-bit.
+///
+/// The full 10 rounds of encryption are used. See [`Aes128Ctr64R`] for reduced rounds.
+#[cfg(not(feature = "verification"))]
+pub type Aes128Ctr64 = Aes128Ctr64R<10>;
+
+/// A random number generator based on the AES-128 block cipher that runs in CTR mode and has a
+/// period of 128-bit.
+///
+/// The full 10 rounds of encryption are used. See [`Aes128Ctr128R`] for reduced rounds.
+#[cfg(not(feature = "verification"))]
+pub type Aes128Ctr128 = Aes128Ctr128R<10>;
+
+/// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
+/// period of 64-bit.
+///
+/// The full 14 rounds of encryption are used. See [`Aes256Ctr64R`] for reduced rounds.
+#[cfg(not(feature = "verification"))]
+pub type Aes256Ctr64 = Aes256Ctr64R<14>;
+
+/// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
+/// period of 128-bit.
+///
+/// The full 14 rounds of encryption are used. See [`Aes256Ctr128R`] for reduced rounds.
+#[cfg(not(feature = "verification"))]
+pub type Aes256Ctr128 = Aes256Ctr128R<14>;
+
+/// A random number generator based on the AES-128 block cipher that runs in CTR mode, has a
+/// period of 64-bit and can be shared between threads.
+///
+/// The full 10 rounds of encryption are used. See [`SharedAes128Ctr64R`] for reduced rounds.
+#[cfg(all(not(feature = "verification"), target_has_atomic = "8"))]
+pub type SharedAes128Ctr64 = SharedAes128Ctr64R<10>;
+
+/// A random number generator based on the AES-128 block cipher that runs in CTR mode, has a
+/// period of 128-bit and can be shared between threads.
+///
+/// The full 10 rounds of encryption are used. See [`SharedAes128Ctr128R`] for reduced rounds.
+#[cfg(all(not(feature = "verification"), target_has_atomic = "8"))]
+pub type SharedAes128Ctr128 = SharedAes128Ctr128R<10>;
+
+/// A random number generator based on the AES-256 block cipher that runs in CTR mode, has a
+/// period of 64-bit and can be shared between threads.
+///
+/// The full 14 rounds of encryption are used. See [`SharedAes256Ctr64R`] for reduced rounds.
+#[cfg(all(not(feature = "verification"), target_has_atomic = "8"))]
+pub type SharedAes256Ctr64 = SharedAes256Ctr64R<14>;
+
+/// A random number generator based on the AES-256 block cipher that runs in CTR mode, has a
+/// period of 128-bit and can be shared between threads.
+///
+/// The full 14 rounds of encryption are used. See [`SharedAes256Ctr128R`] for reduced rounds.
+#[cfg(all(not(feature = "verification"), target_has_atomic = "8"))]
+pub type SharedAes256Ctr128 = SharedAes256Ctr128R<14>;
 
 #[cfg(not(feature = "verification"))]
 mod implementation;
 
+#[cfg(all(not(feature = "verification"), target_has_atomic = "8"))]
+mod shared;
+
+#[cfg(not(feature = "verification"))]
+mod cipher;
+
+#[cfg(not(feature = "verification"))]
+mod drbg;
+
+#[cfg(not(feature = "verification"))]
+mod kdf;
+
+#[cfg(not(feature = "verification"))]
+mod builder;
+
+#[cfg(not(feature = "verification"))]
+mod erasure;
+
+#[cfg(not(feature = "verification"))]
+mod reseeding;
+
+#[cfg(all(feature = "fork_detection", not(feature = "verification")))]
+mod fork;
+
+#[cfg(all(feature = "std", not(feature = "verification")))]
+mod io;
+
+#[cfg(all(feature = "rayon", not(feature = "verification")))]
+mod parallel;
+
+#[cfg(all(not(feature = "verification"), target_has_atomic = "8"))]
+pub use shared::{
+    SharedAes128Ctr128R, SharedAes128Ctr64R, SharedAes256Ctr128R, SharedAes256Ctr64R,
+};
+
 #[cfg(feature = "verification")]
 #[doc(hidden)]
 pub mod verification;
 
-pub use traits::{Jump, Random};
+#[cfg(not(feature = "verification"))]
+pub use drbg::{Aes128CtrDrbg, Aes256CtrDrbg, DrbgError};
+#[cfg(feature = "getrandom")]
+pub use entropy::OsEntropy;
+pub use entropy::{EntropyError, EntropySource, Or, Xor};
+#[cfg(not(feature = "verification"))]
+pub use erasure::{Aes128FastKeyErasure, Aes256FastKeyErasure};
+pub use exhaustion::{CounterExhausted, ExhaustionPolicy};
+#[cfg(all(feature = "fork_detection", not(feature = "verification")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "fork_detection")))]
+pub use fork::ForkSafe;
+pub use hardware::HardwareEntropy;
+#[cfg(all(feature = "std", not(feature = "verification")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use io::Reader;
+#[cfg(all(feature = "rayon", not(feature = "verification")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub use parallel::{Parallel, ParallelValue};
+#[cfg(not(feature = "verification"))]
+pub use reseeding::{ReseedError, Reseeding, DEFAULT_RESEED_THRESHOLD};
+pub use state::{RoundsMismatch, State};
+pub use traits::{Checkpoint, Jump, Random, Seek, Stream};
 
 #[allow(unused)]
 pub(crate) mod constants {
@@ -178,6 +423,8 @@ pub(crate) mod constants {
     pub(crate) const AES256_KEY_SIZE: usize = 32;
     pub(crate) const AES128_KEY_COUNT: usize = 11;
     pub(crate) const AES256_KEY_COUNT: usize = 15;
+    pub(crate) const AES128_ROUNDS: usize = AES128_KEY_COUNT - 1;
+    pub(crate) const AES256_ROUNDS: usize = AES256_KEY_COUNT - 1;
 }
 
 #[cfg(feature = "getrandom")]
@@ -215,6 +462,10 @@ mod tests {
     const TV_AES128_NEXT_0: [u8; AES_BLOCK_SIZE] = hex!("69c4e0d86a7b0430d8cdb78070b4c55a");
     const TV_AES128_NEXT_1: [u8; AES_BLOCK_SIZE] = hex!("a556156c72876577f67f95a9d9e640a7");
 
+    // Reduced to 5 rounds, computed with an independent reference implementation.
+    const TV_AES128_R5_NEXT_0: [u8; AES_BLOCK_SIZE] = hex!("0a993eb8502aa4cdcfdfa67a69b64f89");
+    const TV_AES128_R5_NEXT_1: [u8; AES_BLOCK_SIZE] = hex!("37a9f11df93546682c0b910aa1c7a1f2");
+
     // From NIST FIPS 197
     const TV_AES256_KEY: [u8; AES256_KEY_SIZE] =
         hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
@@ -239,6 +490,10 @@ mod tests {
     const TV_AES256_NEXT_0: [u8; AES_BLOCK_SIZE] = hex!("8ea2b7ca516745bfeafc49904b496089");
     const TV_AES256_NEXT_1: [u8; AES_BLOCK_SIZE] = hex!("81ae7d5e4138bf730d2a8871fec2cd0c");
 
+    // Reduced to 5 rounds, computed with an independent reference implementation.
+    const TV_AES256_R5_NEXT_0: [u8; AES_BLOCK_SIZE] = hex!("f111576826581631eca962d781d3b0f5");
+    const TV_AES256_R5_NEXT_1: [u8; AES_BLOCK_SIZE] = hex!("8308e533ff764384968656cbceb3b4af");
+
     pub(crate) fn aes128_key_expansion_test<F>(expansion: F)
     where
         F: FnOnce([u8; AES128_KEY_SIZE]) -> [[u8; AES_BLOCK_SIZE]; AES128_KEY_COUNT],
@@ -302,4 +557,70 @@ mod tests {
         assert_eq!(unsafe { prng.next_impl().to_le_bytes() }, TV_AES256_NEXT_0);
         assert_eq!(unsafe { prng.next_impl().to_le_bytes() }, TV_AES256_NEXT_1);
     }
+
+    #[test]
+    fn test_aes128_64_ctr_reduced_rounds() {
+        let mut ctr = [0u8; 8];
+        let mut nonce = [0u8; 8];
+        ctr.copy_from_slice(&TV_AES128_IV[0..8]);
+        nonce.copy_from_slice(&TV_AES128_IV[8..16]);
+
+        let prng = unsafe { Aes128Ctr64R::<5>::from_seed_impl(TV_AES128_KEY, nonce, ctr) };
+
+        assert_eq!(
+            unsafe { prng.next_impl().to_le_bytes() },
+            TV_AES128_R5_NEXT_0
+        );
+        assert_eq!(
+            unsafe { prng.next_impl().to_le_bytes() },
+            TV_AES128_R5_NEXT_1
+        );
+    }
+
+    #[test]
+    fn test_aes128_128_ctr_reduced_rounds() {
+        let prng = unsafe { Aes128Ctr128R::<5>::from_seed_impl(TV_AES128_KEY, TV_AES128_IV) };
+
+        assert_eq!(
+            unsafe { prng.next_impl().to_le_bytes() },
+            TV_AES128_R5_NEXT_0
+        );
+        assert_eq!(
+            unsafe { prng.next_impl().to_le_bytes() },
+            TV_AES128_R5_NEXT_1
+        );
+    }
+
+    #[test]
+    fn test_aes256_64_ctr_reduced_rounds() {
+        let mut ctr = [0u8; 8];
+        let mut nonce = [0u8; 8];
+        ctr.copy_from_slice(&TV_AES256_IV[0..8]);
+        nonce.copy_from_slice(&TV_AES256_IV[8..16]);
+
+        let prng = unsafe { Aes256Ctr64R::<5>::from_seed_impl(TV_AES256_KEY, nonce, ctr) };
+
+        assert_eq!(
+            unsafe { prng.next_impl().to_le_bytes() },
+            TV_AES256_R5_NEXT_0
+        );
+        assert_eq!(
+            unsafe { prng.next_impl().to_le_bytes() },
+            TV_AES256_R5_NEXT_1
+        );
+    }
+
+    #[test]
+    fn test_aes256_128_ctr_reduced_rounds() {
+        let prng = unsafe { Aes256Ctr128R::<5>::from_seed_impl(TV_AES256_KEY, TV_AES256_IV) };
+
+        assert_eq!(
+            unsafe { prng.next_impl().to_le_bytes() },
+            TV_AES256_R5_NEXT_0
+        );
+        assert_eq!(
+            unsafe { prng.next_impl().to_le_bytes() },
+            TV_AES256_R5_NEXT_1
+        );
+    }
 }
@@ -0,0 +1,203 @@
+//! Provides the deterministic parallel generation of the 128-bit counter PRNG with rayon.
+
+use rayon::iter::{
+    Either, IndexedParallelIterator, IntoParallelIterator, IntoParallelRefMutIterator,
+    ParallelIterator,
+};
+use rayon::slice::ParallelSliceMut;
+
+use crate::{Aes128Ctr128R, Aes256Ctr128R, Checkpoint, Random, Seek};
+
+/// The number of bytes that [`Parallel::par_fill_bytes()`] fills in one task.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A value that can be generated in parallel by [`Parallel::par_fill()`] and
+/// [`Parallel::par_iter()`].
+///
+/// A value takes the same bits of a block as the corresponding method of [`Random`], starting at
+/// the lowest bits. A block can so serve `128 / BITS` values.
+pub trait ParallelValue: Send + Sized {
+    /// The number of bits of a block that a value takes.
+    const BITS: u32;
+
+    /// Generates the next value of the generator, like the corresponding method of [`Random`].
+    fn random(rng: &impl Random) -> Self;
+
+    /// Returns the value at the given index of the block.
+    fn from_block(block: u128, index: usize) -> Self;
+}
+
+macro_rules! impl_parallel_value {
+    ($type:ty, $bits:expr, $method:ident, |$value:ident| $conversion:expr) => {
+        impl ParallelValue for $type {
+            const BITS: u32 = $bits;
+
+            #[inline(always)]
+            fn random(rng: &impl Random) -> Self {
+                rng.$method()
+            }
+
+            #[inline(always)]
+            fn from_block(block: u128, index: usize) -> Self {
+                let $value = block >> (index as u32 * <Self as ParallelValue>::BITS);
+                $conversion
+            }
+        }
+    };
+}
+
+impl_parallel_value!(u8, 8, u8, |value| value as u8);
+impl_parallel_value!(u16, 16, u16, |value| value as u16);
+impl_parallel_value!(u32, 32, u32, |value| value as u32);
+impl_parallel_value!(u64, 64, u64, |value| value as u64);
+impl_parallel_value!(u128, 128, u128, |value| value);
+impl_parallel_value!(usize, usize::BITS, usize, |value| value as usize);
+impl_parallel_value!(i8, 8, i8, |value| value as i8);
+impl_parallel_value!(i16, 16, i16, |value| value as i16);
+impl_parallel_value!(i32, 32, i32, |value| value as i32);
+impl_parallel_value!(i64, 64, i64, |value| value as i64);
+impl_parallel_value!(i128, 128, i128, |value| value as i128);
+impl_parallel_value!(isize, isize::BITS, isize, |value| value as isize);
+impl_parallel_value!(bool, 1, bool, |value| value & 1 == 0);
+impl_parallel_value!(f32, 32, f32, |value| ((value as u32 >> 8) as f32)
+    * 0.000000059604645);
+impl_parallel_value!(f64, 64, f64, |value| ((value as u64 >> 11) as f64)
+    * 0.00000000000000011102230246251565);
+
+/// Provides the deterministic parallel generation with rayon to the PRNG with a 128-bit counter.
+///
+/// The output of block `n` only depends on the key and the counter plus `n`, so the counter
+/// space is split by the index of the blocks and every task generates its blocks independently.
+/// The output is identical to the sequential generation from the same state, no matter how many
+/// threads rayon uses, and the generator is left in the same state as after the sequential
+/// generation.
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::*;
+/// use rayon::prelude::*;
+///
+/// let rng0 = Aes128Ctr128::from_seed(seeds::Aes128Ctr128Seed::default());
+/// let rng1 = Aes128Ctr128::from_seed(seeds::Aes128Ctr128Seed::default());
+///
+/// let mut values = vec![0.0f64; 100_000];
+/// rng0.par_fill(&mut values);
+///
+/// let collected: Vec<f64> = rng1.par_iter(100_000).collect();
+/// assert_eq!(collected, values);
+/// ```
+pub trait Parallel: Random {
+    /// Fills a mutable `[u8]` slice with random bytes in parallel. The bytes are identical to
+    /// the bytes of [`Random::fill_bytes()`].
+    fn par_fill_bytes(&self, slice: &mut [u8]);
+
+    /// Fills a mutable slice with random values in parallel. The values are identical to the
+    /// values of calling the corresponding method of [`Random`] for every element.
+    fn par_fill<T: ParallelValue>(&self, slice: &mut [T]);
+
+    /// Returns a parallel iterator over the given number of random values. The values are
+    /// identical to the values of calling the corresponding method of [`Random`] `len` times.
+    ///
+    /// The generator is advanced past the values immediately, the values are generated lazily by
+    /// the iterator.
+    fn par_iter<T: ParallelValue>(&self, len: usize) -> impl IndexedParallelIterator<Item = T>;
+}
+
+macro_rules! impl_parallel {
+    ($name:ident) => {
+        impl<const R: usize> Parallel for $name<R> {
+            fn par_fill_bytes(&self, slice: &mut [u8]) {
+                // `fill_bytes()` generates one more block than it fills completely.
+                let blocks = (slice.len() / 16) as u128 + 1;
+                if self.remaining() < blocks {
+                    // The exhaustion policy is applied by the sequential generation.
+                    return self.fill_bytes(slice);
+                }
+
+                let seed = self.snapshot().seed;
+                let counter = self.counter();
+                slice.par_chunks_mut(CHUNK_SIZE).enumerate().for_each_init(
+                    || <Self as Random>::from_seed(seed.clone()),
+                    |rng, (index, chunk)| {
+                        rng.set_counter(counter.wrapping_add((index * CHUNK_SIZE / 16) as u128));
+                        rng.fill_bytes(chunk);
+                    },
+                );
+
+                // `fill_bytes()` doesn't use the unused bits of the last generated block.
+                let (block, bits) = self.leftover_impl().get();
+                self.advance(blocks);
+                self.leftover_impl().set(block, bits);
+            }
+
+            fn par_fill<T: ParallelValue>(&self, slice: &mut [T]) {
+                let values = self.par_iter(slice.len());
+                slice
+                    .par_iter_mut()
+                    .zip(values)
+                    .for_each(|(value, random)| *value = random);
+            }
+
+            fn par_iter<T: ParallelValue>(
+                &self,
+                len: usize,
+            ) -> impl IndexedParallelIterator<Item = T> {
+                let per_block = (u128::BITS / T::BITS) as usize;
+
+                // The values that fit into the unused bits of the last generated block come first.
+                let (_, bits) = self.leftover_impl().get();
+                let head = ((bits / T::BITS) as usize).min(len);
+                let head: Vec<T> = (0..head).map(|_| T::random(self)).collect();
+
+                let len = len - head.len();
+                let blocks = len.div_ceil(per_block) as u128;
+                if self.remaining() < blocks {
+                    // The exhaustion policy is applied by the sequential generation.
+                    let values: Vec<T> = (0..len).map(|_| T::random(self)).collect();
+                    return head
+                        .into_par_iter()
+                        .chain(Either::Right(values.into_par_iter()));
+                }
+
+                let seed = self.snapshot().seed;
+                let counter = self.counter();
+                let values = (0..len).into_par_iter().map_init(
+                    move || (<Self as Random>::from_seed(seed.clone()), None),
+                    move |(rng, cached), index| {
+                        let block_index = index / per_block;
+                        let block = match *cached {
+                            Some((cached_index, block)) if cached_index == block_index => block,
+                            _ => {
+                                rng.set_counter(counter.wrapping_add(block_index as u128));
+                                let block = Random::next(rng);
+                                *cached = Some((block_index, block));
+                                block
+                            }
+                        };
+                        T::from_block(block, index % per_block)
+                    },
+                );
+
+                if len > 0 {
+                    // `u128()` and `i128()` don't use the unused bits of the last generated block.
+                    let (block, bits) = self.leftover_impl().get();
+                    self.advance((len / per_block) as u128);
+                    if T::BITS == u128::BITS {
+                        self.leftover_impl().set(block, bits);
+                    }
+
+                    // Leaves the unused bits of the last block like the sequential generation.
+                    for _ in 0..len % per_block {
+                        T::random(self);
+                    }
+                }
+
+                head.into_par_iter().chain(Either::Left(values))
+            }
+        }
+    };
+}
+
+impl_parallel!(Aes128Ctr128R);
+impl_parallel!(Aes256Ctr128R);
@@ -0,0 +1,448 @@
+//! Provides a wrapper that reseeds a random number generator periodically.
+
+use core::cell::{Cell, RefCell};
+
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+use crate::cipher::wipe;
+#[cfg(feature = "getrandom")]
+use crate::entropy::OsEntropy;
+use crate::entropy::{EntropyError, EntropySource};
+use crate::exhaustion::{CounterExhausted, ExhaustionPolicy};
+use crate::state::{RoundsMismatch, State};
+use crate::{Checkpoint, Random, Seek};
+
+/// A threshold for [`Reseeding::new()`] and [`Reseeding::with_entropy()`], that reseeds the
+/// wrapped generator after every 64 KiB of output.
+pub const DEFAULT_RESEED_THRESHOLD: u64 = 64 * 1024;
+
+/// The error that is returned if the entropy source failed to provide a new seed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ReseedError;
+
+impl core::fmt::Display for ReseedError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt.write_str("the entropy source failed to provide a new seed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReseedError {}
+
+/// A wrapper that reseeds a random number generator after a number of output bytes or after a
+/// duration has elapsed.
+///
+/// The wrapper counts the bytes it hands out and fetches a new seed from its entropy source
+/// before it generates output once the threshold is reached. The narrow methods like
+/// [`Random::u32()`] count the bytes of the returned value and [`Random::bool()`] counts as one
+/// byte. [`Reseeding::with_interval()`] additionally reseeds once the given duration has elapsed
+/// since the last reseed.
+///
+/// If the entropy source fails, the wrapped generator continues with its current state and
+/// the reseed is retried once the threshold or the interval is reached again.
+/// [`Reseeding::last_reseed_error()`] reports the failure.
+///
+/// The constructors of the [`Random`] trait don't take an entropy source, so the wrappers they
+/// create never reseed. A stream that was created with [`Random::from_seed()`] or
+/// [`Random::from_u64()`] therefore stays reproducible. Use [`Reseeding::new()`] or
+/// [`Reseeding::with_entropy()`] to wrap a generator that is reseeded.
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::*;
+///
+/// let rng = Reseeding::new(Aes128Ctr64::from_entropy(), 1024 * 1024);
+/// let _ = rng.u64();
+/// ```
+pub struct Reseeding<R: Random, S: EntropySource> {
+    rng: R,
+    source: Option<RefCell<S>>,
+    threshold: u64,
+    bytes: Cell<u64>,
+    #[cfg(feature = "std")]
+    interval: Option<Duration>,
+    #[cfg(feature = "std")]
+    reseeded_at: Cell<Instant>,
+    error: Cell<Option<ReseedError>>,
+}
+
+#[cfg(feature = "getrandom")]
+#[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+impl<R: Random> Reseeding<R, OsEntropy>
+where
+    R::Seed: AsMut<[u8]> + Default,
+{
+    /// Wraps the given generator, which is reseeded from the entropy source of the OS after
+    /// `threshold` output bytes.
+    pub fn new(rng: R, threshold: u64) -> Self {
+        Self::with_entropy(rng, threshold, OsEntropy)
+    }
+}
+
+impl<R: Random, S: EntropySource> Reseeding<R, S>
+where
+    R::Seed: AsMut<[u8]> + Default,
+{
+    /// Wraps the given generator, which is reseeded from the given entropy source after
+    /// `threshold` output bytes. The source has to fill the whole seed.
+    pub fn with_entropy(rng: R, threshold: u64, source: S) -> Self {
+        Self {
+            rng,
+            source: Some(RefCell::new(source)),
+            threshold,
+            bytes: Cell::new(0),
+            #[cfg(feature = "std")]
+            interval: None,
+            #[cfg(feature = "std")]
+            reseeded_at: Cell::new(Instant::now()),
+            error: Cell::new(None),
+        }
+    }
+
+    /// Additionally reseeds the generator once the given duration has elapsed since the last
+    /// reseed.
+    ///
+    /// The elapsed time is checked before every output, which adds the cost of reading the clock
+    /// to every call.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Reseeds the wrapped generator with a seed from the entropy source.
+    ///
+    /// If the entropy source fails, or the wrapper has no entropy source, the generator keeps its
+    /// current state.
+    pub fn reseed(&self) -> Result<(), ReseedError> {
+        let Some(source) = &self.source else {
+            self.error.set(Some(ReseedError));
+            return Err(ReseedError);
+        };
+
+        let mut seed = R::Seed::default();
+        let result = source.borrow_mut().fill(seed.as_mut());
+
+        let result = match result {
+            Ok(()) => {
+                self.rng.seed(core::mem::take(&mut seed));
+                self.reseeded();
+                Ok(())
+            }
+            Err(_) => {
+                // Back off until the threshold or the interval is reached again, instead of
+                // retrying before every output.
+                self.reset();
+                self.error.set(Some(ReseedError));
+                Err(ReseedError)
+            }
+        };
+
+        // The source may have filled the seed partially before it failed.
+        wipe(seed.as_mut());
+
+        result
+    }
+
+    /// Returns the error of the last reseed, if it failed. A successful reseed clears the error.
+    pub fn last_reseed_error(&self) -> Option<ReseedError> {
+        self.error.get()
+    }
+
+    /// Returns the number of bytes that were generated since the last attempt to reseed.
+    pub fn bytes_since_reseed(&self) -> u64 {
+        self.bytes.get()
+    }
+
+    /// Returns a reference to the wrapped generator.
+    pub fn inner(&self) -> &R {
+        &self.rng
+    }
+
+    /// Returns the wrapped generator.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    /// Wraps the given generator without an entropy source, so that it's never reseeded.
+    fn without_entropy(rng: R) -> Self {
+        Self {
+            rng,
+            source: None,
+            threshold: u64::MAX,
+            bytes: Cell::new(0),
+            #[cfg(feature = "std")]
+            interval: None,
+            #[cfg(feature = "std")]
+            reseeded_at: Cell::new(Instant::now()),
+            error: Cell::new(None),
+        }
+    }
+
+    /// Resets the byte count and the time of the last reseed.
+    fn reset(&self) {
+        self.bytes.set(0);
+        #[cfg(feature = "std")]
+        self.reseeded_at.set(Instant::now());
+    }
+
+    /// Resets the byte count and the time of the last reseed and clears the error.
+    fn reseeded(&self) {
+        self.reset();
+        self.error.set(None);
+    }
+
+    #[inline(always)]
+    fn is_due(&self) -> bool {
+        if self.bytes.get() >= self.threshold {
+            return true;
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(interval) = self.interval {
+            return self.reseeded_at.get().elapsed() >= interval;
+        }
+
+        false
+    }
+
+    /// Reseeds the generator if necessary and counts the bytes of the next output.
+    #[inline(always)]
+    fn consume(&self, bytes: u64) {
+        if self.is_due() {
+            // A failed reseed is retried once the reseed is due again.
+            let _ = self.reseed();
+        }
+        self.bytes.set(self.bytes.get().saturating_add(bytes));
+    }
+}
+
+impl<R: Random, S: EntropySource> Random for Reseeding<R, S>
+where
+    R::Seed: AsMut<[u8]> + Default,
+{
+    type Seed = R::Seed;
+    type Counter = R::Counter;
+
+    /// Creates a new random number generator using the given seed, which is never reseeded.
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::without_entropy(R::from_seed(seed))
+    }
+
+    fn seed(&self, seed: Self::Seed) {
+        self.rng.seed(seed);
+        self.reseeded();
+    }
+
+    /// Creates a new random number generator using a seed from the entropy source of the OS,
+    /// which is never reseeded.
+    #[cfg(feature = "getrandom")]
+    fn from_entropy() -> Self {
+        Self::without_entropy(R::from_entropy())
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn seed_from_entropy(&self) {
+        self.rng.seed_from_entropy();
+        self.reseeded();
+    }
+
+    /// Creates a new random number generator using a seed from the given entropy source, which is
+    /// never reseeded.
+    fn from_entropy_source(source: &mut impl EntropySource) -> Result<Self, EntropyError> {
+        R::from_entropy_source(source).map(Self::without_entropy)
+    }
+
+    /// Creates a new random number generator from a `u64` value, which is never reseeded.
+    fn from_u64(state: u64) -> Self {
+        Self::without_entropy(R::from_u64(state))
+    }
+
+    /// Creates a new random number generator with a seed from the given random number
+    /// generator, which is never reseeded.
+    fn from_random(rng: &impl Random) -> Self {
+        Self::without_entropy(R::from_random(rng))
+    }
+
+    fn is_hardware_accelerated(&self) -> bool {
+        self.rng.is_hardware_accelerated()
+    }
+
+    fn counter(&self) -> Self::Counter {
+        self.rng.counter()
+    }
+
+    #[inline(always)]
+    fn next(&self) -> u128 {
+        self.consume(16);
+        self.rng.next()
+    }
+
+    #[inline(always)]
+    fn try_next(&self) -> Result<u128, CounterExhausted> {
+        self.consume(16);
+        self.rng.try_next()
+    }
+
+    #[inline(always)]
+    fn next_blocks<const N: usize>(&self) -> [u128; N] {
+        self.consume(16 * N as u64);
+        self.rng.next_blocks()
+    }
+
+    #[inline(always)]
+    fn u8(&self) -> u8 {
+        self.consume(1);
+        self.rng.u8()
+    }
+
+    #[inline(always)]
+    fn u16(&self) -> u16 {
+        self.consume(2);
+        self.rng.u16()
+    }
+
+    #[inline(always)]
+    fn u32(&self) -> u32 {
+        self.consume(4);
+        self.rng.u32()
+    }
+
+    #[inline(always)]
+    fn u64(&self) -> u64 {
+        self.consume(8);
+        self.rng.u64()
+    }
+
+    #[inline(always)]
+    fn bool(&self) -> bool {
+        self.consume(1);
+        self.rng.bool()
+    }
+
+    fn fill_bytes(&self, slice: &mut [u8]) {
+        const U128_BYTES: u64 = (u128::BITS / 8) as u64;
+
+        let mut slice = slice;
+        while !slice.is_empty() {
+            let failed = self.is_due() && self.reseed().is_err();
+
+            // Fill whole blocks up to the threshold, so that a reseed doesn't discard any bytes
+            // of a block. If the reseed failed, the rest of the slice is filled at once.
+            let budget = self.threshold.saturating_sub(self.bytes.get());
+            let len = match budget {
+                _ if failed => slice.len(),
+                0 => slice.len(),
+                budget => {
+                    let budget = budget.div_ceil(U128_BYTES).saturating_mul(U128_BYTES);
+                    usize::try_from(budget).map_or(slice.len(), |budget| budget.min(slice.len()))
+                }
+            };
+
+            let (chunk, rest) = slice.split_at_mut(len);
+            self.bytes
+                .set(self.bytes.get().saturating_add(chunk.len() as u64));
+            self.rng.fill_bytes(chunk);
+            slice = rest;
+        }
+    }
+}
+
+impl<R: Seek, S: EntropySource> Seek for Reseeding<R, S>
+where
+    R::Seed: AsMut<[u8]> + Default,
+{
+    fn set_counter(&self, counter: Self::Counter) {
+        self.rng.set_counter(counter);
+    }
+
+    fn advance(&self, n: Self::Counter) {
+        self.rng.advance(n);
+    }
+
+    fn rewind(&self, n: Self::Counter) {
+        self.rng.rewind(n);
+    }
+
+    fn remaining(&self) -> u128 {
+        self.rng.remaining()
+    }
+
+    fn exhaustion_policy(&self) -> ExhaustionPolicy {
+        self.rng.exhaustion_policy()
+    }
+
+    fn set_exhaustion_policy(&self, policy: ExhaustionPolicy) {
+        self.rng.set_exhaustion_policy(policy);
+    }
+}
+
+impl<R: Checkpoint, S: EntropySource> Checkpoint for Reseeding<R, S>
+where
+    R::Seed: AsMut<[u8]> + Default,
+{
+    fn snapshot(&self) -> State<Self::Seed> {
+        self.rng.snapshot()
+    }
+
+    fn restore(&self, state: &State<Self::Seed>) -> Result<(), RoundsMismatch> {
+        self.rng.restore(state)
+    }
+}
+
+impl<R: Random, S: EntropySource> core::fmt::Debug for Reseeding<R, S>
+where
+    R::Seed: AsMut<[u8]> + Default,
+{
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut debug = fmt.debug_struct("Reseeding");
+        debug.field("threshold", &self.threshold);
+        #[cfg(feature = "std")]
+        debug.field("interval", &self.interval);
+        debug.finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "rand_core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+impl<R: Random, S: EntropySource> rand_core::RngCore for Reseeding<R, S>
+where
+    R::Seed: AsMut<[u8]> + Default,
+{
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        Random::u32(self)
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        Random::u64(self)
+    }
+
+    #[inline(always)]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        Random::fill_bytes(self, dest);
+    }
+
+    #[inline(always)]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        Random::fill_bytes(self, dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand_core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+impl<R: Random, S: EntropySource> rand_core::SeedableRng for Reseeding<R, S>
+where
+    R::Seed: AsMut<[u8]> + Default,
+{
+    type Seed = R::Seed;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Random::from_seed(seed)
+    }
+}
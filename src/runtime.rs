@@ -2,26 +2,33 @@
 
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::backend::x86::{
-    Aes128Ctr128 as Aes128Ctr128Hardware, Aes128Ctr64 as Aes128Ctr64Hardware,
-    Aes256Ctr128 as Aes256Ctr128Hardware, Aes256Ctr64 as Aes256Ctr64Hardware,
+    Aes128Cipher as Aes128CipherHardware, Aes128Ctr128R as Aes128Ctr128Hardware,
+    Aes128Ctr64R as Aes128Ctr64Hardware, Aes256Cipher as Aes256CipherHardware,
+    Aes256Ctr128R as Aes256Ctr128Hardware, Aes256Ctr64R as Aes256Ctr64Hardware,
 };
 
 #[cfg(all(target_arch = "riscv64", feature = "experimental_riscv"))]
 use crate::backend::riscv64::{
-    Aes128Ctr128 as Aes128Ctr128Hardware, Aes128Ctr64 as Aes128Ctr64Hardware,
-    Aes256Ctr128 as Aes256Ctr128Hardware, Aes256Ctr64 as Aes256Ctr64Hardware,
+    Aes128Cipher as Aes128CipherHardware, Aes128Ctr128R as Aes128Ctr128Hardware,
+    Aes128Ctr64R as Aes128Ctr64Hardware, Aes256Cipher as Aes256CipherHardware,
+    Aes256Ctr128R as Aes256Ctr128Hardware, Aes256Ctr64R as Aes256Ctr64Hardware,
 };
 
 #[cfg(target_arch = "aarch64")]
 use crate::backend::aarch64::{
-    Aes128Ctr128 as Aes128Ctr128Hardware, Aes128Ctr64 as Aes128Ctr64Hardware,
-    Aes256Ctr128 as Aes256Ctr128Hardware, Aes256Ctr64 as Aes256Ctr64Hardware,
+    Aes128Cipher as Aes128CipherHardware, Aes128Ctr128R as Aes128Ctr128Hardware,
+    Aes128Ctr64R as Aes128Ctr64Hardware, Aes256Cipher as Aes256CipherHardware,
+    Aes256Ctr128R as Aes256Ctr128Hardware, Aes256Ctr64R as Aes256Ctr64Hardware,
 };
 
+use crate::backend::leftover::Leftover;
 use crate::backend::soft::{
-    Aes128Ctr128 as Aes128Ctr128Software, Aes128Ctr64 as Aes128Ctr64Software,
-    Aes256Ctr128 as Aes256Ctr128Software, Aes256Ctr64 as Aes256Ctr64Software,
+    Aes128Cipher as Aes128CipherSoftware, Aes128Ctr128R as Aes128Ctr128Software,
+    Aes128Ctr64R as Aes128Ctr64Software, Aes256Cipher as Aes256CipherSoftware,
+    Aes256Ctr128R as Aes256Ctr128Software, Aes256Ctr64R as Aes256Ctr64Software,
 };
+use crate::constants::{AES128_KEY_SIZE, AES256_KEY_SIZE};
+use crate::exhaustion::Exhaustion;
 
 #[allow(unused)]
 pub(crate) fn has_hardware_acceleration() -> bool {
@@ -40,19 +47,20 @@ pub(crate) fn has_hardware_acceleration() -> bool {
 }
 
 #[derive(Clone)]
-enum Aes128Ctr64Inner {
-    Hardware(Box<Aes128Ctr64Hardware>),
-    Software(Box<Aes128Ctr64Software>),
+enum Aes128Ctr64Inner<const R: usize> {
+    Hardware(Box<Aes128Ctr64Hardware<R>>),
+    Software(Box<Aes128Ctr64Software<R>>),
 }
 
 /// A random number generator based on the AES-128 block cipher that runs in CTR mode and has a
 /// period of 64-bit.
 ///
-/// The full 10 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`Aes128Ctr64`](crate::Aes128Ctr64) is the variant with the full 10 rounds.
 #[derive(Clone)]
-pub struct Aes128Ctr64(Aes128Ctr64Inner);
+pub struct Aes128Ctr64R<const R: usize>(Aes128Ctr64Inner<R>);
 
-impl Aes128Ctr64 {
+impl<const R: usize> Aes128Ctr64R<R> {
     // This function is needed for the TLS.
     pub(crate) fn zeroed() -> Self {
         match has_hardware_acceleration() {
@@ -100,6 +108,13 @@ impl Aes128Ctr64 {
         }
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        match &self.0 {
+            Aes128Ctr64Inner::Hardware(this) => this.key_impl(),
+            Aes128Ctr64Inner::Software(this) => this.key_impl(),
+        }
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         match &self.0 {
             Aes128Ctr64Inner::Hardware(this) => this.counter_impl(),
@@ -107,6 +122,41 @@ impl Aes128Ctr64 {
         }
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        match &self.0 {
+            Aes128Ctr64Inner::Hardware(this) => this.set_counter_impl(counter),
+            Aes128Ctr64Inner::Software(this) => this.set_counter_impl(counter),
+        }
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        match &self.0 {
+            Aes128Ctr64Inner::Hardware(this) => this.stream_impl(),
+            Aes128Ctr64Inner::Software(this) => this.stream_impl(),
+        }
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        match &self.0 {
+            Aes128Ctr64Inner::Hardware(this) => this.set_stream_impl(stream),
+            Aes128Ctr64Inner::Software(this) => this.set_stream_impl(stream),
+        }
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        match &self.0 {
+            Aes128Ctr64Inner::Hardware(this) => this.leftover_impl(),
+            Aes128Ctr64Inner::Software(this) => this.leftover_impl(),
+        }
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        match &self.0 {
+            Aes128Ctr64Inner::Hardware(this) => this.exhaustion_impl(),
+            Aes128Ctr64Inner::Software(this) => this.exhaustion_impl(),
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn next_impl(&self) -> u128 {
         match &self.0 {
@@ -117,22 +167,34 @@ impl Aes128Ctr64 {
             Aes128Ctr64Inner::Software(this) => this.next_impl(),
         }
     }
+
+    #[inline(always)]
+    pub(crate) fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        match &self.0 {
+            Aes128Ctr64Inner::Hardware(this) => {
+                // Safety: We checked that the hardware acceleration is available.
+                unsafe { this.next_blocks_impl() }
+            }
+            Aes128Ctr64Inner::Software(this) => this.next_blocks_impl(),
+        }
+    }
 }
 
 #[derive(Clone)]
-enum Aes128Ctr128Inner {
-    Hardware(Box<Aes128Ctr128Hardware>),
-    Software(Box<Aes128Ctr128Software>),
+enum Aes128Ctr128Inner<const R: usize> {
+    Hardware(Box<Aes128Ctr128Hardware<R>>),
+    Software(Box<Aes128Ctr128Software<R>>),
 }
 
 /// A random number generator based on the AES-128 block cipher that runs in CTR mode and has a
 /// period of 128-bit.
 ///
-/// The full 10 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`Aes128Ctr128`](crate::Aes128Ctr128) is the variant with the full 10 rounds.
 #[derive(Clone)]
-pub struct Aes128Ctr128(Aes128Ctr128Inner);
+pub struct Aes128Ctr128R<const R: usize>(Aes128Ctr128Inner<R>);
 
-impl Aes128Ctr128 {
+impl<const R: usize> Aes128Ctr128R<R> {
     pub(crate) fn jump_impl(&self) -> Self {
         let inner = match &self.0 {
             Aes128Ctr128Inner::Hardware(this) => {
@@ -190,6 +252,13 @@ impl Aes128Ctr128 {
         }
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES128_KEY_SIZE] {
+        match &self.0 {
+            Aes128Ctr128Inner::Hardware(this) => this.key_impl(),
+            Aes128Ctr128Inner::Software(this) => this.key_impl(),
+        }
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         match &self.0 {
             Aes128Ctr128Inner::Hardware(this) => this.counter_impl(),
@@ -197,6 +266,27 @@ impl Aes128Ctr128 {
         }
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        match &self.0 {
+            Aes128Ctr128Inner::Hardware(this) => this.set_counter_impl(counter),
+            Aes128Ctr128Inner::Software(this) => this.set_counter_impl(counter),
+        }
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        match &self.0 {
+            Aes128Ctr128Inner::Hardware(this) => this.leftover_impl(),
+            Aes128Ctr128Inner::Software(this) => this.leftover_impl(),
+        }
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        match &self.0 {
+            Aes128Ctr128Inner::Hardware(this) => this.exhaustion_impl(),
+            Aes128Ctr128Inner::Software(this) => this.exhaustion_impl(),
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn next_impl(&self) -> u128 {
         match &self.0 {
@@ -207,22 +297,34 @@ impl Aes128Ctr128 {
             Aes128Ctr128Inner::Software(this) => this.next_impl(),
         }
     }
+
+    #[inline(always)]
+    pub(crate) fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        match &self.0 {
+            Aes128Ctr128Inner::Hardware(this) => {
+                // Safety: We checked that the hardware acceleration is available.
+                unsafe { this.next_blocks_impl() }
+            }
+            Aes128Ctr128Inner::Software(this) => this.next_blocks_impl(),
+        }
+    }
 }
 
 #[derive(Clone)]
-enum Aes256Ctr64Inner {
-    Hardware(Box<Aes256Ctr64Hardware>),
-    Software(Box<Aes256Ctr64Software>),
+enum Aes256Ctr64Inner<const R: usize> {
+    Hardware(Box<Aes256Ctr64Hardware<R>>),
+    Software(Box<Aes256Ctr64Software<R>>),
 }
 
 /// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
 /// period of 64-bit.
 ///
-/// The full 14 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`Aes256Ctr64`](crate::Aes256Ctr64) is the variant with the full 14 rounds.
 #[derive(Clone)]
-pub struct Aes256Ctr64(Aes256Ctr64Inner);
+pub struct Aes256Ctr64R<const R: usize>(Aes256Ctr64Inner<R>);
 
-impl Aes256Ctr64 {
+impl<const R: usize> Aes256Ctr64R<R> {
     pub(crate) fn from_seed_impl(key: [u8; 32], nonce: [u8; 8], counter: [u8; 8]) -> Self {
         match has_hardware_acceleration() {
             true => {
@@ -256,6 +358,13 @@ impl Aes256Ctr64 {
         }
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        match &self.0 {
+            Aes256Ctr64Inner::Hardware(this) => this.key_impl(),
+            Aes256Ctr64Inner::Software(this) => this.key_impl(),
+        }
+    }
+
     pub(crate) fn counter_impl(&self) -> u64 {
         match &self.0 {
             Aes256Ctr64Inner::Hardware(this) => this.counter_impl(),
@@ -263,6 +372,41 @@ impl Aes256Ctr64 {
         }
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u64) {
+        match &self.0 {
+            Aes256Ctr64Inner::Hardware(this) => this.set_counter_impl(counter),
+            Aes256Ctr64Inner::Software(this) => this.set_counter_impl(counter),
+        }
+    }
+
+    pub(crate) fn stream_impl(&self) -> u64 {
+        match &self.0 {
+            Aes256Ctr64Inner::Hardware(this) => this.stream_impl(),
+            Aes256Ctr64Inner::Software(this) => this.stream_impl(),
+        }
+    }
+
+    pub(crate) fn set_stream_impl(&self, stream: u64) {
+        match &self.0 {
+            Aes256Ctr64Inner::Hardware(this) => this.set_stream_impl(stream),
+            Aes256Ctr64Inner::Software(this) => this.set_stream_impl(stream),
+        }
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        match &self.0 {
+            Aes256Ctr64Inner::Hardware(this) => this.leftover_impl(),
+            Aes256Ctr64Inner::Software(this) => this.leftover_impl(),
+        }
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        match &self.0 {
+            Aes256Ctr64Inner::Hardware(this) => this.exhaustion_impl(),
+            Aes256Ctr64Inner::Software(this) => this.exhaustion_impl(),
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn next_impl(&self) -> u128 {
         match &self.0 {
@@ -273,22 +417,34 @@ impl Aes256Ctr64 {
             Aes256Ctr64Inner::Software(this) => this.next_impl(),
         }
     }
+
+    #[inline(always)]
+    pub(crate) fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        match &self.0 {
+            Aes256Ctr64Inner::Hardware(this) => {
+                // Safety: We checked that the hardware acceleration is available.
+                unsafe { this.next_blocks_impl() }
+            }
+            Aes256Ctr64Inner::Software(this) => this.next_blocks_impl(),
+        }
+    }
 }
 
 #[derive(Clone)]
-enum Aes256Ctr128Inner {
-    Hardware(Box<Aes256Ctr128Hardware>),
-    Software(Box<Aes256Ctr128Software>),
+enum Aes256Ctr128Inner<const R: usize> {
+    Hardware(Box<Aes256Ctr128Hardware<R>>),
+    Software(Box<Aes256Ctr128Software<R>>),
 }
 
 /// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
 /// period of 128-bit.
 ///
-/// The full 14 rounds of encryption are used.
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`Aes256Ctr128`](crate::Aes256Ctr128) is the variant with the full 14 rounds.
 #[derive(Clone)]
-pub struct Aes256Ctr128(Aes256Ctr128Inner);
+pub struct Aes256Ctr128R<const R: usize>(Aes256Ctr128Inner<R>);
 
-impl Aes256Ctr128 {
+impl<const R: usize> Aes256Ctr128R<R> {
     pub(crate) fn jump_impl(&self) -> Self {
         let inner = match &self.0 {
             Aes256Ctr128Inner::Hardware(this) => {
@@ -327,6 +483,13 @@ impl Aes256Ctr128 {
         }
     }
 
+    pub(crate) fn key_impl(&self) -> [u8; AES256_KEY_SIZE] {
+        match &self.0 {
+            Aes256Ctr128Inner::Hardware(this) => this.key_impl(),
+            Aes256Ctr128Inner::Software(this) => this.key_impl(),
+        }
+    }
+
     pub(crate) fn counter_impl(&self) -> u128 {
         match &self.0 {
             Aes256Ctr128Inner::Hardware(this) => this.counter_impl(),
@@ -334,6 +497,27 @@ impl Aes256Ctr128 {
         }
     }
 
+    pub(crate) fn set_counter_impl(&self, counter: u128) {
+        match &self.0 {
+            Aes256Ctr128Inner::Hardware(this) => this.set_counter_impl(counter),
+            Aes256Ctr128Inner::Software(this) => this.set_counter_impl(counter),
+        }
+    }
+
+    pub(crate) fn leftover_impl(&self) -> &Leftover {
+        match &self.0 {
+            Aes256Ctr128Inner::Hardware(this) => this.leftover_impl(),
+            Aes256Ctr128Inner::Software(this) => this.leftover_impl(),
+        }
+    }
+
+    pub(crate) fn exhaustion_impl(&self) -> &Exhaustion {
+        match &self.0 {
+            Aes256Ctr128Inner::Hardware(this) => this.exhaustion_impl(),
+            Aes256Ctr128Inner::Software(this) => this.exhaustion_impl(),
+        }
+    }
+
     pub(crate) fn seed_impl(&self, key: [u8; 32], counter: [u8; 16]) {
         match &self.0 {
             Aes256Ctr128Inner::Hardware(this) => {
@@ -363,4 +547,81 @@ impl Aes256Ctr128 {
             Aes256Ctr128Inner::Software(this) => this.next_impl(),
         }
     }
+
+    #[inline(always)]
+    pub(crate) fn next_blocks_impl<const N: usize>(&self) -> [u128; N] {
+        match &self.0 {
+            Aes256Ctr128Inner::Hardware(this) => {
+                // Safety: We checked that the hardware acceleration is available.
+                unsafe { this.next_blocks_impl() }
+            }
+            Aes256Ctr128Inner::Software(this) => this.next_blocks_impl(),
+        }
+    }
+}
+
+/// The AES-128 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) enum Aes128Cipher<const R: usize> {
+    Hardware(Box<Aes128CipherHardware<R>>),
+    Software(Box<Aes128CipherSoftware<R>>),
+}
+
+impl<const R: usize> Aes128Cipher<R> {
+    pub(crate) fn new_impl(key: [u8; 16]) -> Self {
+        match has_hardware_acceleration() {
+            // Safety: We checked that the hardware acceleration is available.
+            true => Self::Hardware(Box::new(unsafe { Aes128CipherHardware::new_impl(key) })),
+            false => Self::Software(Box::new(Aes128CipherSoftware::new_impl(key))),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        match self {
+            Self::Hardware(this) => this.is_hardware_accelerated_impl(),
+            Self::Software(this) => this.is_hardware_accelerated_impl(),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn encrypt_blocks_impl<const N: usize>(&self, blocks: [u128; N]) -> [u128; N] {
+        match self {
+            // Safety: We checked that the hardware acceleration is available.
+            Self::Hardware(this) => unsafe { this.encrypt_blocks_impl(blocks) },
+            Self::Software(this) => this.encrypt_blocks_impl(blocks),
+        }
+    }
+}
+
+/// The AES-256 block cipher with `R` rounds, that encrypts independent counter blocks.
+#[derive(Clone)]
+pub(crate) enum Aes256Cipher<const R: usize> {
+    Hardware(Box<Aes256CipherHardware<R>>),
+    Software(Box<Aes256CipherSoftware<R>>),
+}
+
+impl<const R: usize> Aes256Cipher<R> {
+    pub(crate) fn new_impl(key: [u8; 32]) -> Self {
+        match has_hardware_acceleration() {
+            // Safety: We checked that the hardware acceleration is available.
+            true => Self::Hardware(Box::new(unsafe { Aes256CipherHardware::new_impl(key) })),
+            false => Self::Software(Box::new(Aes256CipherSoftware::new_impl(key))),
+        }
+    }
+
+    pub(crate) fn is_hardware_accelerated_impl(&self) -> bool {
+        match self {
+            Self::Hardware(this) => this.is_hardware_accelerated_impl(),
+            Self::Software(this) => this.is_hardware_accelerated_impl(),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn encrypt_blocks_impl<const N: usize>(&self, blocks: [u128; N]) -> [u128; N] {
+        match self {
+            // Safety: We checked that the hardware acceleration is available.
+            Self::Hardware(this) => unsafe { this.encrypt_blocks_impl(blocks) },
+            Self::Software(this) => this.encrypt_blocks_impl(blocks),
+        }
+    }
 }
@@ -1,5 +1,57 @@
 //! Seeds are used to properly initialize the provided random number generators.
+//!
+//! # Derivation
+//!
+//! All seeds can be derived from an input of arbitrary length, for example the name of a stream
+//! like `"run-42/agent-7"`, and a context that separates different uses of the same input. The
+//! derivation uses AES-128-CMAC with the two-step key derivation of NIST SP 800-56C:
+//!
+//! 1. The input is extracted into a key `K = AES-CMAC(0^128, input)`, using an all-zero key.
+//! 2. The seed is expanded with the KDF in counter mode of NIST SP 800-108 as the concatenation of
+//!    the blocks `AES-CMAC(K, [i]_32 || label || 0x00 || context || [L]_32)` for `i = 1, 2, ...`,
+//!    truncated to the length of the seed. The label is the name of the seed type (for example
+//!    `Aes128Ctr64Seed`), `L` is the length of the seed in bits and the integers are encoded as
+//!    big endian.
+//!
+//! The construction is stable, so the same input always maps to the same seed in every version of
+//! this crate and on every backend. It doesn't add any entropy, so secret seeds must be derived
+//! from secret input.
+//!
+//! # Mixing Entropy
+//!
+//! [`SeedBuilder`] condenses several entropy inputs, like the OS entropy source, the random number
+//! generator of the CPU, a timestamp and user provided bytes, into a seed of any type. Every input
+//! claims how much entropy it provides and the builder refuses to create a seed below a
+//! configurable threshold.
+//!
+//! # Text Encoding
+//!
+//! The seeds don't implement [`Display`](core::fmt::Display) and their `Debug` output is
+//! redacted, so that seeds don't end up in logs by accident. `reveal()` returns a value that
+//! displays the seed as a hex string, so that it can be logged explicitly to reproduce a stream.
+//! [`FromStr`] parses the hex string again. `base64()` and `from_base64()` use a base64 string
+//! with padding instead.
+//!
+//! ```
+//! use rand_aes::seeds::Aes128Ctr64Seed;
+//!
+//! let seed = Aes128Ctr64Seed::new([1; 16], [2; 8], 3);
+//! let hex = seed.reveal().to_string();
+//! let parsed: Aes128Ctr64Seed = hex.parse().unwrap();
+//!
+//! assert_eq!(parsed.as_ref(), seed.as_ref());
+//! assert_eq!(format!("{seed:?}"), "Aes128Ctr64Seed(<redacted>)");
+//! ```
 
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(not(feature = "verification"))]
+pub use crate::builder::{SeedBuilder, SeedBuilderError, DEFAULT_ENTROPY_THRESHOLD};
+
+use crate::encoding::{decode_base64, decode_hex};
+pub use crate::encoding::{Base64, ParseSeedError, Reveal};
+use crate::entropy::{EntropyError, EntropySource};
 #[cfg(feature = "getrandom")]
 use crate::secure_bytes;
 
@@ -23,6 +75,22 @@ impl Aes128Ctr64Seed {
     pub fn from_entropy() -> Self {
         Aes128Ctr64Seed(secure_bytes())
     }
+
+    /// Creates a new seed from the given entropy source.
+    pub fn from_entropy_source(source: &mut impl EntropySource) -> Result<Self, EntropyError> {
+        let mut seed = Self::default();
+        source.fill(&mut seed.0)?;
+        Ok(seed)
+    }
+
+    /// Derives a seed from the given context and input of arbitrary length.
+    ///
+    /// The same context and input always result in the same seed. See the
+    /// [module documentation](crate::seeds#derivation) for the construction.
+    #[cfg(not(feature = "verification"))]
+    pub fn derive(context: &[u8], input: &[u8]) -> Self {
+        Self(crate::kdf::derive(b"Aes128Ctr64Seed", context, input))
+    }
 }
 
 impl AsMut<[u8]> for Aes128Ctr64Seed {
@@ -62,6 +130,22 @@ impl Aes128Ctr128Seed {
     pub fn from_entropy() -> Self {
         Aes128Ctr128Seed(secure_bytes())
     }
+
+    /// Creates a new seed from the given entropy source.
+    pub fn from_entropy_source(source: &mut impl EntropySource) -> Result<Self, EntropyError> {
+        let mut seed = Self::default();
+        source.fill(&mut seed.0)?;
+        Ok(seed)
+    }
+
+    /// Derives a seed from the given context and input of arbitrary length.
+    ///
+    /// The same context and input always result in the same seed. See the
+    /// [module documentation](crate::seeds#derivation) for the construction.
+    #[cfg(not(feature = "verification"))]
+    pub fn derive(context: &[u8], input: &[u8]) -> Self {
+        Self(crate::kdf::derive(b"Aes128Ctr128Seed", context, input))
+    }
 }
 
 impl AsMut<[u8]> for Aes128Ctr128Seed {
@@ -102,6 +186,22 @@ impl Aes256Ctr64Seed {
     pub fn from_entropy() -> Self {
         Aes256Ctr64Seed(secure_bytes())
     }
+
+    /// Creates a new seed from the given entropy source.
+    pub fn from_entropy_source(source: &mut impl EntropySource) -> Result<Self, EntropyError> {
+        let mut seed = Self::default();
+        source.fill(&mut seed.0)?;
+        Ok(seed)
+    }
+
+    /// Derives a seed from the given context and input of arbitrary length.
+    ///
+    /// The same context and input always result in the same seed. See the
+    /// [module documentation](crate::seeds#derivation) for the construction.
+    #[cfg(not(feature = "verification"))]
+    pub fn derive(context: &[u8], input: &[u8]) -> Self {
+        Self(crate::kdf::derive(b"Aes256Ctr64Seed", context, input))
+    }
 }
 
 impl Default for Aes256Ctr64Seed {
@@ -147,6 +247,22 @@ impl Aes256Ctr128Seed {
     pub fn from_entropy() -> Self {
         Aes256Ctr128Seed(secure_bytes())
     }
+
+    /// Creates a new seed from the given entropy source.
+    pub fn from_entropy_source(source: &mut impl EntropySource) -> Result<Self, EntropyError> {
+        let mut seed = Self::default();
+        source.fill(&mut seed.0)?;
+        Ok(seed)
+    }
+
+    /// Derives a seed from the given context and input of arbitrary length.
+    ///
+    /// The same context and input always result in the same seed. See the
+    /// [module documentation](crate::seeds#derivation) for the construction.
+    #[cfg(not(feature = "verification"))]
+    pub fn derive(context: &[u8], input: &[u8]) -> Self {
+        Self(crate::kdf::derive(b"Aes256Ctr128Seed", context, input))
+    }
 }
 
 impl Default for Aes256Ctr128Seed {
@@ -172,3 +288,206 @@ impl From<[u8; 48]> for Aes256Ctr128Seed {
         Self(value)
     }
 }
+
+/// Seed for the [`crate::Aes128FastKeyErasure`] PRNG.
+#[derive(Clone, Default)]
+pub struct Aes128FastKeyErasureSeed([u8; 16]);
+
+impl Aes128FastKeyErasureSeed {
+    /// Creates a new seed using the key of the first batch.
+    pub fn new(key: [u8; 16]) -> Self {
+        Self(key)
+    }
+
+    /// Creates a new seed from the OS provided entropy source.
+    #[cfg(feature = "getrandom")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+    pub fn from_entropy() -> Self {
+        Aes128FastKeyErasureSeed(secure_bytes())
+    }
+
+    /// Creates a new seed from the given entropy source.
+    pub fn from_entropy_source(source: &mut impl EntropySource) -> Result<Self, EntropyError> {
+        let mut seed = Self::default();
+        source.fill(&mut seed.0)?;
+        Ok(seed)
+    }
+
+    /// Derives a seed from the given context and input of arbitrary length.
+    ///
+    /// The same context and input always result in the same seed. See the
+    /// [module documentation](crate::seeds#derivation) for the construction.
+    #[cfg(not(feature = "verification"))]
+    pub fn derive(context: &[u8], input: &[u8]) -> Self {
+        Self(crate::kdf::derive(
+            b"Aes128FastKeyErasureSeed",
+            context,
+            input,
+        ))
+    }
+}
+
+impl AsMut<[u8]> for Aes128FastKeyErasureSeed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+}
+
+impl AsRef<[u8]> for Aes128FastKeyErasureSeed {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl From<[u8; 16]> for Aes128FastKeyErasureSeed {
+    fn from(value: [u8; 16]) -> Self {
+        Self(value)
+    }
+}
+
+/// Seed for the [`crate::Aes256FastKeyErasure`] PRNG.
+#[derive(Clone, Default)]
+pub struct Aes256FastKeyErasureSeed([u8; 32]);
+
+impl Aes256FastKeyErasureSeed {
+    /// Creates a new seed using the key of the first batch.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// Creates a new seed from the OS provided entropy source.
+    #[cfg(feature = "getrandom")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+    pub fn from_entropy() -> Self {
+        Aes256FastKeyErasureSeed(secure_bytes())
+    }
+
+    /// Creates a new seed from the given entropy source.
+    pub fn from_entropy_source(source: &mut impl EntropySource) -> Result<Self, EntropyError> {
+        let mut seed = Self::default();
+        source.fill(&mut seed.0)?;
+        Ok(seed)
+    }
+
+    /// Derives a seed from the given context and input of arbitrary length.
+    ///
+    /// The same context and input always result in the same seed. See the
+    /// [module documentation](crate::seeds#derivation) for the construction.
+    #[cfg(not(feature = "verification"))]
+    pub fn derive(context: &[u8], input: &[u8]) -> Self {
+        Self(crate::kdf::derive(
+            b"Aes256FastKeyErasureSeed",
+            context,
+            input,
+        ))
+    }
+}
+
+impl AsMut<[u8]> for Aes256FastKeyErasureSeed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+}
+
+impl AsRef<[u8]> for Aes256FastKeyErasureSeed {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl From<[u8; 32]> for Aes256FastKeyErasureSeed {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+/// Implements the wiping of a seed on drop.
+#[cfg(feature = "zeroize")]
+macro_rules! impl_zeroize {
+    ($seed:ident) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+        impl zeroize::Zeroize for $seed {
+            fn zeroize(&mut self) {
+                self.0.zeroize();
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+        impl zeroize::ZeroizeOnDrop for $seed {}
+
+        impl Drop for $seed {
+            fn drop(&mut self) {
+                zeroize::Zeroize::zeroize(self);
+            }
+        }
+    };
+}
+
+#[cfg(feature = "zeroize")]
+impl_zeroize!(Aes128Ctr64Seed);
+#[cfg(feature = "zeroize")]
+impl_zeroize!(Aes128Ctr128Seed);
+#[cfg(feature = "zeroize")]
+impl_zeroize!(Aes256Ctr64Seed);
+#[cfg(feature = "zeroize")]
+impl_zeroize!(Aes256Ctr128Seed);
+#[cfg(feature = "zeroize")]
+impl_zeroize!(Aes128FastKeyErasureSeed);
+#[cfg(feature = "zeroize")]
+impl_zeroize!(Aes256FastKeyErasureSeed);
+
+/// Implements the text encoding of a seed.
+macro_rules! impl_encoding {
+    ($seed:ident, $size:literal) => {
+        impl $seed {
+            /// Parses a seed from a hex string. Upper and lower case characters are accepted.
+            pub fn from_hex(string: &str) -> Result<Self, ParseSeedError> {
+                decode_hex::<$size>(string).map(Self)
+            }
+
+            /// Parses a seed from a base64 string with padding, that uses the standard alphabet
+            /// of RFC 4648.
+            pub fn from_base64(string: &str) -> Result<Self, ParseSeedError> {
+                decode_base64::<$size>(string).map(Self)
+            }
+
+            /// Returns a value that displays the seed as a base64 string with padding, that uses
+            /// the standard alphabet of RFC 4648.
+            pub fn base64(&self) -> Base64<'_> {
+                Base64(&self.0)
+            }
+
+            /// Returns a value that displays the seed as a hex string and shows it in its `Debug`
+            /// output, which are both not available for the seed itself.
+            pub fn reveal(&self) -> Reveal<'_> {
+                Reveal {
+                    name: stringify!($seed),
+                    bytes: &self.0,
+                }
+            }
+        }
+
+        impl fmt::Debug for $seed {
+            fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.debug_tuple(stringify!($seed))
+                    .field(&format_args!("<redacted>"))
+                    .finish()
+            }
+        }
+
+        impl FromStr for $seed {
+            type Err = ParseSeedError;
+
+            fn from_str(string: &str) -> Result<Self, Self::Err> {
+                Self::from_hex(string)
+            }
+        }
+    };
+}
+
+impl_encoding!(Aes128Ctr64Seed, 32);
+impl_encoding!(Aes128Ctr128Seed, 32);
+impl_encoding!(Aes256Ctr64Seed, 48);
+impl_encoding!(Aes256Ctr128Seed, 48);
+impl_encoding!(Aes128FastKeyErasureSeed, 16);
+impl_encoding!(Aes256FastKeyErasureSeed, 32);
@@ -0,0 +1,199 @@
+//! Implements `Serialize` and `Deserialize` of serde for the seeds and the generator state.
+//!
+//! Human-readable formats use a hex string, all other formats use a compact byte string.
+
+use core::fmt;
+
+use serde::de::{self, Deserializer, SeqAccess, Unexpected, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::exhaustion::ExhaustionPolicy;
+use crate::seeds::{
+    Aes128Ctr128Seed, Aes128Ctr64Seed, Aes128FastKeyErasureSeed, Aes256Ctr128Seed, Aes256Ctr64Seed,
+    Aes256FastKeyErasureSeed,
+};
+use crate::state::State;
+
+const HEX_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+/// The size of the state after the seed: The leftover block, the leftover bit count, the
+/// exhaustion policy, the exhausted flag and the number of rounds.
+const STATE_EXTRA_SIZE: usize = 16 + 1 + 1 + 1 + 1;
+
+/// The largest byte string that is serialized (the state of an AES-256 generator).
+const MAX_SIZE: usize = 48 + STATE_EXTRA_SIZE;
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        let mut buffer = [0u8; MAX_SIZE * 2];
+        let hex = &mut buffer[..bytes.len() * 2];
+        for (chunk, byte) in hex.chunks_exact_mut(2).zip(bytes) {
+            chunk[0] = HEX_CHARS[(byte >> 4) as usize];
+            chunk[1] = HEX_CHARS[(byte & 0xF) as usize];
+        }
+        // SAFETY: The buffer only contains ASCII characters.
+        serializer.serialize_str(unsafe { core::str::from_utf8_unchecked(hex) })
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor::<N>)
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor::<N>)
+    }
+}
+
+struct BytesVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{N} bytes or a hex string with {} characters",
+            N * 2
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        fn nibble(char: u8) -> Option<u8> {
+            match char {
+                b'0'..=b'9' => Some(char - b'0'),
+                b'a'..=b'f' => Some(char - b'a' + 10),
+                b'A'..=b'F' => Some(char - b'A' + 10),
+                _ => None,
+            }
+        }
+
+        if v.len() != N * 2 {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+
+        let mut bytes = [0u8; N];
+        for (byte, chunk) in bytes.iter_mut().zip(v.as_bytes().chunks_exact(2)) {
+            match (nibble(chunk[0]), nibble(chunk[1])) {
+                (Some(high), Some(low)) => *byte = (high << 4) | low,
+                _ => return Err(E::invalid_value(Unexpected::Str(v), &self)),
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0u8; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+        Ok(bytes)
+    }
+}
+
+macro_rules! impl_seed {
+    ($seed:ident, $size:expr) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl Serialize for $seed {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes(self.as_ref(), serializer)
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl<'de> Deserialize<'de> for $seed {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_bytes::<D, $size>(deserializer).map($seed::from)
+            }
+        }
+    };
+}
+
+impl_seed!(Aes128Ctr64Seed, 32);
+impl_seed!(Aes128Ctr128Seed, 32);
+impl_seed!(Aes256Ctr64Seed, 48);
+impl_seed!(Aes256Ctr128Seed, 48);
+impl_seed!(Aes128FastKeyErasureSeed, 16);
+impl_seed!(Aes256FastKeyErasureSeed, 32);
+
+/// The state is serialized as the seed, followed by the leftover block (little endian), the
+/// leftover bit count, the exhaustion policy, the exhausted flag and the number of rounds.
+macro_rules! impl_state {
+    ($seed:ident, $size:expr) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl Serialize for State<$seed> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut bytes = [0u8; $size + STATE_EXTRA_SIZE];
+                bytes[..$size].copy_from_slice(self.seed.as_ref());
+                bytes[$size..$size + 16].copy_from_slice(&self.leftover_block.to_le_bytes());
+                bytes[$size + 16] = self.leftover_bits as u8;
+                bytes[$size + 17] = match self.policy {
+                    ExhaustionPolicy::Panic => 0,
+                    ExhaustionPolicy::Wrap => 1,
+                    ExhaustionPolicy::RollOver => 2,
+                };
+                bytes[$size + 18] = self.exhausted as u8;
+                bytes[$size + 19] = self.rounds as u8;
+                serialize_bytes(&bytes, serializer)
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl<'de> Deserialize<'de> for State<$seed> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bytes = deserialize_bytes::<D, { $size + STATE_EXTRA_SIZE }>(deserializer)?;
+
+                let mut seed = [0u8; $size];
+                seed.copy_from_slice(&bytes[..$size]);
+                let mut leftover_block = [0u8; 16];
+                leftover_block.copy_from_slice(&bytes[$size..$size + 16]);
+
+                let leftover_bits = bytes[$size + 16] as u32;
+                if leftover_bits >= u128::BITS {
+                    return Err(de::Error::custom("invalid leftover bit count"));
+                }
+                let policy = match bytes[$size + 17] {
+                    0 => ExhaustionPolicy::Panic,
+                    1 => ExhaustionPolicy::Wrap,
+                    2 => ExhaustionPolicy::RollOver,
+                    _ => return Err(de::Error::custom("invalid exhaustion policy")),
+                };
+                let exhausted = match bytes[$size + 18] {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(de::Error::custom("invalid exhausted flag")),
+                };
+                let rounds = match bytes[$size + 19] {
+                    rounds @ 1..=14 => rounds as usize,
+                    _ => return Err(de::Error::custom("invalid number of rounds")),
+                };
+
+                Ok(State {
+                    seed: $seed::from(seed),
+                    rounds,
+                    leftover_block: u128::from_le_bytes(leftover_block),
+                    leftover_bits,
+                    policy,
+                    exhausted,
+                })
+            }
+        }
+    };
+}
+
+impl_state!(Aes128Ctr64Seed, 32);
+impl_state!(Aes128Ctr128Seed, 32);
+impl_state!(Aes256Ctr64Seed, 48);
+impl_state!(Aes256Ctr128Seed, 48);
@@ -0,0 +1,561 @@
+//! Provides the random number generators that can be shared between threads.
+
+#[cfg(any(test, not(target_has_atomic = "64")))]
+use core::cell::UnsafeCell;
+#[cfg(any(test, not(target_has_atomic = "64")))]
+use core::sync::atomic::AtomicBool;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering;
+
+use crate::cipher::wipe;
+use crate::exhaustion::CounterExhausted;
+use crate::implementation::safely_call;
+use crate::{seeds, Aes128Cipher, Aes256Cipher};
+
+/// The number of blocks a shared generator hands out at most.
+const MAX_BLOCKS: u64 = 1 << 63;
+
+/// The number of blocks that are reserved and encrypted at once when filling a slice.
+const BULK_BLOCKS: usize = 8;
+
+#[cfg(target_has_atomic = "64")]
+type BlockCounter = AtomicCounter;
+
+#[cfg(not(target_has_atomic = "64"))]
+type BlockCounter = LockedCounter;
+
+/// Hands out the offsets of the blocks with an atomic compare-exchange.
+#[cfg(target_has_atomic = "64")]
+struct AtomicCounter(AtomicU64);
+
+#[cfg(target_has_atomic = "64")]
+impl AtomicCounter {
+    const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+
+    /// Reserves `n` blocks and returns the offset of the first block, or `None` if fewer than `n`
+    /// blocks are left below `limit`. A failed reservation keeps the counter, so that the
+    /// remaining blocks can still be reserved in smaller steps.
+    #[inline(always)]
+    fn reserve(&self, n: u64, limit: u64) -> Option<u64> {
+        // The offsets only need to be unique, so no ordering with other memory is required.
+        let mut offset = self.0.load(Ordering::Relaxed);
+        loop {
+            let end = offset.checked_add(n).filter(|end| *end <= limit)?;
+            match self
+                .0
+                .compare_exchange_weak(offset, end, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return Some(offset),
+                Err(current) => offset = current,
+            }
+        }
+    }
+
+    fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Hands out the offsets of the blocks behind a spin lock, for targets without 64-bit atomics.
+#[cfg(any(test, not(target_has_atomic = "64")))]
+struct LockedCounter {
+    locked: AtomicBool,
+    offset: UnsafeCell<u64>,
+}
+
+// Safety: The offset is only accessed while holding the lock.
+#[cfg(any(test, not(target_has_atomic = "64")))]
+unsafe impl Sync for LockedCounter {}
+
+#[cfg(any(test, not(target_has_atomic = "64")))]
+impl LockedCounter {
+    const fn new() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            offset: UnsafeCell::new(0),
+        }
+    }
+
+    fn with_lock<T>(&self, f: impl FnOnce(&mut u64) -> T) -> T {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+
+        // Safety: We hold the lock, so we have exclusive access to the offset.
+        let result = f(unsafe { &mut *self.offset.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+
+    /// Reserves `n` blocks and returns the offset of the first block, or `None` if fewer than `n`
+    /// blocks are left below `limit`. A failed reservation keeps the counter, so that the
+    /// remaining blocks can still be reserved in smaller steps.
+    #[inline(always)]
+    fn reserve(&self, n: u64, limit: u64) -> Option<u64> {
+        self.with_lock(|offset| {
+            let end = offset.checked_add(n).filter(|end| *end <= limit)?;
+            let first = *offset;
+            *offset = end;
+            Some(first)
+        })
+    }
+
+    fn get(&self) -> u64 {
+        self.with_lock(|offset| *offset)
+    }
+}
+
+/// A random number generator based on the AES-128 block cipher that runs in CTR mode and has a
+/// period of 64-bit, which can be shared between threads.
+///
+/// Every call reserves its counter values with an atomic compare-exchange and encrypts them
+/// locally, so any number of threads can draw from the same stream without a lock. Drawn
+/// sequentially, it generates the same blocks as [`Aes128Ctr64R`](crate::Aes128Ctr64R) with the
+/// same seed.
+///
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`SharedAes128Ctr64`](crate::SharedAes128Ctr64) is the variant with the full 10 rounds.
+pub struct SharedAes128Ctr64R<const R: usize> {
+    cipher: Aes128Cipher<R>,
+    base: u128,
+    limit: u64,
+    offset: BlockCounter,
+}
+
+/// A random number generator based on the AES-128 block cipher that runs in CTR mode and has a
+/// period of 128-bit, which can be shared between threads.
+///
+/// Every call reserves its counter values with an atomic compare-exchange and encrypts them
+/// locally, so any number of threads can draw from the same stream without a lock. Drawn
+/// sequentially, it generates the same blocks as [`Aes128Ctr128R`](crate::Aes128Ctr128R) with the
+/// same seed.
+///
+/// Uses `R` rounds of encryption, which must be between 1 and 10.
+/// [`SharedAes128Ctr128`](crate::SharedAes128Ctr128) is the variant with the full 10 rounds.
+pub struct SharedAes128Ctr128R<const R: usize> {
+    cipher: Aes128Cipher<R>,
+    base: u128,
+    limit: u64,
+    offset: BlockCounter,
+}
+
+/// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
+/// period of 64-bit, which can be shared between threads.
+///
+/// Every call reserves its counter values with an atomic compare-exchange and encrypts them
+/// locally, so any number of threads can draw from the same stream without a lock. Drawn
+/// sequentially, it generates the same blocks as [`Aes256Ctr64R`](crate::Aes256Ctr64R) with the
+/// same seed.
+///
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`SharedAes256Ctr64`](crate::SharedAes256Ctr64) is the variant with the full 14 rounds.
+pub struct SharedAes256Ctr64R<const R: usize> {
+    cipher: Aes256Cipher<R>,
+    base: u128,
+    limit: u64,
+    offset: BlockCounter,
+}
+
+/// A random number generator based on the AES-256 block cipher that runs in CTR mode and has a
+/// period of 128-bit, which can be shared between threads.
+///
+/// Every call reserves its counter values with an atomic compare-exchange and encrypts them
+/// locally, so any number of threads can draw from the same stream without a lock. Drawn
+/// sequentially, it generates the same blocks as [`Aes256Ctr128R`](crate::Aes256Ctr128R) with the
+/// same seed.
+///
+/// Uses `R` rounds of encryption, which must be between 1 and 14.
+/// [`SharedAes256Ctr128`](crate::SharedAes256Ctr128) is the variant with the full 14 rounds.
+pub struct SharedAes256Ctr128R<const R: usize> {
+    cipher: Aes256Cipher<R>,
+    base: u128,
+    limit: u64,
+    offset: BlockCounter,
+}
+
+impl<const R: usize> SharedAes128Ctr64R<R> {
+    /// Creates a new random number generator using the given seed.
+    pub fn from_seed(seed: seeds::Aes128Ctr64Seed) -> Self {
+        let mut key = [0u8; 16];
+        let mut nonce_bytes = [0u8; 8];
+        let mut counter_bytes = [0u8; 8];
+
+        key.copy_from_slice(&seed.as_ref()[..16]);
+        nonce_bytes.copy_from_slice(&seed.as_ref()[16..24]);
+        counter_bytes.copy_from_slice(&seed.as_ref()[24..32]);
+
+        let nonce = u64::from_le_bytes(nonce_bytes);
+        let counter = u64::from_le_bytes(counter_bytes);
+        let base = ((nonce as u128) << 64) | counter as u128;
+        let remaining = (1u128 << 64) - counter as u128;
+
+        let rng = {
+            safely_call! { Self::with_cipher(Aes128Cipher::new_impl(key), base, remaining) }
+        };
+
+        wipe(&mut key);
+        wipe(&mut nonce_bytes);
+        wipe(&mut counter_bytes);
+
+        rng
+    }
+}
+
+impl<const R: usize> SharedAes128Ctr128R<R> {
+    /// Creates a new random number generator using the given seed.
+    pub fn from_seed(seed: seeds::Aes128Ctr128Seed) -> Self {
+        let mut key = [0u8; 16];
+        let mut counter_bytes = [0u8; 16];
+
+        key.copy_from_slice(&seed.as_ref()[..16]);
+        counter_bytes.copy_from_slice(&seed.as_ref()[16..32]);
+
+        let counter = u128::from_le_bytes(counter_bytes);
+        let remaining = (u128::MAX - counter).saturating_add(1);
+
+        let rng = {
+            safely_call! { Self::with_cipher(Aes128Cipher::new_impl(key), counter, remaining) }
+        };
+
+        wipe(&mut key);
+        wipe(&mut counter_bytes);
+
+        rng
+    }
+}
+
+impl<const R: usize> SharedAes256Ctr64R<R> {
+    /// Creates a new random number generator using the given seed.
+    pub fn from_seed(seed: seeds::Aes256Ctr64Seed) -> Self {
+        let mut key = [0u8; 32];
+        let mut nonce_bytes = [0u8; 8];
+        let mut counter_bytes = [0u8; 8];
+
+        key.copy_from_slice(&seed.as_ref()[..32]);
+        nonce_bytes.copy_from_slice(&seed.as_ref()[32..40]);
+        counter_bytes.copy_from_slice(&seed.as_ref()[40..48]);
+
+        let nonce = u64::from_le_bytes(nonce_bytes);
+        let counter = u64::from_le_bytes(counter_bytes);
+        let base = ((nonce as u128) << 64) | counter as u128;
+        let remaining = (1u128 << 64) - counter as u128;
+
+        let rng = {
+            safely_call! { Self::with_cipher(Aes256Cipher::new_impl(key), base, remaining) }
+        };
+
+        wipe(&mut key);
+        wipe(&mut nonce_bytes);
+        wipe(&mut counter_bytes);
+
+        rng
+    }
+}
+
+impl<const R: usize> SharedAes256Ctr128R<R> {
+    /// Creates a new random number generator using the given seed.
+    pub fn from_seed(seed: seeds::Aes256Ctr128Seed) -> Self {
+        let mut key = [0u8; 32];
+        let mut counter_bytes = [0u8; 16];
+
+        key.copy_from_slice(&seed.as_ref()[..32]);
+        counter_bytes.copy_from_slice(&seed.as_ref()[32..48]);
+
+        let counter = u128::from_le_bytes(counter_bytes);
+        let remaining = (u128::MAX - counter).saturating_add(1);
+
+        let rng = {
+            safely_call! { Self::with_cipher(Aes256Cipher::new_impl(key), counter, remaining) }
+        };
+
+        wipe(&mut key);
+        wipe(&mut counter_bytes);
+
+        rng
+    }
+}
+
+/// Implements the methods that all shared generators have in common.
+macro_rules! shared_methods {
+    ($name:ident, $cipher:ident, $seed:ident) => {
+        impl<const R: usize> $name<R> {
+            /// Creates the generator from its cipher, the counter block of its first block and
+            /// the number of blocks until its counter is exhausted.
+            fn with_cipher(cipher: $cipher<R>, base: u128, remaining: u128) -> Self {
+                Self {
+                    cipher,
+                    base,
+                    limit: remaining.min(MAX_BLOCKS as u128) as u64,
+                    offset: BlockCounter::new(),
+                }
+            }
+
+            /// Creates a new random number generator using a seed from the entropy source of
+            /// the OS.
+            #[cfg(feature = "getrandom")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+            pub fn from_entropy() -> Self {
+                Self::from_seed(seeds::$seed::from_entropy())
+            }
+
+            /// Returns `true` if the random number generator is using hardware accelerated AES.
+            pub fn is_hardware_accelerated(&self) -> bool {
+                self.cipher.is_hardware_accelerated_impl()
+            }
+
+            /// Returns the number of blocks that can be generated until the counter is
+            /// exhausted.
+            ///
+            /// A shared generator hands out at most 2^63 blocks, even if its counter would
+            /// allow more.
+            pub fn remaining(&self) -> u128 {
+                (self.limit - self.offset.get().min(self.limit)) as u128
+            }
+
+            /// Reserves `N` blocks and encrypts their counters.
+            #[inline(always)]
+            fn try_reserve_blocks<const N: usize>(&self) -> Result<[u128; N], CounterExhausted> {
+                let offset = self
+                    .offset
+                    .reserve(N as u64, self.limit)
+                    .ok_or(CounterExhausted)?;
+
+                // The counter block can't carry into the nonce, since the offset never
+                // exceeds the number of remaining blocks.
+                let counters: [u128; N] =
+                    core::array::from_fn(|i| self.base + offset as u128 + i as u128);
+
+                Ok(self.encrypt_blocks(counters))
+            }
+
+            #[inline(always)]
+            fn encrypt_blocks<const N: usize>(&self, counters: [u128; N]) -> [u128; N] {
+                safely_call! { self.cipher.encrypt_blocks_impl(counters) }
+            }
+
+            /// Generates the next `u128` value.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the counter is exhausted.
+            #[inline(always)]
+            pub fn next(&self) -> u128 {
+                self.try_next().unwrap_or_else(|error| panic!("{error}"))
+            }
+
+            /// Generates the next `u128` value, or returns [`CounterExhausted`] if the counter
+            /// is exhausted.
+            #[inline(always)]
+            pub fn try_next(&self) -> Result<u128, CounterExhausted> {
+                let [block] = self.try_reserve_blocks::<1>()?;
+                Ok(block)
+            }
+
+            /// Generates the next `N` blocks at once. The blocks are reserved together, so they
+            /// are consecutive even if other threads draw from the generator at the same time.
+            ///
+            /// # Panics
+            ///
+            /// Panics if fewer than `N` blocks are left.
+            #[inline(always)]
+            pub fn next_blocks<const N: usize>(&self) -> [u128; N] {
+                self.try_reserve_blocks()
+                    .unwrap_or_else(|error| panic!("{error}"))
+            }
+
+            /// Generates a random `u32` value. Every call consumes a full block.
+            #[inline(always)]
+            pub fn u32(&self) -> u32 {
+                self.next() as u32
+            }
+
+            /// Generates a random `u64` value. Every call consumes a full block.
+            #[inline(always)]
+            pub fn u64(&self) -> u64 {
+                self.next() as u64
+            }
+
+            /// Fills a mutable `[u8]` slice with random bytes.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the counter is exhausted.
+            pub fn fill_bytes(&self, slice: &mut [u8]) {
+                const U128_BYTES: usize = (u128::BITS / 8) as usize;
+
+                let mut bulk_chunks = slice.chunks_exact_mut(U128_BYTES * BULK_BLOCKS);
+                for bulk_chunk in &mut bulk_chunks {
+                    // Close to the end of the counter, the last blocks are reserved one by one.
+                    let blocks: [u128; BULK_BLOCKS] = self
+                        .try_reserve_blocks()
+                        .unwrap_or_else(|_| core::array::from_fn(|_| self.next()));
+                    for (chunk, block) in bulk_chunk.chunks_exact_mut(U128_BYTES).zip(blocks) {
+                        chunk.copy_from_slice(&block.to_le_bytes())
+                    }
+                }
+
+                for chunk in bulk_chunks.into_remainder().chunks_mut(U128_BYTES) {
+                    let random_bytes = self.next().to_le_bytes();
+                    chunk.copy_from_slice(&random_bytes[..chunk.len()]);
+                }
+            }
+        }
+
+        impl<const R: usize> core::fmt::Debug for $name<R> {
+            fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+                fmt.debug_struct(stringify!($name))
+                    .field("rounds", &R)
+                    .finish_non_exhaustive()
+            }
+        }
+
+        #[cfg(feature = "rand_core")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+        impl<const R: usize> rand_core::RngCore for $name<R> {
+            #[inline(always)]
+            fn next_u32(&mut self) -> u32 {
+                $name::u32(self)
+            }
+
+            #[inline(always)]
+            fn next_u64(&mut self) -> u64 {
+                $name::u64(self)
+            }
+
+            #[inline(always)]
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                $name::fill_bytes(self, dest);
+            }
+
+            #[inline(always)]
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                $name::fill_bytes(self, dest);
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "rand_core")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+        impl<const R: usize> rand_core::RngCore for &$name<R> {
+            #[inline(always)]
+            fn next_u32(&mut self) -> u32 {
+                $name::u32(self)
+            }
+
+            #[inline(always)]
+            fn next_u64(&mut self) -> u64 {
+                $name::u64(self)
+            }
+
+            #[inline(always)]
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                $name::fill_bytes(self, dest);
+            }
+
+            #[inline(always)]
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                $name::fill_bytes(self, dest);
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "rand_core")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+        impl<const R: usize> rand_core::SeedableRng for $name<R> {
+            type Seed = seeds::$seed;
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                $name::from_seed(seed)
+            }
+        }
+    };
+}
+
+shared_methods!(SharedAes128Ctr64R, Aes128Cipher, Aes128Ctr64Seed);
+shared_methods!(SharedAes128Ctr128R, Aes128Cipher, Aes128Ctr128Seed);
+shared_methods!(SharedAes256Ctr64R, Aes256Cipher, Aes256Ctr64Seed);
+shared_methods!(SharedAes256Ctr128R, Aes256Cipher, Aes256Ctr128Seed);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locked_counter_reserve() {
+        let counter = LockedCounter::new();
+
+        assert_eq!(counter.reserve(1, 10), Some(0));
+        assert_eq!(counter.reserve(8, 10), Some(1));
+        assert_eq!(counter.get(), 9);
+        assert_eq!(counter.reserve(2, 10), None);
+        assert_eq!(counter.get(), 9);
+        assert_eq!(counter.reserve(1, 10), Some(9));
+        assert_eq!(counter.get(), 10);
+        assert_eq!(counter.reserve(1, 10), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_locked_counter_threads() {
+        let counter = LockedCounter::new();
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..1000 {
+                        counter.reserve(1, MAX_BLOCKS).unwrap();
+                    }
+                });
+            }
+        });
+
+        assert_eq!(counter.get(), 4000);
+    }
+
+    #[test]
+    #[cfg(target_has_atomic = "64")]
+    fn test_atomic_counter_reserve() {
+        let counter = AtomicCounter::new();
+
+        assert_eq!(counter.reserve(1, 10), Some(0));
+        assert_eq!(counter.reserve(8, 10), Some(1));
+        assert_eq!(counter.get(), 9);
+        assert_eq!(counter.reserve(2, 10), None);
+        assert_eq!(counter.get(), 9);
+        assert_eq!(counter.reserve(1, 10), Some(9));
+        assert_eq!(counter.get(), 10);
+        assert_eq!(counter.reserve(1, 10), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", target_has_atomic = "64"))]
+    fn test_atomic_counter_threads_at_limit() {
+        let counter = AtomicCounter::new();
+        let reserved = std::sync::atomic::AtomicU64::new(0);
+
+        std::thread::scope(|scope| {
+            for n in 1..=4 {
+                let counter = &counter;
+                let reserved = &reserved;
+                scope.spawn(move || {
+                    while counter.reserve(n, 1001).is_some() {
+                        reserved.fetch_add(n, Ordering::Relaxed);
+                    }
+                    while counter.reserve(1, 1001).is_some() {
+                        reserved.fetch_add(1, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(counter.get(), 1001);
+        assert_eq!(reserved.load(Ordering::Relaxed), 1001);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::backend::leftover::Leftover;
+use crate::exhaustion::{Exhaustion, ExhaustionPolicy};
+
+/// The full state of a random number generator.
+///
+/// A state is created by [`Checkpoint::snapshot()`](crate::Checkpoint::snapshot) and contains the
+/// key, the counter (and nonce), the number of rounds, the unused bits of the last generated block
+/// and the exhaustion policy. Restoring it with [`Checkpoint::restore()`](crate::Checkpoint::restore)
+/// results in the identical future output, independent of the backend that took or loads the
+/// snapshot. A state can only be restored into a generator with the same number of rounds.
+///
+/// The state contains the key and should be treated as confidential.
+#[derive(Clone)]
+pub struct State<S> {
+    pub(crate) seed: S,
+    pub(crate) rounds: usize,
+    pub(crate) leftover_block: u128,
+    pub(crate) leftover_bits: u32,
+    pub(crate) policy: ExhaustionPolicy,
+    pub(crate) exhausted: bool,
+}
+
+impl<S> State<S> {
+    pub(crate) fn new(
+        seed: S,
+        rounds: usize,
+        leftover: &Leftover,
+        exhaustion: &Exhaustion,
+    ) -> Self {
+        let (leftover_block, leftover_bits) = leftover.get();
+        Self {
+            seed,
+            rounds,
+            leftover_block,
+            leftover_bits,
+            policy: exhaustion.policy(),
+            exhausted: exhaustion.is_exhausted(),
+        }
+    }
+
+    /// Returns the seed, which contains the key and the counter at the time of the snapshot.
+    pub fn seed(&self) -> &S {
+        &self.seed
+    }
+
+    /// Returns the number of AES rounds of the generator that took the snapshot.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Returns the exhaustion policy at the time of the snapshot.
+    pub fn exhaustion_policy(&self) -> ExhaustionPolicy {
+        self.policy
+    }
+
+    /// Returns an error if the state was taken from a generator with a different number of rounds.
+    pub(crate) fn check_rounds(&self, rounds: usize) -> Result<(), RoundsMismatch> {
+        match self.rounds == rounds {
+            true => Ok(()),
+            false => Err(RoundsMismatch {
+                expected: rounds,
+                found: self.rounds,
+            }),
+        }
+    }
+
+    /// Applies the parts of the state that are not contained in the seed.
+    pub(crate) fn apply(&self, leftover: &Leftover, exhaustion: &Exhaustion) {
+        leftover.set(self.leftover_block, self.leftover_bits);
+        exhaustion.set_policy(self.policy);
+        if self.exhausted {
+            exhaustion.set_exhausted();
+        }
+    }
+}
+
+impl<S> core::fmt::Debug for State<S> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt.debug_struct("State")
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The error that is returned if a [`State`] is restored into a generator with a different number
+/// of rounds than the generator that took the snapshot.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RoundsMismatch {
+    /// The number of rounds of the generator that should be restored.
+    pub expected: usize,
+    /// The number of rounds of the generator that took the snapshot.
+    pub found: usize,
+}
+
+impl core::fmt::Display for RoundsMismatch {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            fmt,
+            "the state was taken from a generator with {} rounds, but the generator uses {} rounds",
+            self.found, self.expected
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RoundsMismatch {}
@@ -4,10 +4,33 @@
 //! This implementation is optimized for optimal inlining and minimal cost when calling, so the
 //! TLS based RNG must be `const` at initialization. The user must thus seed the TLS instance
 //! for **each** thread in which these functions are used, using either the
-//! [`rand_seed_from_entropy()`] or [`rand_seed()`] function.
+//! [`rand_seed_from_entropy()`] or [`rand_seed()`] function. Debug builds panic if an unseeded
+//! TLS instance is used.
+//!
+//! [`rand_set_entropy_source()`] registers an [`EntropySource`] for all threads, that is used
+//! instead of the OS entropy source. This allows seeding from entropy without the `getrandom`
+//! feature.
+//!
+//! The `tls_auto_seed` feature instead seeds the TLS instance of each thread from the OS entropy
+//! source (or the registered entropy source) on its first use, at the cost of an additional check on every call.
+//!
+//! With the opt-in `fork_detection` feature, the TLS instance of a forked child process is
+//! reseeded from the OS entropy source before its next use, so that parent and child don't produce
+//! the same output. The registered entropy source isn't used for this, since another thread of the
+//! parent may have held its lock during the fork. This adds a check of the fork counter to every
+//! call, and the child panics if the OS entropy source fails.
+#[cfg(any(
+    debug_assertions,
+    feature = "tls_auto_seed",
+    feature = "fork_detection"
+))]
+use core::cell::Cell;
 use core::ops::RangeBounds;
+use std::sync::{Mutex, PoisonError};
 
-use crate::Random;
+#[cfg(feature = "fork_detection")]
+use crate::fork::fork_count;
+use crate::{EntropyError, EntropySource, Random};
 
 #[cfg(not(any(
     feature = "tls_aes128_ctr128",
@@ -87,108 +110,284 @@ thread_local! {
     pub(super) static RNG: core::cell::LazyCell<Prng> = core::cell::LazyCell::new(Prng::zeroed);
 }
 
-/// Seeds the thread local instance using the OS entropy source.
+#[cfg(feature = "fork_detection")]
+thread_local! {
+    static FORKS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The entropy source registered with [`rand_set_entropy_source()`].
+static ENTROPY_SOURCE: Mutex<Option<Box<dyn EntropySource + Send>>> = Mutex::new(None);
+
+#[cfg(any(debug_assertions, feature = "tls_auto_seed"))]
+thread_local! {
+    static SEEDED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Calls the given function with the thread local instance, after seeding it on its first use
+/// (with the `tls_auto_seed` feature) or reseeding it if the process was forked since its last
+/// use.
+#[inline(always)]
+fn with_rng<T>(f: impl FnOnce(&Prng) -> T) -> T {
+    RNG.with(|rng| {
+        #[cfg(feature = "tls_auto_seed")]
+        SEEDED.with(|seeded| {
+            if !seeded.get() {
+                mark_seeded();
+                rng.seed(expect_entropy_seed());
+            }
+        });
+
+        #[cfg(all(debug_assertions, not(feature = "tls_auto_seed")))]
+        SEEDED.with(|seeded| {
+            assert!(
+                seeded.get(),
+                "The thread local RNG was used before it was seeded. Call `rand_seed()` or \
+                 `rand_seed_from_entropy()` on every thread or enable the `tls_auto_seed` feature."
+            )
+        });
+
+        #[cfg(feature = "fork_detection")]
+        FORKS.with(|forks| {
+            let count = fork_count();
+            if forks.get() != count {
+                forks.set(count);
+                // The child must not lock the registered entropy source, since the lock may have
+                // been held by a thread of the parent, that doesn't exist in the child.
+                rng.seed(Seed::from_entropy());
+            }
+        });
+
+        f(rng)
+    })
+}
+
+/// Marks the thread local instance as seeded in the current process.
+#[inline(always)]
+fn mark_seeded() {
+    #[cfg(any(debug_assertions, feature = "tls_auto_seed"))]
+    SEEDED.with(|seeded| seeded.set(true));
+    #[cfg(feature = "fork_detection")]
+    FORKS.with(|forks| forks.set(fork_count()));
+}
+
+/// Creates a seed from the registered entropy source or from the OS entropy source.
+fn entropy_seed() -> Result<Seed, EntropyError> {
+    let mut source = ENTROPY_SOURCE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    let mut seed = Seed::default();
+    match source.as_mut() {
+        Some(source) => source.fill(seed.as_mut())?,
+        #[cfg(feature = "getrandom")]
+        None => crate::OsEntropy.fill(seed.as_mut())?,
+        #[cfg(not(feature = "getrandom"))]
+        None => return Err(EntropyError::Unavailable),
+    }
+    Ok(seed)
+}
+
+/// Creates a seed like [`entropy_seed()`] and panics if the entropy source fails.
+fn expect_entropy_seed() -> Seed {
+    entropy_seed().unwrap_or_else(|error| panic!("Can't seed the thread local RNG: {error}"))
+}
+
+/// Registers the entropy source that is used to seed the thread local instance of all threads,
+/// instead of the OS entropy source.
+///
+/// # Example
+///
+/// ```
+/// use rand_aes::tls::*;
+/// use rand_aes::EntropyError;
+///
+/// rand_set_entropy_source(|bytes: &mut [u8]| -> Result<(), EntropyError> {
+///     // Read the bytes from the hardware TRNG.
+///     bytes.fill(0x42);
+///     Ok(())
+/// });
+///
+/// rand_seed_from_entropy();
+/// let _ = rand_u64();
+/// ```
+pub fn rand_set_entropy_source(source: impl EntropySource + Send + 'static) {
+    *ENTROPY_SOURCE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some(Box::new(source));
+}
+
+/// Seeds the thread local instance using the registered entropy source, or the OS entropy source
+/// if none was registered.
 ///
 /// The TLS uses the [`crate::Aes128Ctr64`] PRN internally.
-#[cfg(feature = "getrandom")]
-#[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+///
+/// # Panics
+///
+/// Panics if the entropy source fails, or if no entropy source was registered and the
+/// `getrandom` feature is disabled. [`rand_try_seed_from_entropy()`] returns an error instead.
 pub fn rand_seed_from_entropy() {
-    RNG.with(|rng| rng.seed_from_entropy())
+    rand_seed(expect_entropy_seed());
+}
+
+/// Seeds the thread local instance like [`rand_seed_from_entropy()`], but returns an error if the
+/// entropy source fails. The instance keeps its current state in that case.
+pub fn rand_try_seed_from_entropy() -> Result<(), EntropyError> {
+    rand_seed(entropy_seed()?);
+    Ok(())
 }
 
 /// Seeds the thread local instance with the given seed.
 pub fn rand_seed(seed: Seed) {
+    mark_seeded();
     RNG.with(|rng| rng.seed(seed))
 }
 
+/// A handle to the thread local instance, that implements the `RngCore` trait of rand_core 0.6
+/// and 0.9, so that it can be used with the `rand` crates. Wrapped in a [`Reader`](crate::Reader),
+/// it streams random bytes through `std::io::Read`.
+///
+/// The handle has no state. Every call uses the instance of the calling thread, which must be
+/// seeded like for the other functions of this module.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TlsRng;
+
+#[cfg(feature = "rand_core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+impl rand_core::RngCore for TlsRng {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        rand_u32()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        rand_u64()
+    }
+
+    #[inline(always)]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_fill_bytes(dest)
+    }
+
+    #[inline(always)]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        rand_fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand_core_0_9")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core_0_9")))]
+impl rand_core_0_9::RngCore for TlsRng {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        rand_u32()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        rand_u64()
+    }
+
+    #[inline(always)]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_fill_bytes(dest)
+    }
+}
+
 /// Generates a random `u8` value.
 pub fn rand_u8() -> u8 {
-    RNG.with(|rng| rng.u8())
+    with_rng(|rng| rng.u8())
 }
 
 /// Generates a random `u16` value.
 pub fn rand_u16() -> u16 {
-    RNG.with(|rng| rng.u16())
+    with_rng(|rng| rng.u16())
 }
 
 /// Generates a random `u32` value.
 pub fn rand_u32() -> u32 {
-    RNG.with(|rng| rng.u32())
+    with_rng(|rng| rng.u32())
 }
 
 /// Generates a random `u64` value.
 pub fn rand_u64() -> u64 {
-    RNG.with(|rng| rng.u64())
+    with_rng(|rng| rng.u64())
 }
 
 /// Generates a random `u128` value.
 pub fn rand_u128() -> u128 {
-    RNG.with(|rng| rng.u128())
+    with_rng(|rng| rng.u128())
 }
 
 /// Generates a random `usize` value.
 pub fn rand_usize() -> usize {
-    RNG.with(|rng| rng.usize())
+    with_rng(|rng| rng.usize())
 }
 
 /// Generates a random `i8` value.
 pub fn rand_i8() -> i8 {
-    RNG.with(|rng| rng.i8())
+    with_rng(|rng| rng.i8())
 }
 
 /// Generates a random `i16` value.
 pub fn rand_i16() -> i16 {
-    RNG.with(|rng| rng.i16())
+    with_rng(|rng| rng.i16())
 }
 
 /// Generates a random `i32` value.
 pub fn rand_i32() -> i32 {
-    RNG.with(|rng| rng.i32())
+    with_rng(|rng| rng.i32())
 }
 
 /// Generates a random `i64` value.
 pub fn rand_i64() -> i64 {
-    RNG.with(|rng| rng.i64())
+    with_rng(|rng| rng.i64())
 }
 
 /// Generates a random `i128` value.
 pub fn rand_i128() -> i128 {
-    RNG.with(|rng| rng.i128())
+    with_rng(|rng| rng.i128())
 }
 
 /// Generates a random `isize` value.
 pub fn rand_isize() -> isize {
-    RNG.with(|rng| rng.isize())
+    with_rng(|rng| rng.isize())
 }
 
 /// Generates a random `f32` value in the range of 0..1.
 pub fn rand_f32() -> f32 {
-    RNG.with(|rng| rng.f32())
+    with_rng(|rng| rng.f32())
 }
 
 /// Generates a random `f64` value in the range  of 0..1.
 pub fn rand_f64() -> f64 {
-    RNG.with(|rng| rng.f64())
+    with_rng(|rng| rng.f64())
 }
 
 /// Generates a random `bool` value.
 pub fn rand_bool() -> bool {
-    RNG.with(|rng| rng.bool())
+    with_rng(|rng| rng.bool())
 }
 
 /// Randomly shuffles a slice.
 pub fn rand_shuffle<T>(slice: &mut [T]) {
-    RNG.with(|rng| rng.shuffle(slice))
+    with_rng(|rng| rng.shuffle(slice))
 }
 
 /// Fills a mutable `[u8]` slice with random bytes.
 pub fn rand_fill_bytes(slice: &mut [u8]) {
-    RNG.with(|rng| rng.fill_bytes(slice))
+    with_rng(|rng| rng.fill_bytes(slice))
+}
+
+/// Fills the bytes with whole bulks of blocks for the [`Reader`](crate::Reader).
+pub(crate) fn fill_blocks(slice: &mut [u8]) {
+    with_rng(|rng| crate::io::fill_blocks(rng, slice))
 }
 
 /// Generates an array filled with random bytes.
 pub fn rand_byte_array<const N: usize>() -> [u8; N] {
-    RNG.with(|rng| rng.byte_array::<N>())
+    with_rng(|rng| rng.byte_array::<N>())
 }
 
 /// Generates a random `u8` value in the range of 0..n.
@@ -196,7 +395,7 @@ pub fn rand_byte_array<const N: usize>() -> [u8; N] {
 /// # Notice
 /// This has a very slight bias. Use [`rand_range_u8()`] instead for no bias.
 pub fn rand_mod_u8(n: u8) -> u8 {
-    RNG.with(|rng| rng.mod_u8(n))
+    with_rng(|rng| rng.mod_u8(n))
 }
 
 /// Generates a random `u16` value in the range of 0..n.
@@ -204,7 +403,7 @@ pub fn rand_mod_u8(n: u8) -> u8 {
 /// # Notice
 /// This has a very slight bias. Use [`rand_range_u16()`] instead for no bias.
 pub fn rand_mod_u16(n: u16) -> u16 {
-    RNG.with(|rng| rng.mod_u16(n))
+    with_rng(|rng| rng.mod_u16(n))
 }
 
 /// Generates a random `u32` value in the range of 0..n.
@@ -212,7 +411,7 @@ pub fn rand_mod_u16(n: u16) -> u16 {
 /// # Notice
 /// This has a very slight bias. Use [`rand_range_u32()`] instead for no bias.
 pub fn rand_mod_u32(n: u32) -> u32 {
-    RNG.with(|rng| rng.mod_u32(n))
+    with_rng(|rng| rng.mod_u32(n))
 }
 
 /// Generates a random `u64` value in the range of 0..n.
@@ -220,7 +419,7 @@ pub fn rand_mod_u32(n: u32) -> u32 {
 /// # Notice
 /// This has a very slight bias. Use [`rand_range_u64()`] instead for no bias.
 pub fn rand_mod_u64(n: u64) -> u64 {
-    RNG.with(|rng| rng.mod_u64(n))
+    with_rng(|rng| rng.mod_u64(n))
 }
 
 /// Generates a random `usize` value in the range of 0..n.
@@ -228,55 +427,55 @@ pub fn rand_mod_u64(n: u64) -> u64 {
 /// # Notice
 /// This has a very slight bias. Use [`rand_range_usize()`] instead for no bias.
 pub fn rand_mod_usize(n: usize) -> usize {
-    RNG.with(|rng| rng.mod_usize(n))
+    with_rng(|rng| rng.mod_usize(n))
 }
 
 /// Generates a random `u8` value in the given range.
 pub fn rand_range_u8<T: RangeBounds<u8>>(range: T) -> u8 {
-    RNG.with(|rng| rng.range_u8(range))
+    with_rng(|rng| rng.range_u8(range))
 }
 
 /// Generates a random `u16` value in the given range.
 pub fn rand_range_u16<T: RangeBounds<u16>>(range: T) -> u16 {
-    RNG.with(|rng| rng.range_u16(range))
+    with_rng(|rng| rng.range_u16(range))
 }
 
 /// Generates a random `u32` value in the given range.
 pub fn rand_range_u32<T: RangeBounds<u32>>(range: T) -> u32 {
-    RNG.with(|rng| rng.range_u32(range))
+    with_rng(|rng| rng.range_u32(range))
 }
 
 /// Generates a random `u64` value in the given range.
 pub fn rand_range_u64<T: RangeBounds<u64>>(range: T) -> u64 {
-    RNG.with(|rng| rng.range_u64(range))
+    with_rng(|rng| rng.range_u64(range))
 }
 
 /// Generates a random `usize` value in the given range.
 pub fn rand_range_usize<T: RangeBounds<usize>>(range: T) -> usize {
-    RNG.with(|rng| rng.range_usize(range))
+    with_rng(|rng| rng.range_usize(range))
 }
 
 /// Generates a random `i8` value in the given range.
 pub fn rand_range_i8<T: RangeBounds<i8>>(range: T) -> i8 {
-    RNG.with(|rng| rng.range_i8(range))
+    with_rng(|rng| rng.range_i8(range))
 }
 
 /// Generates a random `i16` value in the given range.
 pub fn rand_range_i16<T: RangeBounds<i16>>(range: T) -> i16 {
-    RNG.with(|rng| rng.range_i16(range))
+    with_rng(|rng| rng.range_i16(range))
 }
 
 /// Generates a random `i32` value in the given range.
 pub fn rand_range_i32<T: RangeBounds<i32>>(range: T) -> i32 {
-    RNG.with(|rng| rng.range_i32(range))
+    with_rng(|rng| rng.range_i32(range))
 }
 
 /// Generates a random `i64` value in the given range.
 pub fn rand_range_i64<T: RangeBounds<i64>>(range: T) -> i64 {
-    RNG.with(|rng| rng.range_i64(range))
+    with_rng(|rng| rng.range_i64(range))
 }
 
 /// Generates a random `isize` value in the given range.
 pub fn rand_range_isize<T: RangeBounds<isize>>(range: T) -> isize {
-    RNG.with(|rng| rng.range_isize(range))
+    with_rng(|rng| rng.range_isize(range))
 }
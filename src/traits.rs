@@ -1,3 +1,6 @@
+use crate::entropy::{EntropyError, EntropySource};
+use crate::exhaustion::{CounterExhausted, ExhaustionPolicy};
+use crate::state::{RoundsMismatch, State};
 use core::ops::{Bound, RangeBounds};
 
 macro_rules! range_integer {
@@ -38,19 +41,19 @@ macro_rules! range_integer {
             }
 
             if low == $target::MIN && high == $target::MAX {
-                self.next() as $target
+                self.$base() as $target
             } else {
                 let range = high.wrapping_sub(low).wrapping_add(1) as $base;
 
                 // As described in "Fast Random Integer Generation in an Interval" by Daniel Lemire.
                 // <https://arxiv.org/abs/1805.10941>
-                let mut x = self.next() as $base;
+                let mut x = self.$base();
                 let mut result = (x as $tmp).wrapping_mul(range as $tmp);
                 let mut leftover = result as $base;
                 if leftover < range {
                     let threshold = range.wrapping_neg() % range;
                     while leftover < threshold {
-                        x = self.next() as $base;
+                        x = self.$base();
                         result = (x as $tmp).wrapping_mul(range as $tmp);
                         leftover = result as $base;
                     }
@@ -114,7 +117,150 @@ pub trait Jump {
     fn long_jump(&self) -> Self;
 }
 
+/// Provides stream splitting functionality for the PRNG with a 64-bit counter.
+///
+/// The 64-bit counter PRNG use the upper 64-bit of their counter block as a nonce, which selects
+/// the stream. Since the counter only wraps around inside the lower 64-bit, two PRNG that use the
+/// same key but a different stream never generate the same counter block, so their output doesn't
+/// overlap. Each stream has a period of 2^64.
+///
+/// [`ExhaustionPolicy::RollOver`](crate::ExhaustionPolicy::RollOver) breaks this: an exhausted
+/// PRNG continues with the next stream, which is the stream that [`Stream::next_stream()`] hands
+/// out next. Streams that are used by different workers must not use this policy.
+pub trait Stream {
+    /// Returns the stream of this RNG.
+    fn stream(&self) -> u64;
+
+    /// Returns a clone of this RNG that uses the given stream. The counter is kept.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use rand_aes::*;
+    ///
+    /// let rng0 = Aes128Ctr64::from_entropy();
+    /// let rng1 = rng0.with_stream(42); // Returns a clone that uses stream 42
+    ///
+    /// assert_eq!(rng1.stream(), 42);
+    /// assert_eq!(rng1.counter(), rng0.counter());
+    /// ```
+    fn with_stream(&self, stream: u64) -> Self;
+
+    /// Returns a clone of this RNG and advances the stream of itself by one.
+    ///
+    /// This can be used to create 2^64 non-overlapping streams for parallel computations. The
+    /// stream wraps around after 2^64 calls.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand_aes::*;
+    ///
+    /// let rng0 = Aes128Ctr64::from_seed(seeds::Aes128Ctr64Seed::default());
+    /// let rng1 = rng0.next_stream(); // Returns a clone and advances rng0 to the next stream
+    /// let rng2 = rng0.next_stream(); // Returns a clone and advances rng0 to the next stream
+    ///
+    /// assert_eq!(rng1.stream(), 0);
+    /// assert_eq!(rng2.stream(), 1);
+    /// assert_eq!(rng0.stream(), 2);
+    /// ```
+    fn next_stream(&self) -> Self;
+}
+
+/// Provides control over the position of the counter of a PRNG and what happens once it is
+/// exhausted.
+pub trait Seek: Random {
+    /// Sets the counter of the PRNG, so that the next call to [`Random::next()`] returns the block
+    /// of the given counter value. The unused bits of the last generated block are discarded.
+    ///
+    /// The 64-bit counter variants only change the counter and keep their nonce.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand_aes::*;
+    ///
+    /// let rng0 = Aes128Ctr128::from_seed(seeds::Aes128Ctr128Seed::default());
+    /// let rng1 = Aes128Ctr128::from_seed(seeds::Aes128Ctr128Seed::default());
+    ///
+    /// for _ in 0..1000 {
+    ///     rng0.next();
+    /// }
+    /// rng1.set_counter(1000);
+    ///
+    /// assert_eq!(rng0.next(), rng1.next());
+    /// ```
+    fn set_counter(&self, counter: Self::Counter);
+
+    /// Advances the counter of the PRNG by `n` blocks, equivalent to calling [`Random::next()`]
+    /// `n` times. The unused bits of the last generated block are discarded.
+    ///
+    /// The counter wraps around on overflow.
+    fn advance(&self, n: Self::Counter);
+
+    /// Rewinds the counter of the PRNG by `n` blocks, so that the last `n` blocks are generated
+    /// again. The unused bits of the last generated block are discarded.
+    ///
+    /// The counter wraps around on underflow.
+    fn rewind(&self, n: Self::Counter);
+
+    /// Returns the number of blocks that can be generated until the counter is exhausted.
+    ///
+    /// The 128-bit counter variants saturate at `u128::MAX`. See [`ExhaustionPolicy`] for what
+    /// happens once the counter is exhausted.
+    fn remaining(&self) -> u128;
+
+    /// Returns the policy that is applied once the counter is exhausted.
+    fn exhaustion_policy(&self) -> ExhaustionPolicy;
+
+    /// Sets the policy that is applied once the counter is exhausted. The default policy is
+    /// [`ExhaustionPolicy::Panic`].
+    fn set_exhaustion_policy(&self, policy: ExhaustionPolicy);
+}
+
+/// Provides snapshots of the full state of a PRNG.
+pub trait Checkpoint: Random {
+    /// Returns the full state of the PRNG, which can be used to resume it later with
+    /// [`Checkpoint::restore()`]. The state should be treated as confidential.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand_aes::*;
+    ///
+    /// let rng0 = Aes128Ctr64::from_seed(seeds::Aes128Ctr64Seed::default());
+    /// let _ = rng0.u32();
+    /// let state = rng0.snapshot();
+    ///
+    /// let rng1 = Aes128Ctr64::from_seed(seeds::Aes128Ctr64Seed::default());
+    /// rng1.restore(&state).unwrap();
+    ///
+    /// assert_eq!(rng0.u32(), rng1.u32());
+    /// assert_eq!(rng0.next(), rng1.next());
+    /// ```
+    fn snapshot(&self) -> State<Self::Seed>;
+
+    /// Restores the full state of the PRNG from a state created by [`Checkpoint::snapshot()`].
+    ///
+    /// Returns [`RoundsMismatch`] and keeps the current state if the snapshot was taken from a
+    /// PRNG with a different number of rounds, since restoring it would result in unrelated output.
+    fn restore(&self, state: &State<Self::Seed>) -> Result<(), RoundsMismatch>;
+}
+
 /// Provides common random number generation functionality.
+///
+/// # Output Order
+///
+/// The generators of this crate create one 128-bit block per counter value. [`Random::next()`],
+/// [`Random::u128()`] and [`Random::i128()`] always return a new block. The narrow methods
+/// [`Random::u8()`], [`Random::u16()`], [`Random::u32()`], [`Random::u64()`] and [`Random::bool()`]
+/// (and all methods that are based on them) keep the unused bits of their last block and hand them
+/// out from the least significant bit to the most significant bit. A call to `u32()` for example
+/// takes the next 32 unused bits and a call to `bool()` takes the next single bit. If fewer bits
+/// are left than requested, the remaining bits are discarded and a new block is generated. Methods
+/// that return a new block don't discard the unused bits.
+///
+/// A block can so serve four `u32()`, two `u64()` or 128 `bool()` calls.
 pub trait Random {
     type Seed;
     type Counter;
@@ -135,6 +281,80 @@ pub trait Random {
     /// Seeds the random number generator from the entropy source of the OS.
     fn seed_from_entropy(&self);
 
+    /// Creates a new random number generator using a seed from the given entropy source.
+    ///
+    /// Unlike [`Random::from_entropy()`], this doesn't require the `getrandom` feature and
+    /// returns an error if the entropy source fails.
+    fn from_entropy_source(source: &mut impl EntropySource) -> Result<Self, EntropyError>
+    where
+        Self: Sized,
+        Self::Seed: AsMut<[u8]> + Default,
+    {
+        let mut seed = Self::Seed::default();
+        source.fill(seed.as_mut())?;
+        Ok(Self::from_seed(seed))
+    }
+
+    /// Creates a new random number generator from a `u64` value.
+    ///
+    /// The value is expanded into the full seed with the [seed derivation](crate::seeds#derivation),
+    /// using the context `b"rand_aes::Random::from_u64"` and the little endian bytes of the value
+    /// as input. The expansion is part of the stable API, so a value always results in the same
+    /// stream in every version of this crate. The seed has at most 64 bits of entropy and must not
+    /// be used for anything security related.
+    ///
+    /// The generators of this crate use the name of their seed type as the label of the derivation.
+    /// The default implementation doesn't know it and uses the label `b"Seed"` instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand_aes::*;
+    ///
+    /// let rng0 = Aes128Ctr64::from_u64(42);
+    /// let rng1 = Aes128Ctr64::from_seed(seeds::Aes128Ctr64Seed::derive(
+    ///     b"rand_aes::Random::from_u64",
+    ///     &42u64.to_le_bytes(),
+    /// ));
+    ///
+    /// assert_eq!(rng0.next(), rng1.next());
+    /// ```
+    #[cfg(not(feature = "verification"))]
+    fn from_u64(state: u64) -> Self
+    where
+        Self: Sized,
+        Self::Seed: AsMut<[u8]> + Default,
+    {
+        let mut seed = Self::Seed::default();
+        crate::kdf::derive_into(
+            crate::kdf::SEED_LABEL,
+            crate::kdf::FROM_U64_CONTEXT,
+            &state.to_le_bytes(),
+            seed.as_mut(),
+        );
+        Self::from_seed(seed)
+    }
+
+    #[cfg(feature = "verification")]
+    fn from_u64(state: u64) -> Self
+    where
+        Self: Sized,
+        Self::Seed: AsMut<[u8]> + Default;
+
+    /// Creates a new random number generator with a seed from the given random number generator.
+    ///
+    /// The seed is filled with [`Random::fill_bytes()`] of the given generator. The expansion is
+    /// part of the stable API.
+    fn from_random(rng: &impl Random) -> Self
+    where
+        Self: Sized,
+        Self::Seed: AsMut<[u8]> + Default,
+    {
+        let mut seed = Self::Seed::default();
+        rng.fill_bytes(seed.as_mut());
+        Self::from_seed(seed)
+    }
+
     /// Returns `true` if the random number generator is using hardware accelerated AES.
     fn is_hardware_accelerated(&self) -> bool;
 
@@ -142,8 +362,30 @@ pub trait Random {
     fn counter(&self) -> Self::Counter;
 
     /// Generates the next `u128` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter is exhausted and the exhaustion policy is
+    /// [`ExhaustionPolicy::Panic`].
     fn next(&self) -> u128;
 
+    /// Generates the next `u128` value, or returns [`CounterExhausted`] if the counter is exhausted
+    /// and the exhaustion policy is [`ExhaustionPolicy::Panic`].
+    ///
+    /// The default implementation calls [`Random::next()`] and never fails.
+    fn try_next(&self) -> Result<u128, CounterExhausted> {
+        Ok(self.next())
+    }
+
+    /// Generates the next `N` blocks at once.
+    ///
+    /// Returns the same values as calling [`Random::next()`] `N` times, but allows hardware
+    /// backends to encrypt the independent counters interleaved, which keeps the AES units of
+    /// the CPU busy.
+    fn next_blocks<const N: usize>(&self) -> [u128; N] {
+        core::array::from_fn(|_| self.next())
+    }
+
     /// Generates a random `u8` value.
     fn u8(&self) -> u8 {
         self.next() as u8
@@ -169,29 +411,42 @@ pub trait Random {
         self.next()
     }
 
+    #[cfg(target_pointer_width = "16")]
     /// Generates a random `usize` value.
     fn usize(&self) -> usize {
-        self.next() as usize
+        self.u16() as usize
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    /// Generates a random `usize` value.
+    fn usize(&self) -> usize {
+        self.u32() as usize
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    /// Generates a random `usize` value.
+    fn usize(&self) -> usize {
+        self.u64() as usize
     }
 
     /// Generates a random `i8` value.
     fn i8(&self) -> i8 {
-        self.next() as i8
+        self.u8() as i8
     }
 
     /// Generates a random `i16` value.
     fn i16(&self) -> i16 {
-        self.next() as i16
+        self.u16() as i16
     }
 
     /// Generates a random `i32` value.
     fn i32(&self) -> i32 {
-        self.next() as i32
+        self.u32() as i32
     }
 
     /// Generates a random `i64` value.
     fn i64(&self) -> i64 {
-        self.next() as i64
+        self.u64() as i64
     }
 
     /// Generates a random `i128` value.
@@ -201,7 +456,7 @@ pub trait Random {
 
     /// Generates a random `isize` value.
     fn isize(&self) -> isize {
-        self.next() as isize
+        self.usize() as isize
     }
 
     /// Generates a random `bool` value.
@@ -229,8 +484,17 @@ pub trait Random {
     /// Fills a mutable `[u8]` slice with random bytes.
     fn fill_bytes(&self, slice: &mut [u8]) {
         const U128_BYTES: usize = (u128::BITS / 8) as usize;
+        const BULK_BLOCKS: usize = 8;
+
+        let mut bulk_chunks = slice.chunks_exact_mut(U128_BYTES * BULK_BLOCKS);
+        for bulk_chunk in &mut bulk_chunks {
+            let blocks: [u128; BULK_BLOCKS] = self.next_blocks();
+            for (chunk, block) in bulk_chunk.chunks_exact_mut(U128_BYTES).zip(blocks) {
+                chunk.copy_from_slice(&block.to_le_bytes())
+            }
+        }
 
-        let mut chunks = slice.chunks_exact_mut(U128_BYTES);
+        let mut chunks = bulk_chunks.into_remainder().chunks_exact_mut(U128_BYTES);
         for chunk in &mut chunks {
             let random_bytes: [u8; U128_BYTES] = self.u128().to_le_bytes();
             chunk.copy_from_slice(&random_bytes)
@@ -257,9 +521,7 @@ pub trait Random {
     /// # Notice
     /// This has a very slight bias. Use [`Random::range_u8()`] instead for no bias.
     fn mod_u8(&self, n: u8) -> u8 {
-        (self.next() as u8 as u16)
-            .wrapping_mul(n as u16)
-            .wrapping_shr(8) as u8
+        (self.u8() as u16).wrapping_mul(n as u16).wrapping_shr(8) as u8
     }
 
     /// Generates a random u16 value in the range of 0..n.
@@ -267,9 +529,7 @@ pub trait Random {
     /// # Notice
     /// This has a very slight bias. Use [`Random::range_u16()`] instead for no bias.
     fn mod_u16(&self, n: u16) -> u16 {
-        (self.next() as u16 as u32)
-            .wrapping_mul(n as u32)
-            .wrapping_shr(16) as u16
+        (self.u16() as u32).wrapping_mul(n as u32).wrapping_shr(16) as u16
     }
 
     /// Generates a random u32 value in the range of 0..n.
@@ -277,9 +537,7 @@ pub trait Random {
     /// # Notice
     /// This has a very slight bias. Use [`Random::range_u32()`] instead for no bias.
     fn mod_u32(&self, n: u32) -> u32 {
-        (self.next() as u32 as u64)
-            .wrapping_mul(n as u64)
-            .wrapping_shr(32) as u32
+        (self.u32() as u64).wrapping_mul(n as u64).wrapping_shr(32) as u32
     }
 
     /// Generates a random u64 value in the range of 0..n.
@@ -287,7 +545,7 @@ pub trait Random {
     /// # Notice
     /// This has a very slight bias. Use [`Random::range_u64()`] instead for no bias.
     fn mod_u64(&self, n: u64) -> u64 {
-        (self.next() as u64 as u128)
+        (self.u64() as u128)
             .wrapping_mul(n as u128)
             .wrapping_shr(64) as u64
     }
@@ -298,9 +556,7 @@ pub trait Random {
     /// # Notice
     /// This has a very slight bias. Use [`Random::range_usize()`] instead for no bias.
     fn mod_usize(&self, n: usize) -> usize {
-        (self.next() as u16 as u32)
-            .wrapping_mul(n as u32)
-            .wrapping_shr(16) as usize
+        (self.u16() as u32).wrapping_mul(n as u32).wrapping_shr(16) as usize
     }
 
     #[cfg(target_pointer_width = "32")]
@@ -309,9 +565,7 @@ pub trait Random {
     /// # Notice
     /// This has a very slight bias. Use [`Random::range_usize()`] instead for no bias.
     fn mod_usize(&self, n: usize) -> usize {
-        (self.next() as u32 as u64)
-            .wrapping_mul(n as u64)
-            .wrapping_shr(32) as usize
+        (self.u32() as u64).wrapping_mul(n as u64).wrapping_shr(32) as usize
     }
 
     #[cfg(target_pointer_width = "64")]
@@ -320,7 +574,7 @@ pub trait Random {
     /// # Notice
     /// This has a very slight bias. Use [`Random::range_usize()`] instead for no bias.
     fn mod_usize(&self, n: usize) -> usize {
-        (self.next() as u64 as u128)
+        (self.u64() as u128)
             .wrapping_mul(n as u128)
             .wrapping_shr(64) as usize
     }
@@ -1,30 +1,42 @@
 //! Test that verify that the software backend and the hardware backends produce the same random numbers for a given seed.
 
-use crate::constants::{AES128_KEY_SIZE, AES256_KEY_SIZE, AES_BLOCK_SIZE};
+use crate::constants::{
+    AES128_KEY_SIZE, AES128_ROUNDS, AES256_KEY_SIZE, AES256_ROUNDS, AES_BLOCK_SIZE,
+};
 
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
 use crate::backend::x86::{
-    Aes128Ctr128 as Aes128Ctr128Hardware, Aes128Ctr64 as Aes128Ctr64Hardware,
-    Aes256Ctr128 as Aes256Ctr128Hardware, Aes256Ctr64 as Aes256Ctr64Hardware,
+    Aes128Cipher as Aes128CipherHardware, Aes128Ctr128R as Aes128Ctr128Hardware,
+    Aes128Ctr64R as Aes128Ctr64Hardware, Aes256Cipher as Aes256CipherHardware,
+    Aes256Ctr128R as Aes256Ctr128Hardware, Aes256Ctr64R as Aes256Ctr64Hardware,
 };
 
 #[cfg(all(target_arch = "riscv64", feature = "experimental_riscv"))]
 use crate::backend::riscv64::{
-    Aes128Ctr128 as Aes128Ctr128Hardware, Aes128Ctr64 as Aes128Ctr64Hardware,
-    Aes256Ctr128 as Aes256Ctr128Hardware, Aes256Ctr64 as Aes256Ctr64Hardware,
+    Aes128Cipher as Aes128CipherHardware, Aes128Ctr128R as Aes128Ctr128Hardware,
+    Aes128Ctr64R as Aes128Ctr64Hardware, Aes256Cipher as Aes256CipherHardware,
+    Aes256Ctr128R as Aes256Ctr128Hardware, Aes256Ctr64R as Aes256Ctr64Hardware,
 };
 
 #[cfg(target_arch = "aarch64")]
 use crate::backend::aarch64::{
-    Aes128Ctr128 as Aes128Ctr128Hardware, Aes128Ctr64 as Aes128Ctr64Hardware,
-    Aes256Ctr128 as Aes256Ctr128Hardware, Aes256Ctr64 as Aes256Ctr64Hardware,
+    Aes128Cipher as Aes128CipherHardware, Aes128Ctr128R as Aes128Ctr128Hardware,
+    Aes128Ctr64R as Aes128Ctr64Hardware, Aes256Cipher as Aes256CipherHardware,
+    Aes256Ctr128R as Aes256Ctr128Hardware, Aes256Ctr64R as Aes256Ctr64Hardware,
 };
 
 use crate::backend::soft::{
-    Aes128Ctr128 as Aes128Ctr128Software, Aes128Ctr64 as Aes128Ctr64Software,
-    Aes256Ctr128 as Aes256Ctr128Software, Aes256Ctr64 as Aes256Ctr64Software,
+    Aes128Cipher as Aes128CipherSoftware, Aes128Ctr128R as Aes128Ctr128Software,
+    Aes128Ctr64R as Aes128Ctr64Software, Aes256Cipher as Aes256CipherSoftware,
+    Aes256Ctr128R as Aes256Ctr128Software, Aes256Ctr64R as Aes256Ctr64Software,
 };
 
+macro_rules! verify_rounds {
+    ($verify:ident, $($rounds:literal),+) => {
+        $($verify::<$rounds>();)+
+    };
+}
+
 /// Runs the verification testsuite. Will panic once it finds an error.
 ///
 /// This function is unsafe, since we don't properly gate the function behind compiletime or
@@ -35,7 +47,7 @@ pub unsafe fn run_verification() {
     println!("Verifying Aes128Ctr64");
     for i in 0..u8::MAX {
         for j in 0..u8::MAX {
-            verify_aes128_ctr64([i; AES128_KEY_SIZE], [j; AES_BLOCK_SIZE]);
+            verify_aes128_ctr64::<AES128_ROUNDS>([i; AES128_KEY_SIZE], [j; AES_BLOCK_SIZE]);
         }
     }
     println!("Finished Aes128Ctr64");
@@ -43,7 +55,7 @@ pub unsafe fn run_verification() {
     println!("Verifying Aes128Ctr128");
     for i in 0..u8::MAX {
         for j in 0..u8::MAX {
-            verify_aes128_ctr128([i; AES128_KEY_SIZE], [j; AES_BLOCK_SIZE]);
+            verify_aes128_ctr128::<AES128_ROUNDS>([i; AES128_KEY_SIZE], [j; AES_BLOCK_SIZE]);
         }
     }
     println!("Finished Aes128Ctr128");
@@ -51,7 +63,7 @@ pub unsafe fn run_verification() {
     println!("Verifying Aes256Ctr64");
     for i in 0..u8::MAX {
         for j in 0..u8::MAX {
-            verify_aes256_ctr64([i; AES256_KEY_SIZE], [j; AES_BLOCK_SIZE]);
+            verify_aes256_ctr64::<AES256_ROUNDS>([i; AES256_KEY_SIZE], [j; AES_BLOCK_SIZE]);
         }
     }
     println!("Finished Aes256Ctr64");
@@ -59,62 +71,148 @@ pub unsafe fn run_verification() {
     println!("Verifying Aes256Ctr128");
     for i in 0..u8::MAX {
         for j in 0..u8::MAX {
-            verify_aes256_ctr128([i; AES256_KEY_SIZE], [j; AES_BLOCK_SIZE]);
+            verify_aes256_ctr128::<AES256_ROUNDS>([i; AES256_KEY_SIZE], [j; AES_BLOCK_SIZE]);
         }
     }
     println!("Finished Aes256Ctr128");
+
+    println!("Verifying reduced rounds");
+    verify_rounds!(verify_aes128_rounds, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+    verify_rounds!(
+        verify_aes256_rounds,
+        1,
+        2,
+        3,
+        4,
+        5,
+        6,
+        7,
+        8,
+        9,
+        10,
+        11,
+        12,
+        13
+    );
+    println!("Finished reduced rounds");
+}
+
+fn verify_aes128_rounds<const R: usize>() {
+    for i in 0..16 {
+        for j in 0..16 {
+            verify_aes128_ctr64::<R>([i; AES128_KEY_SIZE], [j; AES_BLOCK_SIZE]);
+            verify_aes128_ctr128::<R>([i; AES128_KEY_SIZE], [j; AES_BLOCK_SIZE]);
+        }
+    }
 }
 
-fn verify_aes128_ctr64(key: [u8; AES128_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) {
+fn verify_aes256_rounds<const R: usize>() {
+    for i in 0..16 {
+        for j in 0..16 {
+            verify_aes256_ctr64::<R>([i; AES256_KEY_SIZE], [j; AES_BLOCK_SIZE]);
+            verify_aes256_ctr128::<R>([i; AES256_KEY_SIZE], [j; AES_BLOCK_SIZE]);
+        }
+    }
+}
+
+fn verify_aes128_ctr64<const R: usize>(key: [u8; AES128_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) {
     let mut ctr = [0u8; 8];
     let mut nonce = [0u8; 8];
     ctr.copy_from_slice(&iv[0..8]);
     nonce.copy_from_slice(&iv[8..16]);
 
-    let mut software = Aes128Ctr64Software::from_seed_impl(key, nonce, ctr);
-    let hardware = unsafe { Aes128Ctr64Hardware::from_seed_impl(key, nonce, ctr) };
+    let mut software = Aes128Ctr64Software::<R>::from_seed_impl(key, nonce, ctr);
+    let hardware = unsafe { Aes128Ctr64Hardware::<R>::from_seed_impl(key, nonce, ctr) };
 
     for _ in 0..u8::MAX {
         assert_eq!(software.next_impl().to_le_bytes(), unsafe {
             hardware.next_impl().to_le_bytes()
         });
     }
+
+    let blocks: [u128; 8] = unsafe { hardware.next_blocks_impl() };
+    for block in blocks {
+        assert_eq!(software.next_impl().to_le_bytes(), block.to_le_bytes());
+    }
+
+    assert_eq!(software.key_impl(), hardware.key_impl());
+    assert_eq!(software.counter_impl(), hardware.counter_impl());
 }
 
-fn verify_aes128_ctr128(key: [u8; AES128_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) {
-    let mut software = Aes128Ctr128Software::from_seed_impl(key, iv);
-    let hardware = unsafe { Aes128Ctr128Hardware::from_seed_impl(key, iv) };
+fn verify_aes128_ctr128<const R: usize>(key: [u8; AES128_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) {
+    let mut software = Aes128Ctr128Software::<R>::from_seed_impl(key, iv);
+    let hardware = unsafe { Aes128Ctr128Hardware::<R>::from_seed_impl(key, iv) };
 
     for _ in 0..u8::MAX {
         assert_eq!(software.next_impl().to_le_bytes(), unsafe {
             hardware.next_impl().to_le_bytes()
         });
     }
+
+    let blocks: [u128; 8] = unsafe { hardware.next_blocks_impl() };
+    for block in blocks {
+        assert_eq!(software.next_impl().to_le_bytes(), block.to_le_bytes());
+    }
+
+    assert_eq!(software.key_impl(), hardware.key_impl());
+    assert_eq!(software.counter_impl(), hardware.counter_impl());
+
+    let software_cipher = Aes128CipherSoftware::<R>::new_impl(key);
+    let hardware_cipher = unsafe { Aes128CipherHardware::<R>::new_impl(key) };
+    let counters: [u128; 8] = core::array::from_fn(|i| u128::from_le_bytes(iv) ^ i as u128);
+
+    let software_blocks = software_cipher.encrypt_blocks_impl(counters);
+    let hardware_blocks = unsafe { hardware_cipher.encrypt_blocks_impl(counters) };
+    assert_eq!(software_blocks, hardware_blocks);
 }
 
-fn verify_aes256_ctr64(key: [u8; AES256_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) {
+fn verify_aes256_ctr64<const R: usize>(key: [u8; AES256_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) {
     let mut ctr = [0u8; 8];
     let mut nonce = [0u8; 8];
     ctr.copy_from_slice(&iv[0..8]);
     nonce.copy_from_slice(&iv[8..16]);
 
-    let mut software = Aes256Ctr64Software::from_seed_impl(key, nonce, ctr);
-    let hardware = unsafe { Aes256Ctr64Hardware::from_seed_impl(key, nonce, ctr) };
+    let mut software = Aes256Ctr64Software::<R>::from_seed_impl(key, nonce, ctr);
+    let hardware = unsafe { Aes256Ctr64Hardware::<R>::from_seed_impl(key, nonce, ctr) };
 
     for _ in 0..u8::MAX {
         assert_eq!(software.next_impl().to_le_bytes(), unsafe {
             hardware.next_impl().to_le_bytes()
         });
     }
+
+    let blocks: [u128; 8] = unsafe { hardware.next_blocks_impl() };
+    for block in blocks {
+        assert_eq!(software.next_impl().to_le_bytes(), block.to_le_bytes());
+    }
+
+    assert_eq!(software.key_impl(), hardware.key_impl());
+    assert_eq!(software.counter_impl(), hardware.counter_impl());
 }
 
-fn verify_aes256_ctr128(key: [u8; AES256_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) {
-    let mut software = Aes256Ctr128Software::from_seed_impl(key, iv);
-    let hardware = unsafe { Aes256Ctr128Hardware::from_seed_impl(key, iv) };
+fn verify_aes256_ctr128<const R: usize>(key: [u8; AES256_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) {
+    let mut software = Aes256Ctr128Software::<R>::from_seed_impl(key, iv);
+    let hardware = unsafe { Aes256Ctr128Hardware::<R>::from_seed_impl(key, iv) };
 
     for _ in 0..u8::MAX {
         assert_eq!(software.next_impl().to_le_bytes(), unsafe {
             hardware.next_impl().to_le_bytes()
         });
     }
+
+    let blocks: [u128; 8] = unsafe { hardware.next_blocks_impl() };
+    for block in blocks {
+        assert_eq!(software.next_impl().to_le_bytes(), block.to_le_bytes());
+    }
+
+    assert_eq!(software.key_impl(), hardware.key_impl());
+    assert_eq!(software.counter_impl(), hardware.counter_impl());
+
+    let software_cipher = Aes256CipherSoftware::<R>::new_impl(key);
+    let hardware_cipher = unsafe { Aes256CipherHardware::<R>::new_impl(key) };
+    let counters: [u128; 8] = core::array::from_fn(|i| u128::from_le_bytes(iv) ^ i as u128);
+
+    let software_blocks = software_cipher.encrypt_blocks_impl(counters);
+    let hardware_blocks = unsafe { hardware_cipher.encrypt_blocks_impl(counters) };
+    assert_eq!(software_blocks, hardware_blocks);
 }
@@ -0,0 +1,109 @@
+#![cfg(any(feature = "rand_core", feature = "rand_core_0_9"))]
+
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_block_rng {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                $seed::from(core::array::from_fn(|i| i as u8))
+            }
+
+            #[cfg(feature = "rand_core")]
+            mod v0_6 {
+                use super::*;
+                use rand_core::block::{BlockRng, BlockRngCore};
+                use rand_core::RngCore;
+
+                #[test]
+                fn test_generate() {
+                    let mut prng0 = $prng::from_seed(seed());
+                    let prng1 = $prng::from_seed(seed());
+
+                    let mut results = Default::default();
+                    prng0.generate(&mut results);
+
+                    for words in results.chunks_exact(4) {
+                        let block = prng1.next();
+                        assert_eq!(words[0], block as u32);
+                        assert_eq!(words[1], (block >> 32) as u32);
+                        assert_eq!(words[2], (block >> 64) as u32);
+                        assert_eq!(words[3], (block >> 96) as u32);
+                    }
+                    assert_eq!(prng0.next(), prng1.next());
+                }
+
+                #[test]
+                fn test_block_rng_words() {
+                    let mut rng = BlockRng::new($prng::from_seed(seed()));
+                    let prng = $prng::from_seed(seed());
+
+                    // More than one buffer of words.
+                    for _ in 0..20 {
+                        let block = prng.next();
+                        assert_eq!(rng.next_u32(), block as u32);
+                        assert_eq!(rng.next_u32(), (block >> 32) as u32);
+                        assert_eq!(rng.next_u64(), (block >> 64) as u64);
+                    }
+                }
+
+                #[test]
+                fn test_block_rng_fill_bytes() {
+                    let mut rng = BlockRng::new($prng::from_seed(seed()));
+                    let prng = $prng::from_seed(seed());
+
+                    let mut bytes0 = [0u8; 333];
+                    let mut bytes1 = [0u8; 333];
+                    rng.fill_bytes(&mut bytes0);
+                    prng.fill_bytes(&mut bytes1);
+                    assert_eq!(bytes0, bytes1);
+                }
+            }
+
+            #[cfg(feature = "rand_core_0_9")]
+            mod v0_9 {
+                use super::*;
+                use rand_core_0_9::block::{BlockRng, BlockRngCore};
+                use rand_core_0_9::{RngCore, SeedableRng};
+
+                #[test]
+                fn test_generate() {
+                    let mut prng0 = <$prng as Random>::from_seed(seed());
+                    let prng1 = <$prng as Random>::from_seed(seed());
+
+                    let mut results = Default::default();
+                    prng0.generate(&mut results);
+
+                    for words in results.chunks_exact(4) {
+                        let block = prng1.next();
+                        assert_eq!(words[0], block as u32);
+                        assert_eq!(words[3], (block >> 96) as u32);
+                    }
+                }
+
+                #[test]
+                fn test_block_rng_fill_bytes() {
+                    let mut rng = BlockRng::<$prng>::from_seed(seed());
+                    let prng = <$prng as Random>::from_seed(seed());
+
+                    assert_eq!(rng.next_u32(), prng.next() as u32);
+
+                    let mut bytes0 = [0u8; 333];
+                    let mut bytes1 = [0u8; 333];
+                    rng.fill_bytes(&mut bytes0);
+                    prng.fill_bytes(&mut bytes1);
+                    // The first word was consumed, the rest of its block follows.
+                    assert_eq!(bytes0[12..], bytes1[..321]);
+                }
+            }
+        }
+    };
+}
+
+test_block_rng!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed);
+test_block_rng!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed);
+test_block_rng!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed);
+test_block_rng!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed);
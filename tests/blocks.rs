@@ -0,0 +1,63 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_blocks {
+    ($name:ident, $prng:ident, $seed:ident, $seed_size:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn test_next_blocks() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let blocks: [u128; 1] = prng.next_blocks();
+                assert_eq!(blocks, [reference.next()]);
+
+                let blocks: [u128; 8] = prng.next_blocks();
+                assert_eq!(blocks, core::array::from_fn(|_| reference.next()));
+
+                let blocks: [u128; 13] = prng.next_blocks();
+                assert_eq!(blocks, core::array::from_fn(|_| reference.next()));
+
+                assert_eq!(prng.counter(), reference.counter());
+                assert_eq!(prng.next(), reference.next());
+            }
+
+            #[test]
+            fn test_next_blocks_counter_overflow() {
+                let prng = $prng::from_seed($seed::from([255; $seed_size]));
+                let reference = $prng::from_seed($seed::from([255; $seed_size]));
+                prng.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+                reference.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+
+                let blocks: [u128; 8] = prng.next_blocks();
+                assert_eq!(blocks, core::array::from_fn(|_| reference.next()));
+
+                assert_eq!(prng.counter(), reference.counter());
+                assert_eq!(prng.next(), reference.next());
+            }
+
+            #[test]
+            fn test_fill_bytes() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let mut bytes = [0u8; 200];
+                prng.fill_bytes(&mut bytes);
+
+                for chunk in bytes.chunks(16) {
+                    let block = reference.next().to_le_bytes();
+                    assert_eq!(chunk, &block[..chunk.len()]);
+                }
+
+                assert_eq!(prng.next(), reference.next());
+            }
+        }
+    };
+}
+
+test_blocks!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed, 32);
+test_blocks!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed, 32);
+test_blocks!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed, 48);
+test_blocks!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed, 48);
@@ -0,0 +1,77 @@
+use core::cell::Cell;
+
+use rand_aes::*;
+
+/// A generator outside of this crate, that only implements the required methods of `Random`.
+struct Counting(Cell<u128>);
+
+impl Random for Counting {
+    type Seed = [u8; 16];
+    type Counter = u128;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self(Cell::new(u128::from_le_bytes(seed)))
+    }
+
+    fn seed(&self, seed: Self::Seed) {
+        self.0.set(u128::from_le_bytes(seed));
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn from_entropy() -> Self {
+        Self::from_seed([0; 16])
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn seed_from_entropy(&self) {}
+
+    fn is_hardware_accelerated(&self) -> bool {
+        false
+    }
+
+    fn counter(&self) -> Self::Counter {
+        self.0.get()
+    }
+
+    fn next(&self) -> u128 {
+        let value = self.0.get();
+        self.0.set(value.wrapping_add(1));
+        value
+    }
+}
+
+#[test]
+fn test_default_methods() {
+    let rng = Counting::from_seed(1u128.to_le_bytes());
+
+    assert_eq!(rng.try_next(), Ok(1));
+    assert_eq!(rng.u32(), 2);
+    assert_eq!(rng.counter(), 3);
+}
+
+#[test]
+fn test_default_from_entropy_source() {
+    let mut source = |bytes: &mut [u8]| -> Result<(), EntropyError> {
+        bytes.fill(0);
+        bytes[0] = 5;
+        Ok(())
+    };
+
+    let rng = Counting::from_entropy_source(&mut source).unwrap();
+    assert_eq!(rng.next(), 5);
+}
+
+#[test]
+fn test_default_from_random() {
+    let rng0 = Aes128Ctr64::from_seed(seeds::Aes128Ctr64Seed::default());
+    let rng1 = Aes128Ctr64::from_seed(seeds::Aes128Ctr64Seed::default());
+
+    let rng = Counting::from_random(&rng0);
+    assert_eq!(rng.next(), rng1.next());
+}
+
+#[test]
+fn test_default_from_u64() {
+    let rng = Counting::from_u64(42);
+    assert_eq!(rng.next(), 45580150856994048789337071254820925265);
+}
@@ -0,0 +1,88 @@
+use hex_literal::hex;
+use rand_aes::seeds::*;
+
+// The expected seeds were cross-checked with the CMAC and the SP 800-108 KBKDF in counter mode of
+// the Python `cryptography` package. They must never change.
+macro_rules! test_derive {
+    ($name:ident, $seed:ident, $expected:expr, $expected_empty:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn test_known_answer() {
+                let seed = $seed::derive(b"experiment", b"run-42/agent-7");
+                assert_eq!(seed.as_ref(), $expected);
+
+                let seed = $seed::derive(b"", b"");
+                assert_eq!(seed.as_ref(), $expected_empty);
+            }
+
+            #[test]
+            fn test_reproducible() {
+                let seed0 = $seed::derive(b"experiment", b"run-42/agent-7");
+                let seed1 = $seed::derive(b"experiment", b"run-42/agent-7");
+                assert_eq!(seed0.as_ref(), seed1.as_ref());
+            }
+
+            #[test]
+            fn test_separation() {
+                let seed = $seed::derive(b"experiment", b"run-42/agent-7");
+
+                let other_input = $seed::derive(b"experiment", b"run-42/agent-8");
+                assert_ne!(seed.as_ref(), other_input.as_ref());
+
+                let other_context = $seed::derive(b"experiment2", b"run-42/agent-7");
+                assert_ne!(seed.as_ref(), other_context.as_ref());
+
+                // The context and the input are not simply concatenated.
+                let shifted = $seed::derive(b"experimentrun-42/", b"agent-7");
+                assert_ne!(seed.as_ref(), shifted.as_ref());
+            }
+
+            #[test]
+            fn test_long_input() {
+                let input = [0x5a; 1000];
+                let seed0 = $seed::derive(b"context", &input);
+                let seed1 = $seed::derive(b"context", &input[..999]);
+                assert_ne!(seed0.as_ref(), seed1.as_ref());
+            }
+        }
+    };
+}
+
+test_derive!(
+    aes128_ctr64,
+    Aes128Ctr64Seed,
+    hex!("56f27da85f89ee7585c0423eeafe280cc1cfb73c037c08a3ace22e6eb6c89788"),
+    hex!("8b06421afa3c3bb41824d6c3b785d93ed50d660002de2befe0f4b8d3a5c0a997")
+);
+test_derive!(
+    aes128_ctr128,
+    Aes128Ctr128Seed,
+    hex!("b70d7d197418bbb340c3f334a56d0f89adfb3ddc243c434f365a38073a086311"),
+    hex!("152110dbe727b76a2c69476cd8026cfa8e323a97e1613702dd857cb4a0aa8821")
+);
+test_derive!(
+    aes256_ctr64,
+    Aes256Ctr64Seed,
+    hex!(
+        "be7a127d71efab1c063154ebb04a7e27322260ea466ce31404e023549b8a1ef8"
+        "0b1524326c127bbb579f8045dd88919a"
+    ),
+    hex!(
+        "385fc48d69cdf0b62741f495fd60197a22e50f5285f3e89704e20704444f4d91"
+        "8ef0370566969f3d27b07b2a5aae8c97"
+    )
+);
+test_derive!(
+    aes256_ctr128,
+    Aes256Ctr128Seed,
+    hex!(
+        "164690d94c500016a17bd79d97a28439d95ff70faa90b5dda01075d0b5b9dc6a"
+        "6bed5cf7bd6f9ed21eb440353e5f1192"
+    ),
+    hex!(
+        "b890ef36204414bf8f890da038a96e3edd074f337f586b53134a0f13a277fe56"
+        "d2bd831ee1f1b1a4cbc9ff6f4e4e9a19"
+    )
+);
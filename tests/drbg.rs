@@ -0,0 +1,381 @@
+use hex_literal::hex;
+use rand_aes::*;
+
+// NIST CAVP CTR_DRBG test vectors (drbgtestvectors.zip, drbgvectors_no_reseed/CTR_DRBG.rsp),
+// [AES-128 use df], COUNT = 0.
+#[test]
+fn test_aes128_cavp_no_reseed() {
+    let mut drbg = Aes128CtrDrbg::instantiate(
+        &hex!("890eb067acf7382eff80b0c73bc872c6"),
+        &hex!("aad471ef3ef1d203"),
+        &[],
+    )
+    .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate(&mut output, &[]).unwrap();
+    drbg.generate(&mut output, &[]).unwrap();
+
+    assert_eq!(
+        output,
+        hex!("a5514ed7095f64f3d0d3a5760394ab42062f373a25072a6ea6bcfd8489e94af6cf18659fea22ed1ca0a9e33f718b115ee536b12809c31b72b08ddd8be1910fa3")
+    );
+}
+
+// NIST CAVP CTR_DRBG test vectors (drbgtestvectors.zip, drbgvectors_pr_false/CTR_DRBG.rsp),
+// [AES-128 use df], [PredictionResistance = False], COUNT = 0.
+#[test]
+fn test_aes128_cavp_pr_false() {
+    let mut drbg = Aes128CtrDrbg::instantiate(
+        &hex!("0f65da13dca407999d4773c2b4a11d85"),
+        &hex!("5209e5b4ed82a234"),
+        &[],
+    )
+    .unwrap();
+
+    drbg.reseed(&hex!("1dea0a12c52bf64339dd291c80d8ca89"), &[])
+        .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate(&mut output, &[]).unwrap();
+    drbg.generate(&mut output, &[]).unwrap();
+
+    assert_eq!(
+        output,
+        hex!("2859cc468a76b08661ffd23b28547ffd0997ad526a0f51261b99ed3a37bd407bf418dbe6c6c3e26ed0ddefcb7474d899bd99f3655427519fc5b4057bcaf306d4")
+    );
+}
+
+// The same CAVP vector, with the reseed folded into a prediction resistance request as defined by
+// NIST SP 800-90A, section 9.3.1.
+#[test]
+fn test_aes128_cavp_pr_false_with_prediction_resistance() {
+    let mut drbg = Aes128CtrDrbg::instantiate(
+        &hex!("0f65da13dca407999d4773c2b4a11d85"),
+        &hex!("5209e5b4ed82a234"),
+        &[],
+    )
+    .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate_with_prediction_resistance(
+        &hex!("1dea0a12c52bf64339dd291c80d8ca89"),
+        &mut output,
+        &[],
+    )
+    .unwrap();
+    drbg.generate(&mut output, &[]).unwrap();
+
+    assert_eq!(
+        output,
+        hex!("2859cc468a76b08661ffd23b28547ffd0997ad526a0f51261b99ed3a37bd407bf418dbe6c6c3e26ed0ddefcb7474d899bd99f3655427519fc5b4057bcaf306d4")
+    );
+}
+
+// The following vectors cover the inputs, that are empty in the CAVP vectors above. They are
+// known answers recorded from this implementation and guard against regressions only.
+#[test]
+fn test_aes128_additional_input() {
+    let mut drbg = Aes128CtrDrbg::instantiate(
+        &hex!("13fc8799ab641b4a0a2ef0e0d0a62f2d"),
+        &hex!("cc0cf9b37466b261"),
+        &hex!("b7445e72f7e1b526e7a76f37eb7ec469"),
+    )
+    .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate(&mut output, &hex!("72dd3de461ef50a01e604c15f0127939"))
+        .unwrap();
+    drbg.generate(&mut output, &hex!("e9b50eb9ae586852fcab11451b0b82ea"))
+        .unwrap();
+
+    assert_eq!(
+        output,
+        hex!("18709cff586e12a70cecc75a314d9bdab8f4750cde237217f832e5363e837f8ec60a727ba6caa278ca4c0e0acfd4e9565cfc33c2daf50230492601c862a77962")
+    );
+}
+
+#[test]
+fn test_aes128_reseed() {
+    let mut drbg = Aes128CtrDrbg::instantiate(
+        &hex!("21788b71e429afc5277d93ad464ce465"),
+        &hex!("7f95964283b9b8f9"),
+        &hex!("6651eb617da89fe89678c86de718c8fc"),
+    )
+    .unwrap();
+
+    drbg.reseed(
+        &hex!("f884e2f5f001775f21fa9c1242edd73d"),
+        &hex!("86e5c40de958a18aab4e4940711fc2e1"),
+    )
+    .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate(&mut output, &hex!("6608f6a54f8621ac633668851ff334d4"))
+        .unwrap();
+    drbg.generate(&mut output, &hex!("21322b1e17d6b5ad0407f4d199c78684"))
+        .unwrap();
+
+    assert_eq!(
+        output,
+        hex!("4a4e6dc115c88192fbc30c4755345b5b8cc05f2b5b930ba89fdcbf4a7d7931e4a0f3b02bcb65702429d6f4817d5bcf823411b8d496f57c6d67dd9683a5740597")
+    );
+}
+
+#[test]
+fn test_aes128_prediction_resistance() {
+    let mut drbg = Aes128CtrDrbg::instantiate(
+        &hex!("a8160edf0efeafdad644b5376c8fc683"),
+        &hex!("40f589c8304e2507"),
+        &hex!("0ab8b6bc22ba7c16d07f4c80a5b539a0"),
+    )
+    .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate_with_prediction_resistance(
+        &hex!("8d66b6c11bdd0656ae0c66614fafd31f"),
+        &mut output,
+        &hex!("04f92075302a7fe044b4903a614f9a38"),
+    )
+    .unwrap();
+    drbg.generate_with_prediction_resistance(
+        &hex!("cb1cf1844e301855d8576c6ae884119e"),
+        &mut output,
+        &hex!("bdd6b8ae8271fe007800925ec309e316"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        hex!("53c7ac93eb7e0f298a7dfc1e0392a62669c74ec26d07eeae998ccbc2ce4784c91a556a3d08be2abb4ed30d3b4d2634d4079769b23c7687c13699adf3f08d1f60")
+    );
+}
+
+#[test]
+fn test_aes128_unaligned_lengths() {
+    let mut drbg = Aes128CtrDrbg::instantiate(
+        &hex!("2974017da3a4220e03c8a393bf178b26750ce70eac"),
+        &hex!("3bb41e647fd193217a914b0556"),
+        &hex!("cddba884d8"),
+    )
+    .unwrap();
+
+    let mut output = [0u8; 100];
+    drbg.generate(
+        &mut output,
+        &hex!("69836fe3af82098dedd64bdd48842856e745a6b77370ce95b155f2b77646427853"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        hex!("2379fedff0d8e09f5263eb5b557a92803b448e4258b4c46debe84e03e7e2ce6c8be3d84ed07ea9b61a55286c74bfc939a0a0fdd4ac997fc8e26feebeac736cbb6b8d64e77ff2cefa9ef986682fe6edf11f26197faf0e83eadef1e5be0737712c51567faf")
+    );
+}
+
+#[test]
+fn test_aes128_errors() {
+    assert_eq!(
+        Aes128CtrDrbg::instantiate(&[0; 15], &[0; 8], &[]).unwrap_err(),
+        DrbgError::EntropyTooShort
+    );
+    assert_eq!(
+        Aes128CtrDrbg::instantiate(&[0; 16], &[0; 7], &[]).unwrap_err(),
+        DrbgError::NonceTooShort
+    );
+
+    let mut drbg = Aes128CtrDrbg::instantiate(&[0; 16], &[0; 8], &[]).unwrap();
+    assert_eq!(drbg.reseed(&[0; 15], &[]), Err(DrbgError::EntropyTooShort));
+
+    let mut output = vec![0u8; (1 << 16) + 1];
+    assert_eq!(
+        drbg.generate(&mut output, &[]),
+        Err(DrbgError::RequestTooLarge)
+    );
+    assert_eq!(drbg.generate(&mut output[..1 << 16], &[]), Ok(()));
+    assert_eq!(drbg.reseed_counter(), 2);
+}
+
+// NIST CAVP CTR_DRBG test vectors (drbgtestvectors.zip, drbgvectors_no_reseed/CTR_DRBG.rsp),
+// [AES-256 use df], COUNT = 0.
+#[test]
+fn test_aes256_cavp_no_reseed() {
+    let mut drbg = Aes256CtrDrbg::instantiate(
+        &hex!("36401940fa8b1fba91a1661f211d78a0b9389a74e5bccfece8d766af1a6d3b14"),
+        &hex!("496f25b0f1301b4f501be30380a137eb"),
+        &[],
+    )
+    .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate(&mut output, &[]).unwrap();
+    drbg.generate(&mut output, &[]).unwrap();
+
+    assert_eq!(
+        output,
+        hex!("5862eb38bd558dd978a696e6df164782ddd887e7e9a6c9f3f1fbafb78941b535a64912dfd224c6dc7454e5250b3d97165e16260c2faf1cc7735cb75fb4f07e1d")
+    );
+}
+
+// A prediction resistance request must be equal to a reseed with the additional input, followed
+// by a generate request without it, as defined by NIST SP 800-90A, section 9.3.1.
+#[test]
+fn test_aes256_prediction_resistance_is_reseed_and_generate() {
+    let entropy = hex!("36401940fa8b1fba91a1661f211d78a0b9389a74e5bccfece8d766af1a6d3b14");
+    let nonce = hex!("496f25b0f1301b4f501be30380a137eb");
+    let reseed_entropy = hex!("979c4d19e515b17511ee18cb5948fe56adbe850f6778ceaa271d4ac6db867445");
+    let additional_input = hex!("6175f5f021fbcf12ab0010fe1737a1faf3337e63fc1258faee71a320aff5fc1c");
+
+    let mut expected_drbg = Aes256CtrDrbg::instantiate(&entropy, &nonce, &[]).unwrap();
+    let mut drbg = Aes256CtrDrbg::instantiate(&entropy, &nonce, &[]).unwrap();
+
+    let mut expected = [0u8; 64];
+    expected_drbg
+        .reseed(&reseed_entropy, &additional_input)
+        .unwrap();
+    expected_drbg.generate(&mut expected, &[]).unwrap();
+    expected_drbg.generate(&mut expected, &[]).unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate_with_prediction_resistance(&reseed_entropy, &mut output, &additional_input)
+        .unwrap();
+    drbg.generate(&mut output, &[]).unwrap();
+
+    assert_eq!(output, expected);
+}
+
+// The following vectors cover the inputs, that are empty in the CAVP vector above. They are
+// known answers recorded from this implementation and guard against regressions only.
+#[test]
+fn test_aes256_additional_input() {
+    let mut drbg = Aes256CtrDrbg::instantiate(
+        &hex!("4af124d25d862bc4b6b2fcbb68874cd45fd2e1afed8bd13d8d3cf6fa46008b92"),
+        &hex!("6493e77d92eba255830aaf0c66f702a2"),
+        &hex!("21ae39d2da815a617e9097e48a02540093e52a969399f8ee21e3d33cd25b1e01"),
+    )
+    .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate(
+        &mut output,
+        &hex!("3594ff9d0db76358da084c627aa66e2a39a00b13afa7c517e24fbd1ff1bddda6"),
+    )
+    .unwrap();
+    drbg.generate(
+        &mut output,
+        &hex!("6b3f9a13c1eeb98b9135e13094c705edd167f26aca74d6a3efc556c6f736ddd8"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        hex!("b6c402d8afded9e6e212e734cef455d6b19bce0245104fa456d09126368145404aa54ae34d6ab01908c2c9ce4f71a2ec308695f9e0d026d92c38e33d61bb6fbc")
+    );
+}
+
+#[test]
+fn test_aes256_reseed() {
+    let mut drbg = Aes256CtrDrbg::instantiate(
+        &hex!("8ccbbd4b59d38651d7bce4fe822abd578a81c4fa457f894816d3a900ceba957e"),
+        &hex!("9868956124f00804519470522693d462"),
+        &hex!("a2eb93c592d6cc1e3828b7c0a30be2c71fbed76ba60578ed8d835f127a7fdbb8"),
+    )
+    .unwrap();
+
+    drbg.reseed(
+        &hex!("11a1c2d2cef5c8740aa94cf83884f884459e05603c06d7cda314fa07f58d78b2"),
+        &hex!("2c1496d07f5c7499293fdd1738b59d1adce402e5ad4b391cb7a61079f9f2d036"),
+    )
+    .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate(
+        &mut output,
+        &hex!("3b981431a49c8768c7239586a5850afddbadf8aa60c88111209e20b61f2f1e35"),
+    )
+    .unwrap();
+    drbg.generate(
+        &mut output,
+        &hex!("5a746816a0a5397d627e3dd993157be101186c325ee9b6a1cbec526bd3dfae7f"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        hex!("4f269820e8f1e685f155c622b1304fc08fcca8283a8bd810acc461e2c5f7f7e498b7a083892fb1d066a58dd5d00cdb5d8aefcd79174372a2c8dcabf39906b0f9")
+    );
+}
+
+#[test]
+fn test_aes256_prediction_resistance() {
+    let mut drbg = Aes256CtrDrbg::instantiate(
+        &hex!("ffd55a20fcc089405c2127693348312aef8b1b44b96160fd621dae6108bd6084"),
+        &hex!("e831f6b67614c9c2695f548e0a39d848"),
+        &hex!("8ca28c19dede5c2bc002a37b255f52c40594f470a954f5fd80cc2002ef550742"),
+    )
+    .unwrap();
+
+    let mut output = [0u8; 64];
+    drbg.generate_with_prediction_resistance(
+        &hex!("979c4d19e515b17511ee18cb5948fe56adbe850f6778ceaa271d4ac6db867445"),
+        &mut output,
+        &hex!("6175f5f021fbcf12ab0010fe1737a1faf3337e63fc1258faee71a320aff5fc1c"),
+    )
+    .unwrap();
+    drbg.generate_with_prediction_resistance(
+        &hex!("745e0573302a7e5afb4c4a03458abe3cda88aae230b49bb01b770eddfe78a42b"),
+        &mut output,
+        &hex!("ae27873cefc38f815eadf9b926e619573dad0162c6851ac633840197e1feb198"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        hex!("c0ebb8aefde5e6641db18d603bf797426bc847016cea86aa84fe8bd648236baf8c085be4af9db6b594fdfc130308fbc3d5ac44d4cc909fa8d2d4ca5856386b0c")
+    );
+}
+
+#[test]
+fn test_aes256_unaligned_lengths() {
+    let mut drbg = Aes256CtrDrbg::instantiate(
+        &hex!("28739a2c11405fda5f34a5931db07c78cf59b7a02f9ae6e5a4ed2063d06407ab720c1c20c3"),
+        &hex!("dadcdf5abb8bfa8b2bf1e487b0094ba1b591100689"),
+        &hex!("0879aa2a36"),
+    )
+    .unwrap();
+
+    let mut output = [0u8; 100];
+    drbg.generate(
+        &mut output,
+        &hex!("b2d47eedcd7a06406d657f78fca5d92bbbe4b024dbdd965601e0deaede26a0f53a"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        hex!("8b76870af759f6d8145ebf3302053d3ee07f370c96194a311a62283b10cb622b2be639656e03edd80f4c3eb5fcf7d0364a481cbda43859596ba8047c2b29a1df69195d24b0ccb5a8e7a7c546c3dfa425bddfc10b527bbcccb0af248b3d153915e3af57a9")
+    );
+}
+
+#[test]
+fn test_aes256_errors() {
+    assert_eq!(
+        Aes256CtrDrbg::instantiate(&[0; 31], &[0; 16], &[]).unwrap_err(),
+        DrbgError::EntropyTooShort
+    );
+    assert_eq!(
+        Aes256CtrDrbg::instantiate(&[0; 32], &[0; 15], &[]).unwrap_err(),
+        DrbgError::NonceTooShort
+    );
+
+    let mut drbg = Aes256CtrDrbg::instantiate(&[0; 32], &[0; 16], &[]).unwrap();
+    assert_eq!(drbg.reseed(&[0; 31], &[]), Err(DrbgError::EntropyTooShort));
+
+    let mut output = vec![0u8; (1 << 16) + 1];
+    assert_eq!(
+        drbg.generate(&mut output, &[]),
+        Err(DrbgError::RequestTooLarge)
+    );
+    assert_eq!(drbg.generate(&mut output[..1 << 16], &[]), Ok(()));
+    assert_eq!(drbg.reseed_counter(), 2);
+}
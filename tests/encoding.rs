@@ -0,0 +1,176 @@
+use rand_aes::seeds::*;
+
+macro_rules! test_encoding {
+    ($name:ident, $seed:ident, $size:literal, $base64:literal) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                $seed::from(core::array::from_fn(|i| i as u8))
+            }
+
+            fn hex() -> String {
+                (0..$size).map(|i| format!("{i:02x}")).collect()
+            }
+
+            #[test]
+            fn test_hex() {
+                assert_eq!(seed().reveal().to_string(), hex());
+
+                let parsed: $seed = hex().parse().unwrap();
+                assert_eq!(parsed.as_ref(), seed().as_ref());
+
+                let parsed = $seed::from_hex(&hex().to_uppercase()).unwrap();
+                assert_eq!(parsed.as_ref(), seed().as_ref());
+            }
+
+            #[test]
+            fn test_base64() {
+                assert_eq!(seed().base64().to_string(), $base64);
+
+                let parsed = $seed::from_base64($base64).unwrap();
+                assert_eq!(parsed.as_ref(), seed().as_ref());
+
+                let seed = $seed::from([0xFF; $size]);
+                let parsed = $seed::from_base64(&seed.base64().to_string()).unwrap();
+                assert_eq!(parsed.as_ref(), seed.as_ref());
+            }
+
+            #[test]
+            fn test_debug_is_redacted() {
+                let debug = format!("{:?}", seed());
+                assert_eq!(debug, concat!(stringify!($seed), "(<redacted>)"));
+
+                let revealed = format!("{:?}", seed().reveal());
+                assert_eq!(revealed, format!("{}({})", stringify!($seed), hex()));
+                assert_eq!(seed().reveal().to_string(), hex());
+            }
+
+            #[test]
+            fn test_invalid_length() {
+                let error = $seed::from_hex(&hex()[1..]).unwrap_err();
+                assert_eq!(
+                    error,
+                    ParseSeedError::InvalidLength {
+                        expected: $size * 2,
+                        found: $size * 2 - 1,
+                    }
+                );
+                assert_eq!(
+                    error.to_string(),
+                    format!(
+                        "invalid seed length: expected {} characters, found {}",
+                        $size * 2,
+                        $size * 2 - 1
+                    )
+                );
+
+                let error = $seed::from_base64(&$base64[1..]).unwrap_err();
+                assert_eq!(
+                    error,
+                    ParseSeedError::InvalidLength {
+                        expected: $base64.len(),
+                        found: $base64.len() - 1,
+                    }
+                );
+            }
+
+            #[test]
+            fn test_invalid_character() {
+                let mut string = hex();
+                string.replace_range(5..6, "g");
+                let error = $seed::from_hex(&string).unwrap_err();
+                assert_eq!(
+                    error,
+                    ParseSeedError::InvalidCharacter {
+                        character: 'g',
+                        position: 5,
+                    }
+                );
+                assert_eq!(
+                    error.to_string(),
+                    "invalid character 'g' at position 5 of the seed"
+                );
+
+                // A multi-byte character that keeps the number of characters.
+                let mut string = hex();
+                string.replace_range(3..4, "ä");
+                assert_eq!(
+                    $seed::from_hex(&string).unwrap_err(),
+                    ParseSeedError::InvalidCharacter {
+                        character: 'ä',
+                        position: 3,
+                    }
+                );
+
+                let mut string = $base64.to_string();
+                string.replace_range(2..3, "-");
+                assert_eq!(
+                    $seed::from_base64(&string).unwrap_err(),
+                    ParseSeedError::InvalidCharacter {
+                        character: '-',
+                        position: 2,
+                    }
+                );
+            }
+
+            #[test]
+            fn test_invalid_padding() {
+                let mut string = $base64.to_string();
+                string.replace_range(0..1, "=");
+                assert_eq!(
+                    $seed::from_base64(&string).unwrap_err(),
+                    ParseSeedError::InvalidPadding
+                );
+            }
+        }
+    };
+}
+
+test_encoding!(
+    aes128_ctr64,
+    Aes128Ctr64Seed,
+    32,
+    "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8="
+);
+test_encoding!(
+    aes128_ctr128,
+    Aes128Ctr128Seed,
+    32,
+    "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8="
+);
+test_encoding!(
+    aes256_ctr64,
+    Aes256Ctr64Seed,
+    48,
+    "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4v"
+);
+test_encoding!(
+    aes256_ctr128,
+    Aes256Ctr128Seed,
+    48,
+    "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4v"
+);
+test_encoding!(
+    aes128_fast_key_erasure,
+    Aes128FastKeyErasureSeed,
+    16,
+    "AAECAwQFBgcICQoLDA0ODw=="
+);
+test_encoding!(
+    aes256_fast_key_erasure,
+    Aes256FastKeyErasureSeed,
+    32,
+    "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8="
+);
+
+#[test]
+fn test_base64_non_canonical() {
+    // The last character carries two unused bits, which must be zero.
+    let error = Aes128Ctr64Seed::from_base64("AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh9=");
+    assert_eq!(error.unwrap_err(), ParseSeedError::InvalidPadding);
+
+    // The padding character is missing.
+    let error = Aes128Ctr64Seed::from_base64("AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8A");
+    assert_eq!(error.unwrap_err(), ParseSeedError::InvalidPadding);
+}
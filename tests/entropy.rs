@@ -0,0 +1,163 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+/// Fills the bytes with an increasing sequence and counts the calls.
+#[derive(Default)]
+struct CountingSource {
+    calls: usize,
+}
+
+impl EntropySource for CountingSource {
+    fn fill(&mut self, bytes: &mut [u8]) -> Result<(), EntropyError> {
+        self.calls += 1;
+        bytes
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, byte)| *byte = i as u8);
+        Ok(())
+    }
+}
+
+struct FailingSource;
+
+impl EntropySource for FailingSource {
+    fn fill(&mut self, _: &mut [u8]) -> Result<(), EntropyError> {
+        Err(EntropyError::Failed)
+    }
+}
+
+macro_rules! test_entropy_source {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                $seed::from(core::array::from_fn(|i| i as u8))
+            }
+
+            #[test]
+            fn test_from_entropy_source() {
+                let mut source = CountingSource::default();
+                let prng0 = $prng::from_entropy_source(&mut source).unwrap();
+                let prng1 = $prng::from_seed(seed());
+
+                assert_eq!(source.calls, 1);
+                for _ in 0..16 {
+                    assert_eq!(prng0.next(), prng1.next());
+                }
+            }
+
+            #[test]
+            fn test_seed_from_entropy_source() {
+                let seed0 = $seed::from_entropy_source(&mut CountingSource::default()).unwrap();
+                assert_eq!(seed0.as_ref(), seed().as_ref());
+            }
+
+            #[test]
+            fn test_failing_source() {
+                let result = $prng::from_entropy_source(&mut FailingSource);
+                assert_eq!(result.err(), Some(EntropyError::Failed));
+
+                let result = $seed::from_entropy_source(&mut FailingSource);
+                assert_eq!(result.err(), Some(EntropyError::Failed));
+            }
+
+            #[test]
+            fn test_closure_source() {
+                let mut source = |bytes: &mut [u8]| -> Result<(), EntropyError> {
+                    bytes.fill(0x42);
+                    Ok(())
+                };
+                let prng0 = $prng::from_entropy_source(&mut source).unwrap();
+                let prng1 = $prng::from_seed($seed::from(core::array::from_fn(|_| 0x42)));
+
+                assert_eq!(prng0.next(), prng1.next());
+            }
+
+            #[test]
+            #[cfg(feature = "getrandom")]
+            fn test_os_entropy() {
+                let prng0 = $prng::from_entropy_source(&mut OsEntropy).unwrap();
+                let prng1 = $prng::from_entropy_source(&mut OsEntropy).unwrap();
+
+                assert_ne!(prng0.next(), prng1.next());
+            }
+
+            #[test]
+            fn test_wrappers() {
+                let prng0 = Reseeding::<$prng, CountingSource>::from_entropy_source(
+                    &mut CountingSource::default(),
+                )
+                .unwrap();
+                let prng1 = $prng::from_seed(seed());
+                assert_eq!(prng0.next(), prng1.next());
+
+                let result =
+                    Reseeding::<$prng, CountingSource>::from_entropy_source(&mut FailingSource);
+                assert!(result.is_err());
+
+                #[cfg(feature = "fork_detection")]
+                {
+                    let prng0 =
+                        ForkSafe::<$prng>::from_entropy_source(&mut CountingSource::default())
+                            .unwrap();
+                    let prng1 = $prng::from_seed(seed());
+                    assert_eq!(prng0.next(), prng1.next());
+                }
+            }
+        }
+    };
+}
+
+test_entropy_source!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed);
+test_entropy_source!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed);
+test_entropy_source!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed);
+test_entropy_source!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed);
+
+#[test]
+fn test_error_display() {
+    assert_eq!(
+        EntropyError::Failed.to_string(),
+        "the entropy source failed to provide entropy"
+    );
+    assert_eq!(
+        EntropyError::Unavailable.to_string(),
+        "the entropy source is not available"
+    );
+}
+
+#[test]
+fn test_xor() {
+    let first = |bytes: &mut [u8]| -> Result<(), EntropyError> {
+        bytes.fill(0xF0);
+        Ok(())
+    };
+    let mut source = first.xor(CountingSource::default());
+
+    let mut bytes = [0u8; 200];
+    source.fill(&mut bytes).unwrap();
+    for (i, byte) in bytes.iter().enumerate() {
+        // The second source is filled in chunks of 64 bytes.
+        assert_eq!(*byte, 0xF0 ^ (i % 64) as u8);
+    }
+
+    assert_eq!(
+        first.xor(FailingSource).fill(&mut bytes),
+        Err(EntropyError::Failed)
+    );
+    assert_eq!(
+        FailingSource.xor(first).fill(&mut bytes),
+        Err(EntropyError::Failed)
+    );
+}
+
+#[test]
+fn test_or() {
+    let mut source = FailingSource.or(CountingSource::default());
+    let prng0 = Aes128Ctr64::from_entropy_source(&mut source).unwrap();
+    let prng1 = Aes128Ctr64::from_seed(Aes128Ctr64Seed::from(core::array::from_fn(|i| i as u8)));
+    assert_eq!(prng0.next(), prng1.next());
+
+    let mut source = FailingSource.or(FailingSource);
+    assert_eq!(source.fill(&mut [0u8; 16]), Err(EntropyError::Failed));
+}
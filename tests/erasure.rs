@@ -0,0 +1,135 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_fast_key_erasure {
+    (
+        $name:ident,
+        $prng:ident,
+        $seed:ident,
+        $reference:ident,
+        $reference_seed:ident,
+        $key_size:literal
+    ) => {
+        mod $name {
+            use super::*;
+
+            const BATCH_BLOCKS: usize = 48;
+            const KEY_BLOCKS: usize = $key_size / 16;
+
+            /// Generates a batch with the CTR generator and returns its output and the next key.
+            fn reference_batch(key: [u8; $key_size]) -> (Vec<u128>, [u8; $key_size]) {
+                let reference = $reference::from_seed($reference_seed::new(key, 0));
+                let blocks: [u128; BATCH_BLOCKS] = reference.next_blocks();
+
+                let mut next_key = [0u8; $key_size];
+                for (chunk, block) in next_key.chunks_exact_mut(16).zip(blocks) {
+                    chunk.copy_from_slice(&block.to_le_bytes());
+                }
+
+                (blocks[KEY_BLOCKS..].to_vec(), next_key)
+            }
+
+            #[test]
+            fn test_batches() {
+                let prng = $prng::from_seed($seed::new([7; $key_size]));
+
+                let mut key = [7; $key_size];
+                for _ in 0..3 {
+                    let (blocks, next_key) = reference_batch(key);
+                    for block in blocks {
+                        assert_eq!(prng.next(), block);
+                    }
+                    key = next_key;
+                }
+            }
+
+            #[test]
+            fn test_fill_bytes() {
+                let prng = $prng::from_seed($seed::new([7; $key_size]));
+                let reference = $prng::from_seed($seed::new([7; $key_size]));
+
+                let mut bytes = [0u8; 1000];
+                prng.fill_bytes(&mut bytes);
+
+                for chunk in bytes.chunks(16) {
+                    assert_eq!(chunk, &reference.next().to_le_bytes()[..chunk.len()]);
+                }
+                assert_eq!(prng.next(), reference.next());
+            }
+
+            #[test]
+            fn test_leftover() {
+                let prng = $prng::from_seed($seed::new([7; $key_size]));
+                let reference = $prng::from_seed($seed::new([7; $key_size]));
+
+                let block = reference.next();
+                assert_eq!(prng.u32(), block as u32);
+                assert_eq!(prng.u32(), (block >> 32) as u32);
+                assert_eq!(prng.u64(), (block >> 64) as u64);
+                assert_eq!(prng.u8(), reference.next() as u8);
+                assert_eq!(prng.counter(), reference.counter());
+            }
+
+            #[test]
+            fn test_seed() {
+                let prng = $prng::from_seed($seed::new([0; $key_size]));
+                let reference = $prng::from_seed($seed::new([7; $key_size]));
+
+                let _ = prng.u8();
+                prng.seed($seed::new([7; $key_size]));
+
+                assert_eq!(prng.u32(), reference.u32());
+                assert_eq!(prng.next(), reference.next());
+            }
+
+            #[test]
+            fn test_from_entropy_source() {
+                let mut source = |bytes: &mut [u8]| -> Result<(), EntropyError> {
+                    bytes.fill(7);
+                    Ok(())
+                };
+
+                let prng = $prng::from_entropy_source(&mut source).unwrap();
+                let reference = $prng::from_seed($seed::new([7; $key_size]));
+
+                assert_eq!(prng.next(), reference.next());
+            }
+
+            #[test]
+            fn test_from_u64() {
+                let prng = $prng::from_u64(42);
+                let reference = $prng::from_seed($seed::derive(
+                    b"rand_aes::Random::from_u64",
+                    &42u64.to_le_bytes(),
+                ));
+
+                assert_eq!(prng.next(), reference.next());
+            }
+
+            #[test]
+            fn test_different_seeds() {
+                let prng0 = $prng::from_seed($seed::new([0; $key_size]));
+                let prng1 = $prng::from_seed($seed::new([1; $key_size]));
+
+                assert_ne!(prng0.next(), prng1.next());
+            }
+        }
+    };
+}
+
+test_fast_key_erasure!(
+    aes128,
+    Aes128FastKeyErasure,
+    Aes128FastKeyErasureSeed,
+    Aes128Ctr128,
+    Aes128Ctr128Seed,
+    16
+);
+test_fast_key_erasure!(
+    aes256,
+    Aes256FastKeyErasure,
+    Aes256FastKeyErasureSeed,
+    Aes256Ctr128,
+    Aes256Ctr128Seed,
+    32
+);
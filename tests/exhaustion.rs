@@ -0,0 +1,160 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_exhaustion {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            type Counter = <$prng as Random>::Counter;
+
+            #[test]
+            fn test_remaining() {
+                let prng = $prng::from_seed($seed::default());
+
+                assert_eq!(prng.remaining(), (Counter::MAX as u128).saturating_add(1));
+
+                prng.set_counter(Counter::MAX - 2);
+                assert_eq!(prng.remaining(), 3);
+                let _ = prng.next();
+                assert_eq!(prng.remaining(), 2);
+            }
+
+            #[test]
+            fn test_default_policy() {
+                let prng = $prng::from_seed($seed::default());
+                assert_eq!(prng.exhaustion_policy(), ExhaustionPolicy::Panic);
+            }
+
+            #[test]
+            fn test_try_next() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+                reference.set_counter(Counter::MAX);
+
+                prng.set_counter(Counter::MAX);
+                assert_eq!(prng.try_next(), Ok(reference.next()));
+                assert_eq!(prng.remaining(), 0);
+                assert_eq!(prng.try_next(), Err(CounterExhausted));
+                assert_eq!(prng.try_next(), Err(CounterExhausted));
+                assert_eq!(prng.counter(), Counter::MAX);
+
+                // Changing the position makes the generator usable again.
+                prng.rewind(1);
+                assert_eq!(prng.remaining(), 2);
+                assert!(prng.try_next().is_ok());
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_panic() {
+                let prng = $prng::from_seed($seed::default());
+                prng.set_counter(Counter::MAX);
+
+                let _ = prng.next();
+                let _ = prng.next();
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_panic_narrow() {
+                let prng = $prng::from_seed($seed::default());
+                prng.set_counter(Counter::MAX);
+
+                for _ in 0..5 {
+                    let _ = prng.u32();
+                }
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_panic_next_blocks() {
+                let prng = $prng::from_seed($seed::default());
+                prng.set_counter(Counter::MAX - 2);
+
+                let _: [u128; 8] = prng.next_blocks();
+            }
+
+            #[test]
+            fn test_next_blocks_until_exhausted() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+                prng.set_counter(Counter::MAX - 7);
+                reference.set_counter(Counter::MAX - 7);
+
+                let blocks: [u128; 8] = prng.next_blocks();
+                assert_eq!(blocks, core::array::from_fn(|_| reference.next()));
+                assert_eq!(prng.try_next(), Err(CounterExhausted));
+            }
+
+            #[test]
+            fn test_wrap() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+                prng.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+
+                prng.set_counter(Counter::MAX);
+                let _ = prng.next();
+                assert_eq!(prng.counter(), 0);
+                assert_eq!(prng.try_next(), Ok(reference.next()));
+            }
+
+            #[test]
+            fn test_policy_survives_seed() {
+                let prng = $prng::from_seed($seed::default());
+                prng.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+                prng.seed($seed::default());
+
+                assert_eq!(prng.exhaustion_policy(), ExhaustionPolicy::Wrap);
+                assert_eq!(prng.clone().exhaustion_policy(), ExhaustionPolicy::Wrap);
+            }
+
+            #[test]
+            fn test_seed_resets_exhaustion() {
+                let prng = $prng::from_seed($seed::default());
+                prng.set_counter(Counter::MAX);
+                let _ = prng.next();
+
+                prng.seed($seed::default());
+                assert!(prng.try_next().is_ok());
+            }
+        }
+    };
+}
+
+test_exhaustion!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed);
+test_exhaustion!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed);
+test_exhaustion!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed);
+test_exhaustion!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed);
+
+macro_rules! test_roll_over {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        #[test]
+        fn $name() {
+            let prng = $prng::from_seed($seed::default());
+            let reference = prng.with_stream(1);
+            prng.set_exhaustion_policy(ExhaustionPolicy::RollOver);
+
+            prng.set_counter(u64::MAX);
+            let _ = prng.u32();
+            assert_eq!(prng.stream(), 1);
+            assert_eq!(prng.counter(), 0);
+            assert_eq!(prng.next(), reference.next());
+        }
+    };
+}
+
+test_roll_over!(test_roll_over_aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed);
+test_roll_over!(test_roll_over_aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed);
+
+#[test]
+fn test_roll_over_aes128_ctr128() {
+    let prng = Aes128Ctr128::from_seed(Aes128Ctr128Seed::default());
+    let reference = Aes128Ctr128::from_seed(Aes128Ctr128Seed::default());
+    prng.set_exhaustion_policy(ExhaustionPolicy::RollOver);
+
+    prng.set_counter(u128::MAX);
+    let _ = prng.next();
+    assert_eq!(prng.counter(), 0);
+    assert_eq!(prng.next(), reference.next());
+}
@@ -0,0 +1,156 @@
+#![cfg(all(unix, feature = "fork_detection"))]
+
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+/// Runs the given function in a forked child process and returns its result.
+fn in_child(f: impl FnOnce() -> u128) -> u128 {
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+
+    let pid = unsafe { libc::fork() };
+    assert!(pid >= 0, "Can't fork the process");
+
+    if pid == 0 {
+        // The child must never return into the test harness.
+        let status = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+            Ok(value) => {
+                let bytes = value.to_le_bytes();
+                let written = unsafe { libc::write(fds[1], bytes.as_ptr().cast(), bytes.len()) };
+                if written == bytes.len() as isize {
+                    0
+                } else {
+                    1
+                }
+            }
+            Err(_) => 1,
+        };
+        unsafe { libc::_exit(status) };
+    }
+
+    unsafe { libc::close(fds[1]) };
+
+    let mut bytes = [0u8; 16];
+    let read = unsafe { libc::read(fds[0], bytes.as_mut_ptr().cast(), bytes.len()) };
+    unsafe { libc::close(fds[0]) };
+
+    let mut status = 0;
+    assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+    assert!(libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0);
+    assert_eq!(read, 16);
+
+    u128::from_le_bytes(bytes)
+}
+
+#[test]
+fn test_owned_generator_repeats_after_fork() {
+    let rng = Aes128Ctr64::from_seed(Aes128Ctr64Seed::default());
+
+    let child = in_child(|| rng.next());
+    assert_eq!(child, rng.next());
+}
+
+#[test]
+fn test_fork_safe_reseeds_after_fork() {
+    let rng = ForkSafe::new(Aes128Ctr64::from_seed(Aes128Ctr64Seed::default()));
+
+    let child = in_child(|| rng.next());
+    assert_ne!(child, rng.next());
+}
+
+#[test]
+fn test_fork_safe_narrow_methods() {
+    let rng = ForkSafe::new(Aes256Ctr128::from_seed(Aes256Ctr128Seed::default()));
+    let _ = rng.u32();
+
+    let child = in_child(|| rng.u32() as u128);
+    assert_ne!(child, rng.u32() as u128);
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn test_fork_safe_rand_core() {
+    use rand_core::{RngCore, SeedableRng};
+
+    let mut rng: ForkSafe<Aes128Ctr64> = SeedableRng::from_seed(Aes128Ctr64Seed::default());
+    let reference: Aes128Ctr64 = Random::from_seed(Aes128Ctr64Seed::default());
+
+    assert_eq!(rng.next_u32(), reference.u32());
+    assert_eq!(rng.next_u32(), reference.u32());
+    assert_eq!(rng.next_u64(), reference.u64());
+}
+
+#[test]
+fn test_fork_safe_seed_in_child() {
+    let rng = ForkSafe::new(Aes128Ctr128::from_seed(Aes128Ctr128Seed::default()));
+
+    let child = in_child(|| {
+        rng.seed(Aes128Ctr128Seed::default());
+        rng.next()
+    });
+    rng.seed(Aes128Ctr128Seed::default());
+    assert_eq!(child, rng.next());
+}
+
+#[cfg(feature = "tls")]
+#[test]
+fn test_tls_reseeds_after_fork() {
+    use rand_aes::tls::*;
+
+    rand_seed(Seed::default());
+
+    let child = in_child(rand_u128);
+    assert_ne!(child, rand_u128());
+}
+
+#[cfg(feature = "tls")]
+#[test]
+fn test_tls_reseeds_after_fork_while_entropy_source_is_locked() {
+    use rand_aes::tls::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Barrier};
+
+    let locked = Arc::new(Barrier::new(2));
+    let released = Arc::new(Barrier::new(2));
+    let block = AtomicBool::new(true);
+
+    let source = {
+        let locked = locked.clone();
+        let released = released.clone();
+        move |bytes: &mut [u8]| -> Result<(), EntropyError> {
+            // Only the first call blocks, while the entropy source is locked.
+            if block.swap(false, Ordering::Relaxed) {
+                locked.wait();
+                released.wait();
+            }
+            bytes.fill(0x42);
+            Ok(())
+        }
+    };
+    rand_set_entropy_source(source);
+
+    let thread = std::thread::spawn(rand_seed_from_entropy);
+    locked.wait();
+
+    rand_seed(Seed::default());
+    let child = in_child(rand_u128);
+    assert_ne!(child, rand_u128());
+
+    released.wait();
+    thread.join().unwrap();
+}
+
+#[cfg(feature = "tls")]
+#[test]
+fn test_tls_seed_in_child() {
+    use rand_aes::tls::*;
+
+    rand_seed(Seed::default());
+
+    let child = in_child(|| {
+        rand_seed(Seed::default());
+        rand_u128()
+    });
+    rand_seed(Seed::default());
+    assert_eq!(child, rand_u128());
+}
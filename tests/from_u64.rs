@@ -0,0 +1,95 @@
+use hex_literal::hex;
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+// The expected seeds were cross-checked with the CMAC and the SP 800-108 KBKDF in counter mode of
+// the Python `cryptography` package. They must never change.
+macro_rules! test_from_u64 {
+    ($name:ident, $prng:ident, $seed:ident, $expected:expr) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                $seed::from(core::array::from_fn(|i| i as u8))
+            }
+
+            #[test]
+            fn test_from_u64_known_answer() {
+                let prng = $prng::from_u64(42);
+                assert_eq!(prng.snapshot().seed().as_ref(), $expected);
+            }
+
+            #[test]
+            fn test_from_u64_is_derived() {
+                let prng0 = $prng::from_u64(7);
+                let prng1 = $prng::from_seed($seed::derive(
+                    b"rand_aes::Random::from_u64",
+                    &7u64.to_le_bytes(),
+                ));
+
+                for _ in 0..16 {
+                    assert_eq!(prng0.next(), prng1.next());
+                }
+                assert_ne!($prng::from_u64(7).next(), $prng::from_u64(8).next());
+            }
+
+            #[test]
+            fn test_from_random() {
+                let source = $prng::from_seed(seed());
+                let prng0 = $prng::from_random(&source);
+
+                let reference = $prng::from_seed(seed());
+                let mut seed = $seed::default();
+                reference.fill_bytes(seed.as_mut());
+                let prng1 = $prng::from_seed(seed);
+
+                for _ in 0..16 {
+                    assert_eq!(prng0.next(), prng1.next());
+                }
+
+                // The source continues after the seed.
+                assert_eq!(source.next(), reference.next());
+            }
+
+            #[test]
+            fn test_from_random_other_type() {
+                let source = Aes256Ctr128::from_u64(1);
+                let prng0 = $prng::from_random(&source);
+                let prng1 = $prng::from_random(&source);
+
+                assert_ne!(prng0.next(), prng1.next());
+            }
+        }
+    };
+}
+
+test_from_u64!(
+    aes128_ctr64,
+    Aes128Ctr64,
+    Aes128Ctr64Seed,
+    hex!("3b91ec0dbc09638d9fd259171447979cfdb44ed9c85651b0bd1b62efe6ea0f7e")
+);
+test_from_u64!(
+    aes128_ctr128,
+    Aes128Ctr128,
+    Aes128Ctr128Seed,
+    hex!("2bdaa4523dfc193260b1e4a83fdba21abd6d8bca4cb1c331419bc5e45bbe4d6c")
+);
+test_from_u64!(
+    aes256_ctr64,
+    Aes256Ctr64,
+    Aes256Ctr64Seed,
+    hex!(
+        "f36273ef730311528877259c9a3cef280a8602998215ad45ee69eab2054a60fd"
+        "b7f805b1fde9c95f02badd4613c15505"
+    )
+);
+test_from_u64!(
+    aes256_ctr128,
+    Aes256Ctr128,
+    Aes256Ctr128Seed,
+    hex!(
+        "5339dfafbf83b99c28a9d0353fe309102d15e85c5821743c0b7d08f4321d0fc5"
+        "830fe3ab60e48e2bb59b7e08ab6e9954"
+    )
+);
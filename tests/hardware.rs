@@ -0,0 +1,70 @@
+use rand_aes::*;
+
+fn sources() -> Vec<HardwareEntropy> {
+    [HardwareEntropy::new(), HardwareEntropy::drbg()]
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect()
+}
+
+#[test]
+fn test_availability() {
+    assert_eq!(
+        HardwareEntropy::is_available(),
+        HardwareEntropy::new().is_ok()
+    );
+    assert_eq!(
+        HardwareEntropy::is_available(),
+        HardwareEntropy::drbg().is_ok()
+    );
+
+    if !HardwareEntropy::is_available() {
+        assert_eq!(
+            HardwareEntropy::new().err(),
+            Some(EntropyError::Unavailable)
+        );
+    }
+}
+
+#[test]
+fn test_fill() {
+    for mut source in sources() {
+        let mut bytes0 = [0u8; 61];
+        let mut bytes1 = [0u8; 61];
+        source.fill(&mut bytes0).unwrap();
+        source.fill(&mut bytes1).unwrap();
+
+        assert_ne!(bytes0, [0u8; 61]);
+        assert_ne!(bytes0, bytes1);
+    }
+}
+
+#[test]
+fn test_seeding() {
+    for mut source in sources() {
+        let prng0 = Aes128Ctr64::from_entropy_source(&mut source).unwrap();
+        let prng1 = Aes128Ctr64::from_entropy_source(&mut source).unwrap();
+        assert_ne!(prng0.next(), prng1.next());
+    }
+}
+
+#[test]
+#[cfg(feature = "getrandom")]
+fn test_combined_with_os() {
+    let mut source = HardwareEntropy::new()
+        .map(|source| source.xor(OsEntropy))
+        .ok();
+
+    if let Some(source) = source.as_mut() {
+        let prng = Aes256Ctr128::from_entropy_source(source).unwrap();
+        let _ = prng.next();
+    }
+
+    let mut source = HardwareEntropy::new()
+        .ok()
+        .map(|source| source.or(OsEntropy));
+    if let Some(source) = source.as_mut() {
+        let mut bytes = [0u8; 32];
+        source.fill(&mut bytes).unwrap();
+    }
+}
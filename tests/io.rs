@@ -0,0 +1,97 @@
+#![cfg(feature = "std")]
+
+use std::io::Read;
+
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_reader {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                $seed::from(core::array::from_fn(|i| i as u8))
+            }
+
+            fn expected(length: usize) -> Vec<u8> {
+                let prng = $prng::from_seed(seed());
+                let mut bytes: Vec<u8> = (0..length.div_ceil(16))
+                    .flat_map(|_| prng.next().to_le_bytes())
+                    .collect();
+                bytes.truncate(length);
+                bytes
+            }
+
+            #[test]
+            fn test_read_matches_blocks() {
+                let expected = expected(4096);
+
+                // Reads of every size must continue the same stream.
+                let mut reader = Reader::new($prng::from_seed(seed()));
+                let mut bytes = Vec::new();
+                let mut size = 1;
+                while bytes.len() < expected.len() {
+                    let mut chunk = vec![0u8; size.min(expected.len() - bytes.len())];
+                    assert_eq!(reader.read(&mut chunk).unwrap(), chunk.len());
+                    bytes.extend_from_slice(&chunk);
+                    size = size * 3 + 1;
+                }
+                assert_eq!(bytes, expected);
+            }
+
+            #[test]
+            fn test_limit() {
+                let mut reader = Reader::new($prng::from_seed(seed())).with_limit(1000);
+                assert_eq!(reader.limit(), Some(1000));
+
+                let mut bytes = [0u8; 600];
+                assert_eq!(reader.read(&mut bytes).unwrap(), 600);
+                assert_eq!(reader.read(&mut bytes).unwrap(), 400);
+                assert_eq!(reader.read(&mut bytes).unwrap(), 0);
+                assert_eq!(reader.limit(), Some(0));
+
+                let mut bytes = Vec::new();
+                let reader = Reader::new($prng::from_seed(seed())).with_limit(333);
+                assert_eq!(reader.take(1000).read_to_end(&mut bytes).unwrap(), 333);
+                assert_eq!(bytes, expected(333));
+            }
+
+            #[test]
+            fn test_into_inner() {
+                let mut reader = Reader::new($prng::from_seed(seed()));
+                let mut bytes = [0u8; 256];
+                reader.read_exact(&mut bytes).unwrap();
+
+                // The reader didn't generate any bytes beyond the ones it returned.
+                let reference = $prng::from_seed(seed());
+                for _ in 0..16 {
+                    reference.next();
+                }
+                assert_eq!(reader.into_inner().next(), reference.next());
+            }
+        }
+    };
+}
+
+test_reader!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed);
+test_reader!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed);
+test_reader!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed);
+test_reader!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed);
+
+#[cfg(feature = "tls")]
+#[test]
+fn test_tls_reader() {
+    use rand_aes::tls::*;
+
+    rand_seed(Seed::default());
+    let expected: Vec<u8> = (0..64).flat_map(|_| rand_u128().to_le_bytes()).collect();
+
+    rand_seed(Seed::default());
+    let mut reader = Reader::new(TlsRng);
+    let mut bytes = [0u8; 1024];
+    for chunk in bytes.chunks_mut(100) {
+        reader.read_exact(chunk).unwrap();
+    }
+    assert_eq!(bytes.as_slice(), expected);
+}
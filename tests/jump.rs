@@ -76,3 +76,21 @@ fn test_long_jump_aes256_ctr128() {
     assert_eq!(rng5.counter(), period * 4);
     assert_eq!(rng0.counter(), period * 5);
 }
+
+#[test]
+fn test_jump_after_next_aes128_ctr128() {
+    let period = 1u128 << 64;
+
+    let rng0 = Aes128Ctr128::from_seed(Aes128Ctr128Seed::default());
+    let reference = Aes128Ctr128::from_seed(Aes128Ctr128Seed::default());
+    let _ = rng0.next();
+    let _ = reference.next();
+
+    let rng1 = rng0.jump();
+    assert_eq!(rng1.counter(), 1);
+    assert_eq!(rng1.next(), reference.next());
+
+    assert_eq!(rng0.counter(), period + 1);
+    reference.set_counter(period + 1);
+    assert_eq!(rng0.next(), reference.next());
+}
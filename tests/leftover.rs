@@ -0,0 +1,101 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_leftover {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn test_narrow_values() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let block = reference.next();
+                for i in 0..4 {
+                    assert_eq!(prng.u32(), (block >> (i * 32)) as u32);
+                }
+
+                let block = reference.next();
+                for i in 0..2 {
+                    assert_eq!(prng.u64(), (block >> (i * 64)) as u64);
+                }
+
+                let block = reference.next();
+                for i in 0..8 {
+                    assert_eq!(prng.u16(), (block >> (i * 16)) as u16);
+                }
+
+                let block = reference.next();
+                for i in 0..16 {
+                    assert_eq!(prng.u8(), (block >> (i * 8)) as u8);
+                }
+
+                let block = reference.next();
+                for i in 0..128 {
+                    assert_eq!(prng.bool(), (block >> i) & 1 == 0);
+                }
+
+                assert_eq!(prng.next(), reference.next());
+            }
+
+            #[test]
+            fn test_discard_short_remainder() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let block = reference.next();
+                assert_eq!(prng.u32(), block as u32);
+                assert_eq!(prng.u64(), (block >> 32) as u64);
+
+                // Only 32 bits are left, so a new block is used.
+                let block = reference.next();
+                assert_eq!(prng.u64(), block as u64);
+                assert_eq!(prng.u32(), (block >> 64) as u32);
+            }
+
+            #[test]
+            fn test_wide_values_keep_remainder() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let block_0 = reference.next();
+                let block_1 = reference.next();
+
+                assert_eq!(prng.u32(), block_0 as u32);
+                assert_eq!(prng.u128(), block_1);
+                assert_eq!(prng.u32(), (block_0 >> 32) as u32);
+            }
+
+            #[test]
+            fn test_seed_discards_remainder() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let _ = prng.u32();
+                prng.seed($seed::default());
+
+                assert_eq!(prng.u32(), reference.next() as u32);
+            }
+
+            #[test]
+            #[cfg(feature = "rand_core")]
+            fn test_rand_core() {
+                use rand_core::RngCore;
+
+                let mut prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let block = reference.next();
+                assert_eq!(prng.next_u32(), block as u32);
+                assert_eq!(prng.next_u32(), (block >> 32) as u32);
+                assert_eq!(prng.next_u64(), (block >> 64) as u64);
+            }
+        }
+    };
+}
+
+test_leftover!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed);
+test_leftover!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed);
+test_leftover!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed);
+test_leftover!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed);
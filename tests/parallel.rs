@@ -0,0 +1,116 @@
+#![cfg(feature = "rayon")]
+
+use rand_aes::seeds::*;
+use rand_aes::*;
+use rayon::prelude::*;
+
+macro_rules! test_parallel {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            fn prng() -> $prng {
+                $prng::from_seed($seed::from(core::array::from_fn(|i| i as u8)))
+            }
+
+            macro_rules! test_par_fill {
+                ($test:ident, $type:ty, $method:ident) => {
+                    #[test]
+                    fn $test() {
+                        for (skip, len) in [(0, 0), (0, 1), (0, 1000), (1, 999), (3, 100_000)] {
+                            let prng0 = prng();
+                            let prng1 = prng();
+
+                            // Leaves unused bits in the last generated block.
+                            for _ in 0..skip {
+                                assert_eq!(prng0.u32(), prng1.u32());
+                            }
+
+                            let expected: Vec<$type> = (0..len).map(|_| prng0.$method()).collect();
+                            let mut values = vec![<$type>::default(); len];
+                            prng1.par_fill(&mut values);
+                            assert_eq!(values, expected);
+
+                            // The generator is left in the same state.
+                            assert_eq!(prng0.u8(), prng1.u8());
+                            assert_eq!(prng0.next(), prng1.next());
+                        }
+                    }
+                };
+            }
+
+            test_par_fill!(test_par_fill_u8, u8, u8);
+            test_par_fill!(test_par_fill_u16, u16, u16);
+            test_par_fill!(test_par_fill_u32, u32, u32);
+            test_par_fill!(test_par_fill_u64, u64, u64);
+            test_par_fill!(test_par_fill_u128, u128, u128);
+            test_par_fill!(test_par_fill_usize, usize, usize);
+            test_par_fill!(test_par_fill_i8, i8, i8);
+            test_par_fill!(test_par_fill_i16, i16, i16);
+            test_par_fill!(test_par_fill_i32, i32, i32);
+            test_par_fill!(test_par_fill_i64, i64, i64);
+            test_par_fill!(test_par_fill_i128, i128, i128);
+            test_par_fill!(test_par_fill_isize, isize, isize);
+            test_par_fill!(test_par_fill_bool, bool, bool);
+            test_par_fill!(test_par_fill_f32, f32, f32);
+            test_par_fill!(test_par_fill_f64, f64, f64);
+
+            #[test]
+            fn test_par_fill_bytes() {
+                for len in [0, 1, 15, 16, 333, 64 * 1024, 200_000] {
+                    let prng0 = prng();
+                    let prng1 = prng();
+                    assert_eq!(prng0.u16(), prng1.u16());
+
+                    let mut expected = vec![0u8; len];
+                    prng0.fill_bytes(&mut expected);
+                    let mut bytes = vec![0u8; len];
+                    prng1.par_fill_bytes(&mut bytes);
+                    assert_eq!(bytes, expected);
+
+                    // The unused bits of the last narrow value are kept.
+                    assert_eq!(prng0.u16(), prng1.u16());
+                    assert_eq!(prng0.next(), prng1.next());
+                }
+            }
+
+            #[test]
+            fn test_par_iter() {
+                let prng0 = prng();
+                let prng1 = prng();
+
+                let expected: Vec<u32> = (0..10_001).map(|_| prng0.u32()).collect();
+                let values: Vec<u32> = prng1.par_iter(10_001).collect();
+                assert_eq!(values, expected);
+                assert_eq!(prng0.u32(), prng1.u32());
+
+                let values: Vec<u64> = prng().par_iter(1000).rev().collect();
+                assert_eq!(values[999], prng().u64());
+            }
+
+            #[test]
+            fn test_thread_count_independence() {
+                let generate = |threads| {
+                    let pool = rayon::ThreadPoolBuilder::new()
+                        .num_threads(threads)
+                        .build()
+                        .unwrap();
+                    pool.install(|| {
+                        let prng = prng();
+                        let mut bytes = vec![0u8; 300_000];
+                        prng.par_fill_bytes(&mut bytes);
+                        let values: Vec<f64> = prng.par_iter(100_000).collect();
+                        (bytes, values)
+                    })
+                };
+
+                let expected = generate(1);
+                assert_eq!(generate(2), expected);
+                assert_eq!(generate(7), expected);
+            }
+        }
+    };
+}
+
+test_parallel!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed);
+test_parallel!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed);
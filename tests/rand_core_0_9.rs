@@ -0,0 +1,94 @@
+#![cfg(feature = "rand_core_0_9")]
+
+use rand_aes::seeds::*;
+use rand_aes::*;
+use rand_core_0_9::{RngCore, SeedableRng, TryRngCore};
+
+macro_rules! test_rand_core {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                $seed::from(core::array::from_fn(|i| i as u8))
+            }
+
+            #[test]
+            fn test_rng_core() {
+                let mut prng0 = <$prng as SeedableRng>::from_seed(seed());
+                let prng1 = <$prng as Random>::from_seed(seed());
+
+                assert_eq!(prng0.next_u32(), prng1.u32());
+                assert_eq!(prng0.next_u32(), prng1.u32());
+                assert_eq!(prng0.next_u64(), prng1.u64());
+
+                let mut bytes0 = [0u8; 37];
+                let mut bytes1 = [0u8; 37];
+                prng0.fill_bytes(&mut bytes0);
+                prng1.fill_bytes(&mut bytes1);
+                assert_eq!(bytes0, bytes1);
+            }
+
+            #[test]
+            fn test_try_rng_core() {
+                let mut prng0 = <$prng as SeedableRng>::from_seed(seed());
+                let prng1 = <$prng as Random>::from_seed(seed());
+
+                assert_eq!(prng0.try_next_u64(), Ok(prng1.u64()));
+
+                let mut bytes0 = [0u8; 16];
+                prng0.try_fill_bytes(&mut bytes0).unwrap();
+                assert_eq!(bytes0, prng1.next().to_le_bytes());
+            }
+
+            #[test]
+            fn test_seedable_rng() {
+                let mut prng0 = <$prng as SeedableRng>::seed_from_u64(42);
+                let mut prng1 = <$prng as SeedableRng>::seed_from_u64(42);
+                assert_eq!(prng0.next_u64(), prng1.next_u64());
+
+                let mut prng2 = <$prng as SeedableRng>::from_rng(&mut prng0);
+                let mut prng3 = <$prng as SeedableRng>::from_rng(&mut prng1);
+                assert_eq!(prng2.next_u64(), prng3.next_u64());
+            }
+
+            #[test]
+            #[cfg(feature = "rand_core")]
+            fn test_same_output_as_rand_core_0_6() {
+                let mut prng0 = <$prng as SeedableRng>::from_seed(seed());
+                let mut prng1 = <$prng as rand_core::SeedableRng>::from_seed(seed());
+
+                assert_eq!(
+                    RngCore::next_u32(&mut prng0),
+                    rand_core::RngCore::next_u32(&mut prng1)
+                );
+                assert_eq!(
+                    RngCore::next_u64(&mut prng0),
+                    rand_core::RngCore::next_u64(&mut prng1)
+                );
+            }
+        }
+    };
+}
+
+test_rand_core!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed);
+test_rand_core!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed);
+test_rand_core!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed);
+test_rand_core!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed);
+
+#[test]
+#[cfg(feature = "tls")]
+fn test_tls_rng() {
+    use rand_aes::tls::*;
+
+    rand_seed(Seed::default());
+    let expected = rand_u64();
+
+    rand_seed(Seed::default());
+    let mut rng = TlsRng;
+    assert_eq!(rng.next_u64(), expected);
+    assert!(rng.try_next_u32().is_ok());
+
+    // The handle can seed a generator.
+    let _ = Aes128Ctr64::from_rng(&mut rng);
+}
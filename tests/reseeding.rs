@@ -0,0 +1,201 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+fn fixed_entropy(bytes: &mut [u8]) -> Result<(), EntropyError> {
+    bytes.fill(0x42);
+    Ok(())
+}
+
+fn failing_entropy(_: &mut [u8]) -> Result<(), EntropyError> {
+    Err(EntropyError::Failed)
+}
+
+/// Fails and counts the calls.
+#[derive(Default)]
+struct CountingSource {
+    calls: usize,
+}
+
+impl EntropySource for CountingSource {
+    fn fill(&mut self, _: &mut [u8]) -> Result<(), EntropyError> {
+        self.calls += 1;
+        Err(EntropyError::Failed)
+    }
+}
+
+macro_rules! test_reseeding {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                $seed::from(core::array::from_fn(|i| i as u8))
+            }
+
+            fn fixed_seed() -> $seed {
+                $seed::from(core::array::from_fn(|_| 0x42))
+            }
+
+            #[test]
+            fn test_reseeds_after_threshold() {
+                let rng = Reseeding::with_entropy($prng::from_seed(seed()), 64, fixed_entropy);
+                let reference = $prng::from_seed(seed());
+                let reseeded = $prng::from_seed(fixed_seed());
+
+                for _ in 0..4 {
+                    assert_eq!(rng.next(), reference.next());
+                }
+                assert_eq!(rng.bytes_since_reseed(), 64);
+
+                assert_eq!(rng.next(), reseeded.next());
+                assert_eq!(rng.bytes_since_reseed(), 16);
+                assert_eq!(rng.last_reseed_error(), None);
+            }
+
+            #[test]
+            fn test_narrow_methods() {
+                let rng = Reseeding::with_entropy($prng::from_seed(seed()), 16, fixed_entropy);
+                let reference = $prng::from_seed(seed());
+                let reseeded = $prng::from_seed(fixed_seed());
+
+                for _ in 0..4 {
+                    assert_eq!(rng.u32(), reference.u32());
+                }
+                assert_eq!(rng.u64(), reseeded.u64());
+                assert_eq!(rng.bytes_since_reseed(), 8);
+            }
+
+            #[test]
+            fn test_fill_bytes() {
+                let rng = Reseeding::with_entropy($prng::from_seed(seed()), 40, fixed_entropy);
+                let reference = $prng::from_seed(seed());
+                let reseeded = $prng::from_seed(fixed_seed());
+
+                let mut bytes = [0u8; 100];
+                rng.fill_bytes(&mut bytes);
+
+                // The threshold is rounded up to whole blocks.
+                let mut expected = [0u8; 100];
+                reference.fill_bytes(&mut expected[..48]);
+                reseeded.fill_bytes(&mut expected[48..96]);
+                expected.copy_within(48..52, 96);
+
+                assert_eq!(bytes, expected);
+                assert_eq!(rng.bytes_since_reseed(), 4);
+            }
+
+            #[test]
+            fn test_failing_entropy() {
+                let rng = Reseeding::with_entropy($prng::from_seed(seed()), 32, failing_entropy);
+                let reference = $prng::from_seed(seed());
+
+                for _ in 0..8 {
+                    assert_eq!(rng.next(), reference.next());
+                }
+                assert_eq!(rng.bytes_since_reseed(), 32);
+                assert_eq!(rng.last_reseed_error(), Some(ReseedError));
+
+                let mut bytes = [0u8; 100];
+                let mut expected = [0u8; 100];
+                rng.fill_bytes(&mut bytes);
+                reference.fill_bytes(&mut expected);
+                assert_eq!(bytes, expected);
+
+                assert_eq!(rng.reseed(), Err(ReseedError));
+                assert_eq!(rng.bytes_since_reseed(), 0);
+            }
+
+            #[test]
+            fn test_backs_off_after_failure() {
+                let rng = Reseeding::with_entropy(
+                    $prng::from_seed(seed()),
+                    32,
+                    CountingSource::default(),
+                );
+
+                // The reseeds are attempted before the 3rd, 5th and 7th block.
+                for _ in 0..8 {
+                    let _ = rng.next();
+                }
+                assert_eq!(rng.bytes_since_reseed(), 32);
+                assert_eq!(rng.last_reseed_error(), Some(ReseedError));
+
+                // A failed reseed fills the rest of the slice at once.
+                let mut bytes = [0u8; 100];
+                rng.fill_bytes(&mut bytes);
+                assert_eq!(rng.bytes_since_reseed(), 100);
+            }
+
+            #[test]
+            fn test_never_reseeds_without_source() {
+                let rng = Reseeding::<$prng, CountingSource>::from_seed(seed());
+                let reference = $prng::from_seed(seed());
+
+                let mut bytes = vec![0u8; 2 * DEFAULT_RESEED_THRESHOLD as usize];
+                let mut expected = vec![0u8; 2 * DEFAULT_RESEED_THRESHOLD as usize];
+                rng.fill_bytes(&mut bytes);
+                reference.fill_bytes(&mut expected);
+                assert_eq!(bytes, expected);
+                assert_eq!(rng.last_reseed_error(), None);
+
+                assert_eq!(rng.reseed(), Err(ReseedError));
+                assert_eq!(rng.next(), reference.next());
+
+                let rng = Reseeding::<$prng, CountingSource>::from_u64(42);
+                let reference = $prng::from_u64(42);
+                for _ in 0..DEFAULT_RESEED_THRESHOLD / 16 + 1 {
+                    assert_eq!(rng.next(), reference.next());
+                }
+            }
+
+            #[test]
+            fn test_recovers_after_failure() {
+                let rng = Reseeding::with_entropy($prng::from_seed(seed()), 32, failing_entropy);
+                let _ = rng.next_blocks::<4>();
+                assert_eq!(rng.last_reseed_error(), None);
+
+                let _ = rng.next();
+                assert_eq!(rng.last_reseed_error(), Some(ReseedError));
+
+                rng.seed(fixed_seed());
+                assert_eq!(rng.last_reseed_error(), None);
+                assert_eq!(rng.bytes_since_reseed(), 0);
+            }
+
+            #[test]
+            #[cfg(feature = "std")]
+            fn test_interval() {
+                use std::time::Duration;
+
+                let rng =
+                    Reseeding::with_entropy($prng::from_seed(seed()), u64::MAX, fixed_entropy)
+                        .with_interval(Duration::ZERO);
+                let reseeded = $prng::from_seed(fixed_seed());
+                let expected = reseeded.next();
+
+                for _ in 0..4 {
+                    assert_eq!(rng.next(), expected);
+                }
+            }
+
+            #[test]
+            #[cfg(feature = "rand_core")]
+            fn test_rand_core() {
+                use rand_core::RngCore;
+
+                let mut rng = Reseeding::with_entropy($prng::from_seed(seed()), 64, fixed_entropy);
+                let reference = $prng::from_seed(seed());
+
+                for _ in 0..8 {
+                    assert_eq!(rng.next_u64(), reference.u64());
+                }
+                assert_eq!(rng.next_u64(), $prng::from_seed(fixed_seed()).u64());
+            }
+        }
+    };
+}
+
+test_reseeding!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed);
+test_reseeding!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed);
+test_reseeding!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed);
+test_reseeding!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed);
@@ -0,0 +1,94 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_rounds {
+    ($name:ident, $prng:ident, $reduced:ident, $rounds:expr, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                $seed::from(core::array::from_fn(|i| i as u8))
+            }
+
+            #[test]
+            fn test_full_rounds() {
+                let prng = $prng::from_seed(seed());
+                let full = $reduced::<$rounds>::from_seed(seed());
+
+                for _ in 0..64 {
+                    assert_eq!(prng.next(), full.next());
+                }
+            }
+
+            #[test]
+            fn test_reduced_rounds() {
+                let prng = $prng::from_seed(seed());
+                let reduced = $reduced::<5>::from_seed(seed());
+                let single = $reduced::<1>::from_seed(seed());
+
+                let block = prng.next();
+                assert_ne!(block, reduced.next());
+                assert_ne!(block, single.next());
+                assert_eq!(reduced.counter(), prng.counter());
+            }
+
+            #[test]
+            fn test_reduced_rounds_blocks() {
+                let prng = $reduced::<5>::from_seed(seed());
+                let reference = $reduced::<5>::from_seed(seed());
+
+                let blocks: [u128; 8] = prng.next_blocks();
+                assert_eq!(blocks, core::array::from_fn(|_| reference.next()));
+            }
+
+            #[test]
+            fn test_reduced_rounds_snapshot() {
+                let prng = $reduced::<3>::from_seed(seed());
+                prng.u32();
+
+                let state = prng.snapshot();
+                let restored = $reduced::<3>::from_seed($seed::default());
+                restored.restore(&state).unwrap();
+
+                for _ in 0..16 {
+                    assert_eq!(prng.u32(), restored.u32());
+                }
+            }
+
+            #[test]
+            fn test_reduced_rounds_snapshot_mismatch() {
+                let prng = $reduced::<5>::from_seed(seed());
+                let state = prng.snapshot();
+                assert_eq!(state.rounds(), 5);
+
+                let full = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+                assert_eq!(
+                    full.restore(&state),
+                    Err(RoundsMismatch {
+                        expected: $rounds,
+                        found: 5
+                    })
+                );
+                assert_eq!(full.next(), reference.next());
+            }
+        }
+    };
+}
+
+test_rounds!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64R, 10, Aes128Ctr64Seed);
+test_rounds!(
+    aes128_ctr128,
+    Aes128Ctr128,
+    Aes128Ctr128R,
+    10,
+    Aes128Ctr128Seed
+);
+test_rounds!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64R, 14, Aes256Ctr64Seed);
+test_rounds!(
+    aes256_ctr128,
+    Aes256Ctr128,
+    Aes256Ctr128R,
+    14,
+    Aes256Ctr128Seed
+);
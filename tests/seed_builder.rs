@@ -0,0 +1,147 @@
+use hex_literal::hex;
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+fn failing_source(_: &mut [u8]) -> Result<(), EntropyError> {
+    Err(EntropyError::Failed)
+}
+
+fn fixed_source(bytes: &mut [u8]) -> Result<(), EntropyError> {
+    bytes.fill(0x11);
+    Ok(())
+}
+
+// The expected seeds were cross-checked with the CMAC and the SP 800-108 KBKDF in counter mode of
+// the Python `cryptography` package. They must never change.
+#[test]
+fn test_known_answer() {
+    let builder = || {
+        SeedBuilder::new()
+            .bytes(&[0x11; 32], 256)
+            .bytes(b"label", 0)
+    };
+
+    let seed: Aes128Ctr64Seed = builder().build().unwrap();
+    assert_eq!(
+        seed.as_ref(),
+        hex!("d93bdab813c11d75dd5f6a5c00d77f6cdf6346cf59cc96b7c95f3867acd979a7")
+    );
+
+    let seed: Aes256Ctr128Seed = builder().build().unwrap();
+    assert_eq!(
+        seed.as_ref(),
+        hex!(
+            "321cd6db8c5cccf0abf70765dd5d6905aa339095883246d6a0b6c43a646e3aff"
+            "339e570233843f575843ed934a2123b4"
+        )
+    );
+}
+
+#[test]
+fn test_threshold() {
+    let result: Result<Aes128Ctr128Seed, _> = SeedBuilder::new().bytes(&[0; 16], 100).build();
+    assert_eq!(
+        result.err(),
+        Some(SeedBuilderError::InsufficientEntropy {
+            claimed: 100,
+            required: DEFAULT_ENTROPY_THRESHOLD,
+        })
+    );
+
+    let result: Result<Aes128Ctr128Seed, _> = SeedBuilder::new()
+        .with_threshold(100)
+        .bytes(&[0; 16], 100)
+        .build();
+    assert!(result.is_ok());
+
+    let error = SeedBuilder::new().build::<Aes128Ctr128Seed>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "insufficient entropy: the inputs claim 0 bits, but 256 bits are required"
+    );
+}
+
+#[test]
+#[should_panic(expected = "the threshold can't be larger than 256 bits")]
+fn test_threshold_too_large() {
+    let _ = SeedBuilder::new().with_threshold(257);
+}
+
+#[test]
+fn test_claims() {
+    // An input can't claim more than eight bits per byte.
+    let builder = SeedBuilder::new().bytes(&[0; 4], 1000);
+    assert_eq!(builder.entropy(), 32);
+
+    let builder = builder.source(&mut fixed_source, 8, 10);
+    assert_eq!(builder.entropy(), 42);
+
+    // A failing source claims no entropy.
+    let builder = builder.source(&mut failing_source, 32, 256);
+    assert_eq!(builder.entropy(), 42);
+
+    // The claims of the inputs add up beyond the threshold.
+    let builder = builder.bytes(&[0; 64], 512);
+    assert_eq!(builder.entropy(), 554);
+    assert!(builder.build::<Aes256Ctr64Seed>().is_ok());
+}
+
+#[test]
+fn test_inputs_are_separated() {
+    let build =
+        |builder: SeedBuilder| -> Aes128Ctr64Seed { builder.with_threshold(0).build().unwrap() };
+
+    let seed0 = build(SeedBuilder::new().bytes(b"ab", 0).bytes(b"c", 0));
+    let seed1 = build(SeedBuilder::new().bytes(b"a", 0).bytes(b"bc", 0));
+    let seed2 = build(SeedBuilder::new().bytes(b"abc", 0));
+    let seed3 = build(SeedBuilder::new().source(&mut fixed_source, 3, 0));
+
+    assert_ne!(seed0.as_ref(), seed1.as_ref());
+    assert_ne!(seed0.as_ref(), seed2.as_ref());
+    assert_ne!(seed1.as_ref(), seed2.as_ref());
+    assert_ne!(seed2.as_ref(), seed3.as_ref());
+
+    let seed4 = build(SeedBuilder::new().bytes(b"abc", 0));
+    assert_eq!(seed2.as_ref(), seed4.as_ref());
+}
+
+#[test]
+fn test_reproducible() {
+    let seed0: Aes128Ctr64Seed = SeedBuilder::new()
+        .source(&mut fixed_source, 100, 256)
+        .build()
+        .unwrap();
+    let seed1: Aes128Ctr64Seed = SeedBuilder::new()
+        .source(&mut fixed_source, 100, 256)
+        .build()
+        .unwrap();
+    assert_eq!(seed0.as_ref(), seed1.as_ref());
+}
+
+#[test]
+#[cfg(feature = "getrandom")]
+fn test_os_entropy() {
+    let builder = SeedBuilder::new().os_entropy();
+    assert_eq!(builder.entropy(), 256);
+
+    let seed0: Aes128Ctr64Seed = builder.build().unwrap();
+    let seed1: Aes128Ctr64Seed = SeedBuilder::new().os_entropy().build().unwrap();
+    assert_ne!(seed0.as_ref(), seed1.as_ref());
+}
+
+#[test]
+fn test_hardware_entropy() {
+    let builder = SeedBuilder::new().hardware_entropy();
+    if HardwareEntropy::is_available() {
+        assert_eq!(builder.entropy(), 256);
+    } else {
+        assert_eq!(builder.entropy(), 0);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_timestamp_claims_no_entropy() {
+    let builder = SeedBuilder::new().timestamp();
+    assert_eq!(builder.entropy(), 0);
+}
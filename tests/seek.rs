@@ -0,0 +1,103 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_seek {
+    ($name:ident, $prng:ident, $seed:ident, $seed_size:expr) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn test_set_counter() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let blocks: [u128; 10] = core::array::from_fn(|_| reference.next());
+
+                prng.set_counter(7);
+                assert_eq!(prng.counter(), 7);
+                assert_eq!(prng.next(), blocks[7]);
+
+                prng.set_counter(2);
+                assert_eq!(prng.next(), blocks[2]);
+                assert_eq!(prng.next(), blocks[3]);
+
+                prng.set_counter(0);
+                assert_eq!(prng.next(), blocks[0]);
+            }
+
+            #[test]
+            fn test_advance_and_rewind() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let blocks: [u128; 10] = core::array::from_fn(|_| reference.next());
+
+                assert_eq!(prng.next(), blocks[0]);
+                prng.advance(5);
+                assert_eq!(prng.counter(), 6);
+                assert_eq!(prng.next(), blocks[6]);
+
+                prng.rewind(3);
+                assert_eq!(prng.counter(), 4);
+                assert_eq!(prng.next(), blocks[4]);
+                assert_eq!(prng.next(), blocks[5]);
+
+                prng.advance(0);
+                assert_eq!(prng.next(), blocks[6]);
+            }
+
+            #[test]
+            fn test_seek_discards_remainder() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let block_0 = reference.next();
+                let block_1 = reference.next();
+
+                assert_eq!(prng.u32(), block_0 as u32);
+                prng.set_counter(1);
+                assert_eq!(prng.u32(), block_1 as u32);
+
+                prng.rewind(2);
+                assert_eq!(prng.u32(), block_0 as u32);
+            }
+
+            #[test]
+            fn test_seek_wraps_around() {
+                let prng = $prng::from_seed($seed::default());
+                let reference = $prng::from_seed($seed::default());
+
+                let block_0 = reference.next();
+                prng.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+
+                prng.rewind(1);
+                assert_eq!(prng.counter(), <$prng as Random>::Counter::MAX);
+                let last_block = prng.next();
+                assert_eq!(prng.counter(), 0);
+                assert_eq!(prng.next(), block_0);
+
+                prng.set_counter(<$prng as Random>::Counter::MAX);
+                assert_eq!(prng.next(), last_block);
+
+                prng.advance(<$prng as Random>::Counter::MAX);
+                assert_eq!(prng.next(), last_block);
+            }
+
+            #[test]
+            fn test_seek_keeps_nonce() {
+                let seed: [u8; $seed_size] = core::array::from_fn(|i| i as u8);
+                let prng = $prng::from_seed($seed::from(seed));
+                let reference = $prng::from_seed($seed::from(seed));
+
+                let _ = prng.next();
+                prng.set_counter(reference.counter());
+                assert_eq!(prng.next(), reference.next());
+            }
+        }
+    };
+}
+
+test_seek!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed, 32);
+test_seek!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed, 32);
+test_seek!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed, 48);
+test_seek!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed, 48);
@@ -0,0 +1,108 @@
+#[cfg(feature = "serde")]
+mod test {
+    use rand_aes::seeds::*;
+    use rand_aes::*;
+
+    macro_rules! test_serde {
+        ($name:ident, $prng:ident, $seed:ident, $seed_size:expr) => {
+            mod $name {
+                use super::*;
+
+                fn seed() -> $seed {
+                    let seed: [u8; $seed_size] = core::array::from_fn(|i| i as u8);
+                    $seed::from(seed)
+                }
+
+                fn hex(bytes: &[u8]) -> String {
+                    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+                }
+
+                #[test]
+                fn test_seed_json() {
+                    let json = serde_json::to_string(&seed()).unwrap();
+                    assert_eq!(json, format!("\"{}\"", hex(seed().as_ref())));
+
+                    let deserialized: $seed = serde_json::from_str(&json).unwrap();
+                    assert_eq!(deserialized.as_ref(), seed().as_ref());
+
+                    let deserialized: $seed = serde_json::from_str(&json.to_uppercase()).unwrap();
+                    assert_eq!(deserialized.as_ref(), seed().as_ref());
+                }
+
+                #[test]
+                fn test_seed_json_invalid() {
+                    let too_short = format!("\"{}\"", hex(&seed().as_ref()[1..]));
+                    assert!(serde_json::from_str::<$seed>(&too_short).is_err());
+
+                    let invalid = format!("\"{}\"", "x".repeat($seed_size * 2));
+                    assert!(serde_json::from_str::<$seed>(&invalid).is_err());
+                }
+
+                #[test]
+                fn test_seed_bincode() {
+                    let bytes = bincode::serialize(&seed()).unwrap();
+                    assert_eq!(bytes.len(), 8 + $seed_size);
+
+                    let deserialized: $seed = bincode::deserialize(&bytes).unwrap();
+                    assert_eq!(deserialized.as_ref(), seed().as_ref());
+                }
+
+                #[test]
+                fn test_state() {
+                    let prng = $prng::from_seed(seed());
+                    prng.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+                    let _ = prng.next();
+                    let _ = prng.u32();
+                    let state = prng.snapshot();
+
+                    let json = serde_json::to_string(&state).unwrap();
+                    let bytes = bincode::serialize(&state).unwrap();
+
+                    for state in [
+                        serde_json::from_str::<State<$seed>>(&json).unwrap(),
+                        bincode::deserialize::<State<$seed>>(&bytes).unwrap(),
+                    ] {
+                        let restored = $prng::from_seed($seed::from([0; $seed_size]));
+                        restored.restore(&state).unwrap();
+
+                        let reference = prng.clone();
+                        assert_eq!(restored.exhaustion_policy(), ExhaustionPolicy::Wrap);
+                        assert_eq!(restored.u32(), reference.u32());
+                        assert_eq!(restored.next(), reference.next());
+                    }
+                }
+            }
+        };
+    }
+
+    test_serde!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed, 32);
+    test_serde!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed, 32);
+    test_serde!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed, 48);
+    test_serde!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed, 48);
+
+    #[test]
+    fn test_state_invalid() {
+        let prng = Aes128Ctr64::from_seed(Aes128Ctr64Seed::default());
+        let mut bytes = bincode::serialize(&prng.snapshot()).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last - 1] = 2;
+
+        assert!(bincode::deserialize::<State<Aes128Ctr64Seed>>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_state_rounds() {
+        let prng = Aes128Ctr64R::<5>::from_seed(Aes128Ctr64Seed::default());
+        let mut bytes = bincode::serialize(&prng.snapshot()).unwrap();
+
+        let state = bincode::deserialize::<State<Aes128Ctr64Seed>>(&bytes).unwrap();
+        assert_eq!(state.rounds(), 5);
+        assert!(Aes128Ctr64::from_seed(Aes128Ctr64Seed::default())
+            .restore(&state)
+            .is_err());
+
+        let last = bytes.len() - 1;
+        bytes[last] = 0;
+        assert!(bincode::deserialize::<State<Aes128Ctr64Seed>>(&bytes).is_err());
+    }
+}
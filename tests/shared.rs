@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_shared {
+    ($name:ident, $shared:ident, $prng:ident, $shared_r:ident, $prng_r:ident, $seed:ident, $end_seed:expr) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                $seed::from(core::array::from_fn(|i| i as u8))
+            }
+
+            #[test]
+            fn test_send_sync() {
+                fn assert_send_sync<T: Send + Sync>() {}
+                assert_send_sync::<$shared>();
+            }
+
+            #[test]
+            fn test_same_stream() {
+                let shared = $shared::from_seed(seed());
+                let prng = $prng::from_seed(seed());
+
+                for _ in 0..64 {
+                    assert_eq!(shared.next(), prng.next());
+                }
+
+                let blocks: [u128; 8] = shared.next_blocks();
+                assert_eq!(blocks, prng.next_blocks::<8>());
+            }
+
+            #[test]
+            fn test_fill_bytes() {
+                let shared = $shared::from_seed(seed());
+                let prng = $prng::from_seed(seed());
+
+                let mut bytes = [0u8; 200];
+                shared.fill_bytes(&mut bytes);
+
+                let mut expected = [0u8; 208];
+                for chunk in expected.chunks_exact_mut(16) {
+                    chunk.copy_from_slice(&prng.next().to_le_bytes());
+                }
+                assert_eq!(bytes, expected[..200]);
+                assert_eq!(shared.next(), prng.next());
+            }
+
+            #[test]
+            fn test_threads() {
+                let shared = $shared::from_seed(seed());
+
+                let blocks: Vec<u128> = std::thread::scope(|scope| {
+                    let handles: Vec<_> = (0..4)
+                        .map(|_| {
+                            scope.spawn(|| {
+                                let mut blocks = Vec::new();
+                                for _ in 0..1000 {
+                                    blocks.push(shared.next());
+                                    blocks.extend(shared.next_blocks::<3>());
+                                }
+                                blocks
+                            })
+                        })
+                        .collect();
+
+                    handles
+                        .into_iter()
+                        .flat_map(|handle| handle.join().unwrap())
+                        .collect()
+                });
+
+                // Every block was handed out exactly once.
+                let prng = $prng::from_seed(seed());
+                let expected: HashSet<u128> = (0..16000).map(|_| prng.next()).collect();
+                let blocks_set: HashSet<u128> = blocks.iter().copied().collect();
+                assert_eq!(blocks.len(), 16000);
+                assert_eq!(blocks_set, expected);
+            }
+
+            #[test]
+            fn test_exhaustion() {
+                let shared = $shared::from_seed($end_seed);
+
+                assert_eq!(shared.remaining(), 3);
+                assert!(shared.try_next().is_ok());
+                assert_eq!(shared.remaining(), 2);
+                assert!(shared.try_next().is_ok());
+                assert!(shared.try_next().is_ok());
+                assert_eq!(shared.remaining(), 0);
+                assert_eq!(shared.try_next(), Err(CounterExhausted));
+                assert_eq!(shared.try_next(), Err(CounterExhausted));
+                assert_eq!(shared.remaining(), 0);
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_exhaustion_blocks() {
+                let shared = $shared::from_seed($end_seed);
+                let _: [u128; 4] = shared.next_blocks();
+            }
+
+            #[test]
+            fn test_exhaustion_blocks_keeps_remaining() {
+                let shared = $shared::from_seed($end_seed);
+                let prng = $prng::from_seed($end_seed);
+
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    let _: [u128; 4] = shared.next_blocks();
+                }));
+                assert!(result.is_err());
+
+                // The failed reservation doesn't discard the blocks that are still valid.
+                assert_eq!(shared.remaining(), 3);
+                let blocks: [u128; 3] = shared.next_blocks();
+                assert_eq!(blocks, core::array::from_fn(|_| prng.next()));
+                assert_eq!(shared.try_next(), Err(CounterExhausted));
+            }
+
+            #[test]
+            fn test_remaining_cap() {
+                let shared = $shared::from_seed(seed());
+                assert_eq!(shared.remaining(), 1 << 63);
+            }
+
+            #[test]
+            fn test_reduced_rounds() {
+                let shared = $shared_r::<5>::from_seed(seed());
+                let prng = $prng_r::<5>::from_seed(seed());
+
+                for _ in 0..16 {
+                    assert_eq!(shared.next(), prng.next());
+                }
+            }
+        }
+    };
+}
+
+test_shared!(
+    aes128_ctr64,
+    SharedAes128Ctr64,
+    Aes128Ctr64,
+    SharedAes128Ctr64R,
+    Aes128Ctr64R,
+    Aes128Ctr64Seed,
+    Aes128Ctr64Seed::new([1; 16], [2; 8], u64::MAX - 2)
+);
+test_shared!(
+    aes128_ctr128,
+    SharedAes128Ctr128,
+    Aes128Ctr128,
+    SharedAes128Ctr128R,
+    Aes128Ctr128R,
+    Aes128Ctr128Seed,
+    Aes128Ctr128Seed::new([1; 16], u128::MAX - 2)
+);
+test_shared!(
+    aes256_ctr64,
+    SharedAes256Ctr64,
+    Aes256Ctr64,
+    SharedAes256Ctr64R,
+    Aes256Ctr64R,
+    Aes256Ctr64Seed,
+    Aes256Ctr64Seed::new([1; 32], [2; 8], u64::MAX - 2)
+);
+test_shared!(
+    aes256_ctr128,
+    SharedAes256Ctr128,
+    Aes256Ctr128,
+    SharedAes256Ctr128R,
+    Aes256Ctr128R,
+    Aes256Ctr128Seed,
+    Aes256Ctr128Seed::new([1; 32], u128::MAX - 2)
+);
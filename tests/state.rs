@@ -0,0 +1,91 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_state {
+    ($name:ident, $prng:ident, $seed:ident, $seed_size:expr) => {
+        mod $name {
+            use super::*;
+
+            fn seed() -> $seed {
+                let seed: [u8; $seed_size] = core::array::from_fn(|i| i as u8);
+                $seed::from(seed)
+            }
+
+            #[test]
+            fn test_snapshot_restore() {
+                let prng = $prng::from_seed(seed());
+                let _ = prng.next();
+                let _ = prng.u64();
+                let _ = prng.u16();
+                let state = prng.snapshot();
+
+                let restored = $prng::from_seed($seed::from([0; $seed_size]));
+                restored.restore(&state).unwrap();
+
+                assert_eq!(restored.counter(), prng.counter());
+                for _ in 0..10 {
+                    assert_eq!(restored.u8(), prng.u8());
+                    assert_eq!(restored.u32(), prng.u32());
+                    assert_eq!(restored.bool(), prng.bool());
+                }
+                assert_eq!(restored.next(), prng.next());
+            }
+
+            #[test]
+            fn test_snapshot_is_independent() {
+                let prng = $prng::from_seed(seed());
+                let counter = prng.counter();
+                let state = prng.snapshot();
+                let expected: [u128; 5] = core::array::from_fn(|_| prng.next());
+
+                prng.restore(&state).unwrap();
+                assert_eq!(prng.counter(), counter);
+                assert_eq!(expected, core::array::from_fn(|_| prng.next()));
+            }
+
+            #[test]
+            fn test_snapshot_seed() {
+                let prng = $prng::from_seed(seed());
+                let _ = prng.next();
+                let state = prng.snapshot();
+
+                let reference = $prng::from_seed(seed());
+                let _ = reference.next();
+                let from_seed = $prng::from_seed(state.seed().clone());
+                assert_eq!(from_seed.next(), reference.next());
+            }
+
+            #[test]
+            fn test_snapshot_exhaustion() {
+                let prng = $prng::from_seed(seed());
+                prng.set_exhaustion_policy(ExhaustionPolicy::Panic);
+                prng.set_counter(<$prng as Random>::Counter::MAX);
+                let _ = prng.next();
+                let state = prng.snapshot();
+                assert_eq!(state.exhaustion_policy(), ExhaustionPolicy::Panic);
+
+                let restored = $prng::from_seed(seed());
+                restored.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+                restored.restore(&state).unwrap();
+                assert_eq!(restored.exhaustion_policy(), ExhaustionPolicy::Panic);
+                assert_eq!(restored.remaining(), 0);
+                assert_eq!(restored.try_next(), Err(CounterExhausted));
+            }
+        }
+    };
+}
+
+test_state!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed, 32);
+test_state!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed, 32);
+test_state!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed, 48);
+test_state!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed, 48);
+
+#[test]
+fn test_snapshot_stream() {
+    let prng = Aes128Ctr64::from_seed(Aes128Ctr64Seed::default()).with_stream(42);
+    let restored = Aes128Ctr64::from_seed(Aes128Ctr64Seed::default());
+    restored.restore(&prng.snapshot()).unwrap();
+
+    assert_eq!(restored.stream(), 42);
+    assert_eq!(restored.next(), prng.next());
+}
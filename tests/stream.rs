@@ -0,0 +1,72 @@
+use rand_aes::seeds::*;
+use rand_aes::*;
+
+macro_rules! test_stream {
+    ($name:ident, $prng:ident, $seed:ident, $seed_size:expr, $nonce_offset:expr) => {
+        mod $name {
+            use super::*;
+
+            fn seed_with_stream(stream: u64) -> $seed {
+                let mut seed = [0u8; $seed_size];
+                seed[$nonce_offset..$nonce_offset + 8].copy_from_slice(&stream.to_le_bytes());
+                $seed::from(seed)
+            }
+
+            #[test]
+            fn test_with_stream() {
+                let rng0 = $prng::from_seed(seed_with_stream(0));
+                let rng1 = rng0.with_stream(42);
+                let reference = $prng::from_seed(seed_with_stream(42));
+
+                assert_eq!(rng0.stream(), 0);
+                assert_eq!(rng1.stream(), 42);
+                assert_eq!(rng1.counter(), rng0.counter());
+                assert_eq!(rng1.next(), reference.next());
+                assert_ne!(rng0.next(), rng1.next());
+            }
+
+            #[test]
+            fn test_next_stream() {
+                let rng0 = $prng::from_seed(seed_with_stream(0));
+                let rng1 = rng0.next_stream();
+                let rng2 = rng0.next_stream();
+
+                assert_eq!(rng1.stream(), 0);
+                assert_eq!(rng2.stream(), 1);
+                assert_eq!(rng0.stream(), 2);
+
+                let reference = $prng::from_seed(seed_with_stream(1));
+                assert_eq!(rng2.next(), reference.next());
+            }
+
+            #[test]
+            fn test_stream_keeps_counter() {
+                let rng0 = $prng::from_seed(seed_with_stream(0));
+                let reference = $prng::from_seed(seed_with_stream(7));
+                let _ = reference.next();
+
+                let _ = rng0.u32();
+                let rng1 = rng0.with_stream(7);
+
+                assert_eq!(rng1.counter(), 1);
+                assert_eq!(rng1.u32(), reference.next() as u32);
+            }
+
+            #[test]
+            fn test_streams_dont_overlap_on_wrap() {
+                let rng0 = $prng::from_seed(seed_with_stream(0));
+                let rng1 = rng0.with_stream(1);
+                rng0.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+                rng0.set_counter(u64::MAX);
+
+                let last = rng0.next();
+                assert_eq!(rng0.stream(), 0);
+                assert_eq!(rng0.counter(), 0);
+                assert_ne!(last, rng1.next());
+            }
+        }
+    };
+}
+
+test_stream!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed, 32, 16);
+test_stream!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed, 48, 32);
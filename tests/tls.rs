@@ -166,4 +166,25 @@ mod test {
             );
         }
     }
+
+    #[test]
+    #[cfg(feature = "tls_auto_seed")]
+    fn test_auto_seed() {
+        let first = std::thread::spawn(rand_u128).join().unwrap();
+        let second = std::thread::spawn(rand_u128).join().unwrap();
+
+        rand_seed(Seed::default());
+        let default_seed = rand_u128();
+
+        assert_ne!(first, second);
+        assert_ne!(first, default_seed);
+        assert_ne!(second, default_seed);
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "tls_auto_seed")))]
+    #[should_panic(expected = "used before it was seeded")]
+    fn test_unseeded_panics() {
+        let _ = rand_u32();
+    }
 }
@@ -0,0 +1,63 @@
+// The registered entropy source is global, so these tests use their own test binary.
+#![cfg(feature = "tls")]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use rand_aes::tls::*;
+use rand_aes::EntropyError;
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+static FAIL: AtomicBool = AtomicBool::new(false);
+
+fn register() {
+    rand_set_entropy_source(|bytes: &mut [u8]| -> Result<(), EntropyError> {
+        if FAIL.load(Ordering::SeqCst) {
+            return Err(EntropyError::Failed);
+        }
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        bytes.fill(0x42);
+        Ok(())
+    });
+}
+
+fn fixed_seed() -> Seed {
+    Seed::from(core::array::from_fn(|_| 0x42))
+}
+
+// A single test, so that the tests don't interfere through the global state.
+#[test]
+fn test_registered_source() {
+    register();
+
+    let calls = CALLS.load(Ordering::SeqCst);
+    rand_seed_from_entropy();
+    assert_eq!(CALLS.load(Ordering::SeqCst), calls + 1);
+    let value = rand_u128();
+
+    rand_seed(fixed_seed());
+    assert_eq!(rand_u128(), value);
+
+    // The source is used by all threads.
+    let value = std::thread::spawn(|| {
+        rand_try_seed_from_entropy().unwrap();
+        rand_u128()
+    })
+    .join()
+    .unwrap();
+    rand_seed(fixed_seed());
+    assert_eq!(rand_u128(), value);
+
+    // A failing source keeps the current state.
+    FAIL.store(true, Ordering::SeqCst);
+    rand_seed(fixed_seed());
+    let _ = rand_u128();
+    assert_eq!(rand_try_seed_from_entropy(), Err(EntropyError::Failed));
+    let value = rand_u128();
+    rand_seed(fixed_seed());
+    let _ = rand_u128();
+    assert_eq!(rand_u128(), value);
+
+    let result = std::panic::catch_unwind(rand_seed_from_entropy);
+    assert!(result.is_err());
+    FAIL.store(false, Ordering::SeqCst);
+}
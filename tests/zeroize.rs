@@ -0,0 +1,43 @@
+#![cfg(feature = "zeroize")]
+
+use rand_aes::seeds::*;
+use rand_aes::*;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+macro_rules! test_zeroize {
+    ($name:ident, $prng:ident, $seed:ident) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn test_traits() {
+                fn assert_zeroize<T: Zeroize + ZeroizeOnDrop>() {}
+                assert_zeroize::<$seed>();
+            }
+
+            #[test]
+            fn test_zeroize() {
+                let mut seed = $seed::from(core::array::from_fn(|i| i as u8 + 1));
+                assert!(seed.as_ref().iter().all(|byte| *byte != 0));
+
+                seed.zeroize();
+                assert!(seed.as_ref().iter().all(|byte| *byte == 0));
+            }
+
+            #[test]
+            fn test_seeding() {
+                let seed = $seed::from(core::array::from_fn(|i| i as u8));
+                let prng0 = $prng::from_seed(seed.clone());
+                let prng1 = $prng::from_seed($seed::default());
+                prng1.seed(seed);
+
+                assert_eq!(prng0.next(), prng1.next());
+            }
+        }
+    };
+}
+
+test_zeroize!(aes128_ctr64, Aes128Ctr64, Aes128Ctr64Seed);
+test_zeroize!(aes128_ctr128, Aes128Ctr128, Aes128Ctr128Seed);
+test_zeroize!(aes256_ctr64, Aes256Ctr64, Aes256Ctr64Seed);
+test_zeroize!(aes256_ctr128, Aes256Ctr128, Aes256Ctr128Seed);